@@ -0,0 +1,19 @@
+-- Full-text search over restaurants (name, city, description)
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- French stemming with accents stripped, so "creperie" matches "Crêperies"
+CREATE TEXT SEARCH CONFIGURATION french_unaccent (COPY = french);
+ALTER TEXT SEARCH CONFIGURATION french_unaccent
+    ALTER MAPPING FOR hword, hword_part, word
+    WITH unaccent, french_stem;
+
+-- Weighted search document: name > city > description
+ALTER TABLE restaurants
+    ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('french_unaccent', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('french_unaccent', coalesce(city, '')), 'B') ||
+        setweight(to_tsvector('french_unaccent', coalesce(description, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_restaurants_search_vector ON restaurants USING GIN (search_vector);
@@ -0,0 +1,21 @@
+-- Visit log: one row per meal at a restaurant
+CREATE TABLE IF NOT EXISTS visits (
+    id SERIAL PRIMARY KEY,
+    restaurant_id INTEGER NOT NULL REFERENCES restaurants(id) ON DELETE CASCADE,
+    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    visited_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    rating SMALLINT CHECK (rating >= 1 AND rating <= 5),
+    party_size SMALLINT CHECK (party_size >= 1),
+    spend NUMERIC(10, 2) CHECK (spend >= 0),
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_visits_restaurant_id ON visits(restaurant_id, visited_on DESC);
+CREATE INDEX IF NOT EXISTS idx_visits_owner_id ON visits(owner_id);
+
+-- Mean rating of the rated visits, kept apart from the rating given by hand
+-- and NULL while no visit is rated
+ALTER TABLE restaurants ADD COLUMN IF NOT EXISTS visit_rating NUMERIC(3, 2)
+    CHECK (visit_rating >= 1 AND visit_rating <= 5);
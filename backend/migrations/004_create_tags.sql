@@ -0,0 +1,35 @@
+-- The repositories and docker/init.sql both use "wishlist_items"
+ALTER TABLE IF EXISTS wishlist RENAME TO wishlist_items;
+ALTER INDEX IF EXISTS idx_wishlist_owner_id RENAME TO idx_wishlist_items_owner_id;
+ALTER INDEX IF EXISTS idx_wishlist_priority RENAME TO idx_wishlist_items_priority;
+
+-- Tag categories: cuisine taxonomy, establishment type, or free-form
+CREATE TYPE tag_kind AS ENUM ('cuisine', 'type', 'custom');
+
+-- Create tags table
+CREATE TABLE IF NOT EXISTS tags (
+    id SERIAL PRIMARY KEY,
+    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    kind tag_kind NOT NULL DEFAULT 'custom',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Tag names are unique per user, case-insensitively
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_owner_name ON tags(owner_id, lower(name));
+
+-- Create join tables
+CREATE TABLE IF NOT EXISTS restaurant_tags (
+    restaurant_id INTEGER NOT NULL REFERENCES restaurants(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (restaurant_id, tag_id)
+);
+
+CREATE TABLE IF NOT EXISTS wishlist_item_tags (
+    wishlist_item_id INTEGER NOT NULL REFERENCES wishlist_items(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (wishlist_item_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_restaurant_tags_tag_id ON restaurant_tags(tag_id);
+CREATE INDEX IF NOT EXISTS idx_wishlist_item_tags_tag_id ON wishlist_item_tags(tag_id);
@@ -0,0 +1,25 @@
+-- Optional street address and WGS84 coordinates for restaurants and wishlist items.
+-- Coordinates are set together or not at all.
+ALTER TABLE restaurants
+    ADD COLUMN IF NOT EXISTS address VARCHAR(500),
+    ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180);
+
+ALTER TABLE restaurants
+    ADD CONSTRAINT restaurants_coordinates_pair
+    CHECK ((latitude IS NULL) = (longitude IS NULL));
+
+ALTER TABLE wishlist_items
+    ADD COLUMN IF NOT EXISTS address VARCHAR(500),
+    ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180);
+
+ALTER TABLE wishlist_items
+    ADD CONSTRAINT wishlist_items_coordinates_pair
+    CHECK ((latitude IS NULL) = (longitude IS NULL));
+
+-- Bounding-box prefilter for radius and map queries
+CREATE INDEX IF NOT EXISTS idx_restaurants_location
+    ON restaurants(owner_id, latitude, longitude) WHERE latitude IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_wishlist_items_location
+    ON wishlist_items(owner_id, latitude, longitude) WHERE latitude IS NOT NULL;
@@ -0,0 +1,33 @@
+-- Curated lists of restaurants and wishlist items
+CREATE TABLE IF NOT EXISTS lists (
+    id SERIAL PRIMARY KEY,
+    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    -- Set while the list is published, readable without login
+    share_token VARCHAR(64) UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_lists_owner_id ON lists(owner_id);
+
+-- Each entry points at exactly one restaurant or wishlist item
+CREATE TABLE IF NOT EXISTS list_entries (
+    id SERIAL PRIMARY KEY,
+    list_id INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    restaurant_id INTEGER REFERENCES restaurants(id) ON DELETE CASCADE,
+    wishlist_item_id INTEGER REFERENCES wishlist_items(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL CHECK (position >= 0),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT list_entries_one_target
+        CHECK ((restaurant_id IS NULL) <> (wishlist_item_id IS NULL)),
+    CONSTRAINT list_entries_restaurant_unique UNIQUE (list_id, restaurant_id),
+    CONSTRAINT list_entries_wishlist_item_unique UNIQUE (list_id, wishlist_item_id),
+    -- Checked at commit so entries can be shifted one by one
+    CONSTRAINT list_entries_position_unique UNIQUE (list_id, position) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX IF NOT EXISTS idx_list_entries_restaurant_id ON list_entries(restaurant_id);
+CREATE INDEX IF NOT EXISTS idx_list_entries_wishlist_item_id ON list_entries(wishlist_item_id);
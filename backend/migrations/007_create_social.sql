@@ -0,0 +1,36 @@
+-- Private profiles cannot be followed and stay out of activity feeds. Until
+-- now everything was private to its owner, so every profile, existing or
+-- new, starts private and going public is an opt-in.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_private BOOLEAN NOT NULL DEFAULT true;
+
+-- Who follows whom
+CREATE TABLE IF NOT EXISTS follows (
+    follower_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followee_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CONSTRAINT follows_not_self CHECK (follower_id <> followee_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_follows_followee_id ON follows(followee_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_follows_follower_id ON follows(follower_id, created_at DESC);
+
+-- Activity shown in followers' feeds
+CREATE TYPE activity_kind AS ENUM ('restaurant_added', 'restaurant_rated', 'wishlist_added');
+
+-- Name, city and rating are copied when the event happens; events go away
+-- with the restaurant or wishlist item they are about
+CREATE TABLE IF NOT EXISTS activity_events (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind activity_kind NOT NULL,
+    restaurant_id INTEGER REFERENCES restaurants(id) ON DELETE CASCADE,
+    wishlist_item_id INTEGER REFERENCES wishlist_items(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    city VARCHAR(255) NOT NULL,
+    rating SMALLINT CHECK (rating >= 1 AND rating <= 5),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_activity_events_user_created
+    ON activity_events(user_id, created_at DESC, id DESC);
@@ -0,0 +1,3 @@
+-- Remember which restaurants came from the wishlist, so statistics can
+-- report how much of the wishlist ends up being visited
+ALTER TABLE restaurants ADD COLUMN IF NOT EXISTS promoted_from_wishlist BOOLEAN NOT NULL DEFAULT false;
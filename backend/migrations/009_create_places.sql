@@ -0,0 +1,21 @@
+-- Offline autocomplete: restaurants imported from an OSM/CSV dump and
+-- searched by trigram similarity
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE TABLE IF NOT EXISTS places (
+    id SERIAL PRIMARY KEY,
+    source VARCHAR(50) NOT NULL,
+    source_id VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    city VARCHAR(255),
+    cuisine VARCHAR(255),
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (source, source_id),
+    CONSTRAINT places_coordinates_pair CHECK ((latitude IS NULL) = (longitude IS NULL)),
+    CONSTRAINT places_latitude_range CHECK (latitude BETWEEN -90 AND 90),
+    CONSTRAINT places_longitude_range CHECK (longitude BETWEEN -180 AND 180)
+);
+
+CREATE INDEX IF NOT EXISTS idx_places_name_trgm ON places USING GIN (lower(name) gin_trgm_ops);
@@ -0,0 +1,16 @@
+-- Details shown in autocomplete suggestions for imported places
+ALTER TABLE places
+    ADD COLUMN IF NOT EXISTS address VARCHAR(500),
+    ADD COLUMN IF NOT EXISTS postcode VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS opening_hours VARCHAR(500),
+    ADD COLUMN IF NOT EXISTS website VARCHAR(500);
+
+-- Upstream record a restaurant or wishlist item was created from, as
+-- "<source>:<id>" (e.g. "osm:node/123456")
+ALTER TABLE restaurants ADD COLUMN IF NOT EXISTS source_id VARCHAR(255);
+ALTER TABLE wishlist_items ADD COLUMN IF NOT EXISTS source_id VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_restaurants_source
+    ON restaurants(owner_id, source_id) WHERE source_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_wishlist_items_source
+    ON wishlist_items(owner_id, source_id) WHERE source_id IS NOT NULL;
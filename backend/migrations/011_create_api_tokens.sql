@@ -0,0 +1,19 @@
+-- Personal access tokens for scripts and mobile clients
+CREATE TYPE api_token_scope AS ENUM ('read_only', 'read_write');
+
+-- Only a SHA-256 hash of each token is stored; the prefix tells tokens apart
+-- in listings
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    token_prefix VARCHAR(16) NOT NULL,
+    scope api_token_scope NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Never expires when NULL
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_user_id ON api_tokens(user_id);
@@ -0,0 +1,55 @@
+-- docker/init.sql created an audit_log table filled by row triggers, which
+-- copied whole rows, password hashes included. Events are now recorded by
+-- the application: the triggers and the old table go.
+DROP TRIGGER IF EXISTS users_audit_trigger ON users;
+DROP TRIGGER IF EXISTS sessions_audit_trigger ON sessions;
+DROP FUNCTION IF EXISTS audit_trigger_function();
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'audit_log' AND column_name = 'table_name'
+    ) THEN
+        DROP TABLE audit_log;
+    END IF;
+END $$;
+
+-- docker/init.sql creates the type too
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'security_event_type') THEN
+        CREATE TYPE security_event_type AS ENUM (
+            'login',
+            'login_failed',
+            'account_locked',
+            'logout',
+            'password_changed',
+            'password_reset',
+            'session_revoked',
+            'username_changed',
+            'email_changed',
+            'two_factor_enabled',
+            'two_factor_disabled',
+            'api_token_created',
+            'api_token_revoked',
+            'data_deleted',
+            'account_deleted'
+        );
+    END IF;
+END $$;
+
+-- No foreign key on user_id: events outlive deleted accounts until the
+-- retention job removes them
+CREATE TABLE IF NOT EXISTS audit_log (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER,
+    event_type security_event_type NOT NULL,
+    details JSONB,
+    ip_address INET,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_user_id ON audit_log(user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
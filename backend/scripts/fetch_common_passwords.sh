@@ -0,0 +1,38 @@
+#!/bin/sh
+# Merge the 10,000 most common passwords of SecLists, gathered from public
+# breach corpora, into data/common_passwords.txt. Entries already in the file
+# (French and project-specific ones) are kept. Run from backend/ and commit
+# the result:
+#
+#   sh scripts/fetch_common_passwords.sh <seclists-commit-sha> <sha256>
+#
+# The download is pinned to a SecLists commit and checked against the sha256
+# of 10k-most-common.txt at that commit, so the password policy only changes
+# through a reviewed commit of the list.
+set -eu
+
+if [ "$#" -ne 2 ]; then
+    echo "usage: $0 <seclists-commit-sha> <sha256-of-10k-most-common.txt>" >&2
+    exit 2
+fi
+
+COMMIT="$1"
+SHA256="$2"
+URL="https://raw.githubusercontent.com/danielmiessler/SecLists/$COMMIT/Passwords/Common-Credentials/10k-most-common.txt"
+OUT="data/common_passwords.txt"
+TMP="$(mktemp)"
+trap 'rm -f "$TMP" "$OUT.new"' EXIT
+
+curl -fsSL "$URL" > "$TMP"
+echo "$SHA256  $TMP" | sha256sum -c - > /dev/null || {
+    echo "Checksum mismatch for $URL, keeping $OUT" >&2
+    exit 1
+}
+
+{
+    echo "# Most common leaked passwords, one per line, compared case-insensitively."
+    echo "# Merged with SecLists 10k-most-common at $COMMIT by scripts/fetch_common_passwords.sh"
+    { grep -v '^#' "$OUT" || true; tr -d '\r' < "$TMP"; } | awk 'NF && !seen[tolower($0)]++'
+} > "$OUT.new"
+mv "$OUT.new" "$OUT"
+echo "$(grep -vc '^#' "$OUT") passwords in $OUT"
@@ -1,14 +1,52 @@
-use utoipa::OpenApi;
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 
-use crate::domain::user::{RegisterUserCommand, LoginCommand, UserResponse};
+use crate::domain::user::{
+    RegisterUserCommand, LoginCommand, UserResponse, ChangePasswordRequest, ChangeUsernameRequest,
+    ChangeEmailRequest, DeleteAccountRequest
+};
+use crate::domain::session::{SessionResponse, RevokeSessionsResponse};
+use crate::domain::user_token::{VerifyEmailRequest, PasswordResetRequest, PasswordResetConfirmRequest};
+use crate::domain::two_factor::{
+    TwoFactorStatusResponse, TwoFactorSetupResponse, TwoFactorCodeRequest, RecoveryCodesResponse,
+    LoginTwoFactorRequest, LoginChallengeResponse
+};
+use crate::domain::audit::{SecurityEventType, SecurityEventResponse};
+use crate::domain::api_token::{
+    ApiTokenScope, CreateApiTokenRequest, ApiTokenResponse, CreatedApiTokenResponse
+};
 use crate::domain::restaurant::{
-    CreateRestaurantRequest, UpdateRestaurantRequest, RestaurantResponse, RestaurantStatsResponse
+    CreateRestaurantRequest, UpdateRestaurantRequest, RestaurantResponse, RestaurantStatsResponse,
+    RestaurantSearchRequest, RestaurantSearchResponse, RestaurantSearchResult, RestaurantSearchHighlights,
+    NearbyRestaurantResponse, RatingBucket, CityCount, MonthCount, WishlistConversionStats
 };
 use crate::domain::wishlist::{
-    CreateWishlistRequest, UpdateWishlistRequest, WishlistItemResponse, WishlistCountResponse
+    CreateWishlistRequest, UpdateWishlistRequest, WishlistItemResponse, WishlistCountResponse,
+    NearbyWishlistItemResponse
+};
+use crate::domain::geo::{NearbyQuery, WithinQuery};
+use crate::domain::restaurant::RestaurantSort;
+use crate::domain::wishlist::WishlistSort;
+use crate::domain::pagination::{
+    SortDirection, RestaurantPage, WishlistPage, ActivityPage, FollowPage, SecurityEventPage
+};
+use crate::domain::visit::{CreateVisitRequest, UpdateVisitRequest, VisitResponse};
+use crate::domain::tag::{CreateTagRequest, UpdateTagRequest, SetTagsRequest, TagResponse, TagKind};
+use crate::domain::list::{
+    CreateListRequest, UpdateListRequest, AddListEntryRequest, UpdateListEntryRequest,
+    ReorderListRequest, ListResponse, ListDetailResponse, ListEntryResponse, ListEntryKind,
+    PublicListResponse, PublicListEntryResponse
+};
+use crate::domain::social::{
+    ActivityKind, ActivityResponse, FollowResponse, UserProfileResponse, PrivacySettingsRequest,
+    PrivacySettingsResponse
+};
+use crate::domain::export::{ExportFormat, ExportAccount, ExportDocument};
+use crate::domain::import::{ImportTarget, ImportUpload, ImportReport, ImportRowReport, ImportRowStatus};
+use crate::domain::autocomplete::{
+    AutocompleteRequest, AutocompleteResponse, AutocompleteSuggestion, SuggestionOrigin,
+    AutocompleteCacheStats,
 };
-use crate::domain::autocomplete::{AutocompleteRequest, AutocompleteResponse};
 use crate::models::ApiResponse;
 use crate::error::AppError;
 
@@ -18,9 +56,32 @@ use crate::error::AppError;
         // Auth endpoints
         crate::routes::register,
         crate::routes::login,
+        crate::routes::login_two_factor,
         crate::routes::logout,
         crate::routes::get_user_info,
         crate::routes::check_auth,
+        crate::routes::verify_email,
+        crate::routes::resend_verification,
+        crate::routes::request_password_reset,
+        crate::routes::confirm_password_reset,
+        crate::routes::list_sessions,
+        crate::routes::revoke_other_sessions,
+        crate::routes::revoke_session,
+        crate::routes::create_api_token,
+        crate::routes::get_api_tokens,
+        crate::routes::revoke_api_token,
+        crate::routes::get_two_factor_status,
+        crate::routes::setup_two_factor,
+        crate::routes::enable_two_factor,
+        crate::routes::disable_two_factor,
+        crate::routes::regenerate_recovery_codes,
+        crate::routes::get_security_events,
+
+        // Account endpoints
+        crate::routes::change_password,
+        crate::routes::change_username,
+        crate::routes::change_email,
+        crate::routes::delete_account,
         
         // Restaurant endpoints
         crate::routes::create_restaurant,
@@ -29,7 +90,16 @@ use crate::error::AppError;
         crate::routes::update_restaurant,
         crate::routes::delete_restaurant,
         crate::routes::get_restaurant_stats,
+        crate::routes::get_restaurants_nearby,
+        crate::routes::get_restaurants_within,
         crate::routes::search_restaurants,
+
+        // Visit endpoints
+        crate::routes::create_visit,
+        crate::routes::get_visits,
+        crate::routes::get_visit,
+        crate::routes::update_visit,
+        crate::routes::delete_visit,
         
         // Wishlist endpoints
         crate::routes::create_wishlist_item,
@@ -38,7 +108,48 @@ use crate::error::AppError;
         crate::routes::update_wishlist_item,
         crate::routes::delete_wishlist_item,
         crate::routes::get_wishlist_count,
+        crate::routes::get_wishlist_items_nearby,
+        crate::routes::get_wishlist_items_within,
         crate::routes::promote_wishlist_item,
+
+        // Tag endpoints
+        crate::routes::create_tag,
+        crate::routes::get_tags,
+        crate::routes::update_tag,
+        crate::routes::delete_tag,
+        crate::routes::get_restaurant_tags,
+        crate::routes::set_restaurant_tags,
+        crate::routes::get_wishlist_item_tags,
+        crate::routes::set_wishlist_item_tags,
+
+        // List endpoints
+        crate::routes::create_list,
+        crate::routes::get_lists,
+        crate::routes::get_list,
+        crate::routes::update_list,
+        crate::routes::delete_list,
+        crate::routes::share_list,
+        crate::routes::unshare_list,
+        crate::routes::reorder_list,
+        crate::routes::add_list_entry,
+        crate::routes::update_list_entry,
+        crate::routes::remove_list_entry,
+        crate::routes::get_public_list,
+
+        // Social endpoints
+        crate::routes::get_feed,
+        crate::routes::get_user_profile,
+        crate::routes::follow_user,
+        crate::routes::unfollow_user,
+        crate::routes::get_user_followers,
+        crate::routes::get_user_following,
+        crate::routes::get_user_activity,
+        crate::routes::get_privacy_settings,
+        crate::routes::update_privacy_settings,
+
+        // Import/export endpoints
+        crate::routes::export_collection,
+        crate::routes::import_collection,
         
         // Health endpoints
         crate::handlers::health_handler::health_check,
@@ -47,6 +158,7 @@ use crate::error::AppError;
         // Autocomplete endpoints
         crate::routes::search_restaurants_autocomplete,
         crate::routes::search_wishlist_autocomplete,
+        crate::routes::get_autocomplete_cache_stats,
     ),
     components(
         schemas(
@@ -54,24 +166,107 @@ use crate::error::AppError;
             RegisterUserCommand,
             LoginCommand,
             UserResponse,
+            VerifyEmailRequest,
+            PasswordResetRequest,
+            PasswordResetConfirmRequest,
+            SessionResponse,
+            RevokeSessionsResponse,
+            ApiTokenScope,
+            CreateApiTokenRequest,
+            ApiTokenResponse,
+            CreatedApiTokenResponse,
+            TwoFactorStatusResponse,
+            TwoFactorSetupResponse,
+            TwoFactorCodeRequest,
+            RecoveryCodesResponse,
+            LoginTwoFactorRequest,
+            LoginChallengeResponse,
+            SecurityEventType,
+            SecurityEventResponse,
+            SecurityEventPage,
+            ChangePasswordRequest,
+            ChangeUsernameRequest,
+            ChangeEmailRequest,
+            DeleteAccountRequest,
             CreateRestaurantRequest,
             UpdateRestaurantRequest,
             RestaurantResponse,
             RestaurantStatsResponse,
+            RatingBucket,
+            CityCount,
+            MonthCount,
+            WishlistConversionStats,
+            RestaurantSearchRequest,
+            RestaurantSearchResponse,
+            RestaurantSearchResult,
+            RestaurantSearchHighlights,
+            RestaurantSort,
+            RestaurantPage,
+            NearbyRestaurantResponse,
+            CreateVisitRequest,
+            UpdateVisitRequest,
+            VisitResponse,
             CreateWishlistRequest,
             UpdateWishlistRequest,
             WishlistItemResponse,
             WishlistCountResponse,
+            WishlistSort,
+            WishlistPage,
+            NearbyWishlistItemResponse,
+            SortDirection,
+            NearbyQuery,
+            WithinQuery,
+            CreateTagRequest,
+            UpdateTagRequest,
+            SetTagsRequest,
+            TagResponse,
+            TagKind,
+            CreateListRequest,
+            UpdateListRequest,
+            AddListEntryRequest,
+            UpdateListEntryRequest,
+            ReorderListRequest,
+            ListResponse,
+            ListDetailResponse,
+            ListEntryResponse,
+            ListEntryKind,
+            PublicListResponse,
+            PublicListEntryResponse,
+            ActivityKind,
+            ActivityResponse,
+            ActivityPage,
+            FollowResponse,
+            FollowPage,
+            UserProfileResponse,
+            PrivacySettingsRequest,
+            PrivacySettingsResponse,
+            ExportFormat,
+            ExportAccount,
+            ExportDocument,
+            ImportTarget,
+            ImportUpload,
+            ImportReport,
+            ImportRowReport,
+            ImportRowStatus,
             AutocompleteRequest,
             AutocompleteResponse,
+            AutocompleteSuggestion,
+            SuggestionOrigin,
+            AutocompleteCacheStats,
             ApiResponse<serde_json::Value>,
             AppError,
         )
     ),
     tags(
         (name = "Authentication", description = "User authentication and session management"),
+        (name = "Account", description = "Change the password, username or email, or delete the account"),
         (name = "Restaurants", description = "Restaurant management operations"),
+        (name = "Visits", description = "Visit log: every meal at a restaurant"),
         (name = "Wishlist", description = "Wishlist management operations"),
+        (name = "Tags", description = "Tags and cuisine taxonomy for restaurants and wishlist items"),
+        (name = "Lists", description = "Curated lists of restaurants and wishlist items, shareable by link"),
+        (name = "Social", description = "Follow other users and see what they eat, with a per-user privacy setting"),
+        (name = "Import/Export", description = "Back up the whole collection or load it from a spreadsheet"),
         (name = "Autocomplete", description = "Restaurant autocomplete suggestions using OpenDataSoft API"),
         (name = "Health", description = "Health check and monitoring endpoints"),
     ),
@@ -92,18 +287,37 @@ use crate::error::AppError;
         (url = "http://localhost:8080", description = "Local development server"),
         (url = "https://api.gourmestre.app", description = "Production server")
     ),
+    modifiers(&SecurityAddon),
     security(
-        ("session_auth" = [])
+        ("session_auth" = []),
+        ("api_token" = [])
     )
 )]
 pub struct ApiDoc;
 
+/// Declares the authentication schemes listed under `security`
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "session_auth",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                "gourmestre_session",
+                "Session cookie set by login",
+            ))),
+        );
+        components.add_security_scheme("api_token", ApiDoc::security_scheme());
+    }
+}
+
 impl ApiDoc {
     pub fn security_scheme() -> SecurityScheme {
         SecurityScheme::Http(
             HttpBuilder::new()
                 .scheme(HttpAuthScheme::Bearer)
-                .description(Some("Session-based authentication using cookies"))
+                .description(Some("Personal API token created with POST /api/v1/auth/tokens"))
                 .build()
         )
     }
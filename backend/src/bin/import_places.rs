@@ -0,0 +1,79 @@
+//! Load an OSM/CSV dump of restaurants into the `places` table used by the
+//! local autocomplete provider (`AUTOCOMPLETE_PROVIDER=local`).
+//!
+//! Usage: `import_places <file.csv> [source]`
+//!
+//! The OpenDataSoft CSV export of `osm-france-food-service` (`;`-separated)
+//! and plain `name,city,cuisine,latitude,longitude` files are both accepted.
+//! Optional `address`, `postcode`, `opening_hours` and `website` columns (or
+//! their OSM `addr:*` counterparts) are shown in suggestions.
+//! Rows are keyed on `source` (defaults to `osm`) and their identifier, so
+//! importing a newer dump updates the places already known.
+
+use gourmestre::config::Config;
+use gourmestre::database;
+use gourmestre::domain::autocomplete::{ImportPlace, PlaceDumpRow};
+use gourmestre::repositories::place_repository::{PlaceRepository, PostgresPlaceRepository};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek};
+
+/// Number of places written per statement
+const BATCH_SIZE: usize = 1000;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().ok_or("usage: import_places <file.csv> [source]")?;
+    let source = args.next().unwrap_or_else(|| "osm".to_string());
+
+    let config = Config::from_env()?;
+    let pool = database::create_pool(&config).await?;
+    let repo = PostgresPlaceRepository::new(pool);
+
+    // The delimiter is whichever of ';' and ',' the header uses most
+    let mut file = BufReader::new(File::open(&path)?);
+    let mut header = String::new();
+    file.read_line(&mut header)?;
+    let delimiter = if header.matches(';').count() > header.matches(',').count() { b';' } else { b',' };
+    file.rewind()?;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(file);
+    let headers = reader.headers()?.clone();
+    let mut headers = csv::StringRecord::from(
+        headers.iter().map(|h| h.trim().to_lowercase()).collect::<Vec<_>>(),
+    );
+    headers.trim();
+
+    let (mut imported, mut skipped) = (0u64, 0u64);
+    let mut batch: HashMap<String, ImportPlace> = HashMap::with_capacity(BATCH_SIZE);
+
+    for record in reader.records() {
+        let place = record
+            .ok()
+            .and_then(|record| record.deserialize::<PlaceDumpRow>(Some(&headers)).ok())
+            .and_then(PlaceDumpRow::to_command);
+        match place {
+            Some(place) => {
+                batch.insert(place.source_id.clone(), place);
+            }
+            None => skipped += 1,
+        }
+
+        if batch.len() >= BATCH_SIZE {
+            let places: Vec<ImportPlace> = batch.drain().map(|(_, place)| place).collect();
+            imported += repo.upsert_many(&source, &places).await?;
+        }
+    }
+
+    if !batch.is_empty() {
+        let places: Vec<ImportPlace> = batch.drain().map(|(_, place)| place).collect();
+        imported += repo.upsert_many(&source, &places).await?;
+    }
+
+    println!("Imported {} places from {} ({} rows skipped)", imported, path, skipped);
+    Ok(())
+}
@@ -1,3 +1,7 @@
+use crate::domain::autocomplete::AutocompleteProviderKind;
+use crate::domain::mail::MailerKind;
+use crate::domain::rate_limit::RateLimitStoreKind;
+use crate::services::password_hasher::PasswordHashAlgorithm;
 use crate::error::{AppError, Result};
 use std::env;
 
@@ -6,8 +10,44 @@ pub struct Config {
     pub database_url: String,
     pub server_host: String,
     pub server_port: u16,
+    pub trust_proxy: bool,
     pub session_key: String,
+    pub password_hasher: PasswordHashAlgorithm,
     pub bcrypt_cost: u32,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    pub password_min_length: usize,
+    pub password_reject_common: bool,
+    pub password_reject_username: bool,
+    pub session_ttl_hours: i64,
+    pub session_cleanup_interval_secs: u64,
+    pub account_unlock_interval_secs: u64,
+    pub audit_log_retention_days: u32,
+    pub audit_log_cleanup_interval_secs: u64,
+    pub autocomplete_provider: AutocompleteProviderKind,
+    pub opendatasoft_url: String,
+    pub autocomplete_cache_capacity: usize,
+    pub autocomplete_cache_ttl_secs: u64,
+    pub autocomplete_cache_stale_secs: u64,
+    pub app_base_url: String,
+    pub mailer: MailerKind,
+    pub mail_from: String,
+    pub mail_dir: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub smtp_starttls: bool,
+    pub rate_limit_enabled: bool,
+    pub rate_limit_store: RateLimitStoreKind,
+    pub rate_limit_prune_interval_secs: u64,
+    pub rate_limit_auth_per_minute: u32,
+    pub rate_limit_auth_burst: u32,
+    pub rate_limit_autocomplete_per_minute: u32,
+    pub rate_limit_autocomplete_burst: u32,
+    pub rate_limit_api_per_minute: u32,
+    pub rate_limit_api_burst: u32,
 }
 
 impl Config {
@@ -23,15 +63,143 @@ impl Config {
                 .unwrap_or_else(|_| "8080".to_string())
                 .parse()
                 .map_err(|_| AppError::Internal("Invalid SERVER_PORT".into()))?,
+            trust_proxy: env::var("TRUST_PROXY")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid TRUST_PROXY".into()))?,
             session_key: env::var("SESSION_KEY")
                 .unwrap_or_else(|_| {
                     // Generate a 64-byte key for development (in production, use a proper environment variable)
                     "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!@".to_string()
                 }),
+            password_hasher: env::var("PASSWORD_HASHER")
+                .unwrap_or_else(|_| "argon2id".to_string())
+                .parse()?,
             bcrypt_cost: env::var("BCRYPT_COST")
                 .unwrap_or_else(|_| "12".to_string())
                 .parse()
                 .map_err(|_| AppError::Internal("Invalid BCRYPT_COST".into()))?,
+            argon2_memory_kib: env::var("ARGON2_MEMORY_KIB")
+                .unwrap_or_else(|_| "19456".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid ARGON2_MEMORY_KIB".into()))?,
+            argon2_iterations: env::var("ARGON2_ITERATIONS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid ARGON2_ITERATIONS".into()))?,
+            argon2_parallelism: env::var("ARGON2_PARALLELISM")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid ARGON2_PARALLELISM".into()))?,
+            password_min_length: env::var("PASSWORD_MIN_LENGTH")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()
+                .ok()
+                .filter(|length| *length >= 1)
+                .ok_or_else(|| AppError::Internal("Invalid PASSWORD_MIN_LENGTH".into()))?,
+            password_reject_common: env::var("PASSWORD_REJECT_COMMON")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid PASSWORD_REJECT_COMMON".into()))?,
+            password_reject_username: env::var("PASSWORD_REJECT_USERNAME")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid PASSWORD_REJECT_USERNAME".into()))?,
+            session_ttl_hours: env::var("SESSION_TTL_HOURS")
+                .unwrap_or_else(|_| "168".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid SESSION_TTL_HOURS".into()))?,
+            session_cleanup_interval_secs: env::var("SESSION_CLEANUP_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid SESSION_CLEANUP_INTERVAL_SECS".into()))?,
+            account_unlock_interval_secs: env::var("ACCOUNT_UNLOCK_INTERVAL_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid ACCOUNT_UNLOCK_INTERVAL_SECS".into()))?,
+            audit_log_retention_days: env::var("AUDIT_LOG_RETENTION_DAYS")
+                .unwrap_or_else(|_| "365".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid AUDIT_LOG_RETENTION_DAYS".into()))?,
+            audit_log_cleanup_interval_secs: env::var("AUDIT_LOG_CLEANUP_INTERVAL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid AUDIT_LOG_CLEANUP_INTERVAL_SECS".into()))?,
+            autocomplete_provider: env::var("AUTOCOMPLETE_PROVIDER")
+                .unwrap_or_else(|_| "opendatasoft".to_string())
+                .parse()?,
+            opendatasoft_url: env::var("OPENDATASOFT_URL")
+                .unwrap_or_else(|_| "https://public.opendatasoft.com/api/records/1.0/search/".to_string()),
+            autocomplete_cache_capacity: env::var("AUTOCOMPLETE_CACHE_CAPACITY")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid AUTOCOMPLETE_CACHE_CAPACITY".into()))?,
+            autocomplete_cache_ttl_secs: env::var("AUTOCOMPLETE_CACHE_TTL_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid AUTOCOMPLETE_CACHE_TTL_SECS".into()))?,
+            autocomplete_cache_stale_secs: env::var("AUTOCOMPLETE_CACHE_STALE_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid AUTOCOMPLETE_CACHE_STALE_SECS".into()))?,
+            app_base_url: env::var("APP_BASE_URL")
+                .unwrap_or_else(|_| "http://localhost:5173".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            mailer: env::var("MAILER")
+                .unwrap_or_else(|_| "smtp".to_string())
+                .parse()?,
+            mail_from: env::var("MAIL_FROM")
+                .unwrap_or_else(|_| "Gourmestre <no-reply@gourmestre.app>".to_string()),
+            mail_dir: env::var("MAIL_DIR")
+                .unwrap_or_else(|_| "./mail".to_string()),
+            smtp_host: env::var("SMTP_HOST")
+                .unwrap_or_else(|_| "localhost".to_string()),
+            smtp_port: env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid SMTP_PORT".into()))?,
+            smtp_username: env::var("SMTP_USERNAME").ok().filter(|value| !value.is_empty()),
+            smtp_password: env::var("SMTP_PASSWORD").ok().filter(|value| !value.is_empty()),
+            smtp_starttls: env::var("SMTP_STARTTLS")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid SMTP_STARTTLS".into()))?,
+            rate_limit_enabled: env::var("RATE_LIMIT_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_ENABLED".into()))?,
+            rate_limit_store: env::var("RATE_LIMIT_STORE")
+                .unwrap_or_else(|_| "memory".to_string())
+                .parse()?,
+            rate_limit_prune_interval_secs: env::var("RATE_LIMIT_PRUNE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_PRUNE_INTERVAL_SECS".into()))?,
+            rate_limit_auth_per_minute: env::var("RATE_LIMIT_AUTH_PER_MINUTE")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_AUTH_PER_MINUTE".into()))?,
+            rate_limit_auth_burst: env::var("RATE_LIMIT_AUTH_BURST")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_AUTH_BURST".into()))?,
+            rate_limit_autocomplete_per_minute: env::var("RATE_LIMIT_AUTOCOMPLETE_PER_MINUTE")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_AUTOCOMPLETE_PER_MINUTE".into()))?,
+            rate_limit_autocomplete_burst: env::var("RATE_LIMIT_AUTOCOMPLETE_BURST")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_AUTOCOMPLETE_BURST".into()))?,
+            rate_limit_api_per_minute: env::var("RATE_LIMIT_API_PER_MINUTE")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_API_PER_MINUTE".into()))?,
+            rate_limit_api_burst: env::var("RATE_LIMIT_API_BURST")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .map_err(|_| AppError::Internal("Invalid RATE_LIMIT_API_BURST".into()))?,
         })
     }
 }
\ No newline at end of file
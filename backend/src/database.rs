@@ -1,5 +1,5 @@
 use crate::config::Config;
-use crate::error::{AppError, Result};
+use crate::error::Result;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::{Pool, Postgres};
 use std::str::FromStr;
@@ -0,0 +1,161 @@
+use crate::domain::token::{generate_token, hash_token};
+use crate::domain::user::UserId;
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Maximum number of API tokens per user
+pub const MAX_API_TOKENS: usize = 20;
+
+/// Marks a string as a Gourmestre API token
+const TOKEN_PREFIX: &str = "gmt_";
+
+/// Characters of a token kept in clear to tell tokens apart
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// Unique identifier for an API token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct ApiTokenId(pub i32);
+
+/// What an API token may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "api_token_scope", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[schema(example = "read_only")]
+pub enum ApiTokenScope {
+    /// Only GET requests
+    ReadOnly,
+    /// Everything a logged in user can do, except managing sessions and tokens
+    ReadWrite,
+}
+
+impl ApiTokenScope {
+    /// Whether a request with this HTTP method is allowed
+    pub fn allows(self, method: &actix_web::http::Method) -> bool {
+        match self {
+            ApiTokenScope::ReadOnly => method.is_safe(),
+            ApiTokenScope::ReadWrite => true,
+        }
+    }
+}
+
+/// API token entity. The token itself is only known to its owner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: ApiTokenId,
+    pub user_id: UserId,
+    pub name: String,
+    pub token_prefix: String,
+    pub scope: ApiTokenScope,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Generate a new API token: a recognizable prefix and a random token
+pub fn generate_api_token() -> String {
+    format!("{}{}", TOKEN_PREFIX, generate_token())
+}
+
+/// Command to create a new API token
+#[derive(Debug, Clone)]
+pub struct CreateApiToken {
+    pub user_id: UserId,
+    pub name: String,
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub scope: ApiTokenScope,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request to create a new API token
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateApiTokenRequest {
+    /// Name reminding what the token is for (1-100 characters)
+    #[schema(example = "Backup script")]
+    pub name: String,
+    /// What the token may do
+    pub scope: ApiTokenScope,
+    /// Days until the token expires (1-365), never when omitted
+    #[schema(example = 90)]
+    pub expires_in_days: Option<i64>,
+}
+
+impl CreateApiTokenRequest {
+    /// Convert to domain command for a freshly generated token
+    pub fn to_command(self, user_id: UserId, token: &str) -> Result<CreateApiToken> {
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::Validation(
+                "Token name must be between 1 and 100 characters".into(),
+            ));
+        }
+
+        let expires_at = match self.expires_in_days {
+            Some(days) if !(1..=365).contains(&days) => {
+                return Err(AppError::Validation(
+                    "Token expiry must be between 1 and 365 days".into(),
+                ));
+            }
+            Some(days) => Some(Utc::now() + Duration::days(days)),
+            None => None,
+        };
+
+        Ok(CreateApiToken {
+            user_id,
+            name: name.to_string(),
+            token_hash: hash_token(token),
+            token_prefix: token.chars().take(DISPLAY_PREFIX_LEN).collect(),
+            scope: self.scope,
+            expires_at,
+        })
+    }
+}
+
+/// Response for an API token
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiTokenResponse {
+    /// Token ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// Token name
+    #[schema(example = "Backup script")]
+    pub name: String,
+    /// First characters of the token
+    #[schema(example = "gmt_q1Xh6o7s")]
+    pub prefix: String,
+    /// What the token may do
+    pub scope: ApiTokenScope,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Expiry timestamp, if any
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last time the token was used, to the minute
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiToken> for ApiTokenResponse {
+    fn from(token: ApiToken) -> Self {
+        Self {
+            id: token.id.0,
+            name: token.name,
+            prefix: token.token_prefix,
+            scope: token.scope,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+        }
+    }
+}
+
+/// Response for a newly created API token, the only one showing the token
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CreatedApiTokenResponse {
+    /// The token, to send as `Authorization: Bearer <token>`
+    #[schema(example = "gmt_q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub token: String,
+    /// Token details
+    pub api_token: ApiTokenResponse,
+}
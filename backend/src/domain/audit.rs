@@ -0,0 +1,116 @@
+use crate::domain::pagination::PageRequest;
+use crate::domain::session::ClientInfo;
+use crate::domain::social::Chronological;
+use crate::domain::user::UserId;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Unique identifier for an audit log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct AuditEventId(pub i32);
+
+/// Security-relevant things that happen to an account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "security_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[schema(example = "login")]
+pub enum SecurityEventType {
+    /// A login opened a session
+    Login,
+    /// A wrong password or two-factor code, or a login while locked. Also
+    /// recorded for a wrong current password or code confirming a change.
+    LoginFailed,
+    /// Too many failed logins locked the account
+    AccountLocked,
+    Logout,
+    PasswordChanged,
+    /// A new password was chosen through an emailed reset link
+    PasswordReset,
+    /// One or more sessions were logged out from another one
+    SessionRevoked,
+    UsernameChanged,
+    EmailChanged,
+    TwoFactorEnabled,
+    TwoFactorDisabled,
+    ApiTokenCreated,
+    ApiTokenRevoked,
+    /// A restaurant, wishlist item, visit, tag or list was deleted
+    DataDeleted,
+    AccountDeleted,
+}
+
+/// Audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: AuditEventId,
+    pub user_id: Option<UserId>,
+    pub event_type: SecurityEventType,
+    pub details: Option<serde_json::Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Command for recording an audit log entry
+#[derive(Debug, Clone)]
+pub struct RecordAuditEvent {
+    /// `None` for failed logins with an unknown username
+    pub user_id: Option<UserId>,
+    pub event_type: SecurityEventType,
+    pub details: Option<serde_json::Value>,
+    pub client: ClientInfo,
+}
+
+/// Pagination parameters for the security history
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct SecurityEventQuery {
+    /// Maximum number of results to return (defaults to 20)
+    #[schema(example = 20, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+}
+
+impl SecurityEventQuery {
+    /// Validate the pagination parameters
+    pub fn page(&self) -> Result<PageRequest<Chronological>> {
+        PageRequest::new(Chronological, None, self.limit, self.cursor.as_deref())
+    }
+}
+
+/// Response for a security event
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SecurityEventResponse {
+    /// Event ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// What happened
+    pub event_type: SecurityEventType,
+    /// Extra information depending on the event type
+    #[schema(example = json!({"reason": "invalid_password"}))]
+    pub details: Option<serde_json::Value>,
+    /// IP address of the client
+    #[schema(example = "203.0.113.7")]
+    pub ip_address: Option<String>,
+    /// User agent of the client
+    #[schema(example = "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0")]
+    pub user_agent: Option<String>,
+    /// When it happened
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for SecurityEventResponse {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            id: event.id.0,
+            event_type: event.event_type,
+            details: event.details,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            created_at: event.created_at,
+        }
+    }
+}
@@ -1,6 +1,191 @@
+use crate::domain::geo::validate_coordinates;
+use crate::domain::restaurant::Restaurant;
+use crate::domain::tag::parse_cuisines;
+use crate::domain::wishlist::WishlistItem;
+use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use utoipa::ToSchema;
 
+/// Maximum number of suggestions returned for a search
+pub const AUTOCOMPLETE_LIMIT: usize = 10;
+
+/// City shown for suggestions whose source does not know it
+pub const UNKNOWN_CITY: &str = "Ville inconnue";
+
+/// Maximum length of a source ID
+pub const MAX_SOURCE_ID_LENGTH: usize = 255;
+
+/// Maximum number of the user's own restaurants, and of their wishlist
+/// items, merged into a search
+pub const OWN_SUGGESTION_LIMIT: i64 = 5;
+
+/// Where autocomplete suggestions come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocompleteProviderKind {
+    /// OpenDataSoft public API (needs internet access)
+    OpenDataSoft,
+    /// Places imported into PostgreSQL, searched by trigram similarity
+    Local,
+    /// Fixed in-memory suggestions, for tests and demos
+    Mock,
+}
+
+impl FromStr for AutocompleteProviderKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "opendatasoft" => Ok(Self::OpenDataSoft),
+            "local" => Ok(Self::Local),
+            "mock" => Ok(Self::Mock),
+            other => Err(AppError::Internal(format!(
+                "Unknown autocomplete provider '{}' (expected opendatasoft, local or mock)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Restaurant of an imported OSM/CSV dump
+#[derive(Debug, Clone)]
+pub struct Place {
+    pub source: String,
+    pub source_id: String,
+    pub name: String,
+    pub city: Option<String>,
+    pub cuisine: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub postcode: Option<String>,
+    pub opening_hours: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Row of an OSM/CSV dump. Both the OpenDataSoft export of
+/// `osm-france-food-service` and plain `name,city,cuisine,latitude,longitude`
+/// files are understood.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaceDumpRow {
+    #[serde(alias = "meta_osm_id", alias = "osm_id", alias = "id")]
+    pub source_id: Option<String>,
+    pub name: Option<String>,
+    #[serde(alias = "meta_name_com")]
+    pub city: Option<String>,
+    pub cuisine: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Location as `"latitude, longitude"`
+    pub meta_geo_point: Option<String>,
+    #[serde(alias = "addr_housenumber", alias = "addr:housenumber")]
+    pub housenumber: Option<String>,
+    #[serde(alias = "addr_street", alias = "addr:street", alias = "address")]
+    pub street: Option<String>,
+    #[serde(alias = "addr_postcode", alias = "addr:postcode", alias = "code_postal")]
+    pub postcode: Option<String>,
+    pub opening_hours: Option<String>,
+    #[serde(alias = "contact_website", alias = "contact:website")]
+    pub website: Option<String>,
+}
+
+/// Command for importing a place
+#[derive(Debug, Clone)]
+pub struct ImportPlace {
+    pub source_id: String,
+    pub name: String,
+    pub city: Option<String>,
+    pub cuisine: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub postcode: Option<String>,
+    pub opening_hours: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Trimmed value, or `None` when blank
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Street address from the OSM `addr:housenumber` and `addr:street` tags
+fn street_address(housenumber: Option<String>, street: Option<String>) -> Option<String> {
+    match (non_blank(housenumber), non_blank(street)) {
+        (Some(number), Some(street)) => Some(format!("{} {}", number, street)),
+        (None, street) => street,
+        (Some(_), None) => None,
+    }
+}
+
+/// Source ID of an upstream record, as `"<source>:<id>"`
+pub fn source_id(source: &str, id: &str) -> String {
+    format!("{}:{}", source, id)
+}
+
+/// Validate the source ID given when creating an entry from a suggestion
+pub fn normalize_source_id(source_id: Option<String>) -> Result<Option<String>> {
+    let Some(source_id) = non_blank(source_id) else {
+        return Ok(None);
+    };
+    if source_id.chars().count() > MAX_SOURCE_ID_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Source ID must be at most {} characters",
+            MAX_SOURCE_ID_LENGTH
+        )));
+    }
+    let valid = source_id.split_once(':').is_some_and(|(source, id)| {
+        !source.is_empty()
+            && source.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !id.is_empty()
+    });
+    if !valid {
+        return Err(AppError::Validation(
+            "Source ID must look like \"source:id\", e.g. \"osm:node/123456\"".into(),
+        ));
+    }
+    Ok(Some(source_id))
+}
+
+impl PlaceDumpRow {
+    /// Validate the row. Rows without a name are skipped, rows without an
+    /// identifier are keyed on their name, city and position.
+    pub fn to_command(self) -> Option<ImportPlace> {
+        let name = non_blank(self.name)?;
+        let city = non_blank(self.city);
+        let cuisine = non_blank(self.cuisine);
+
+        let geo_point = self.meta_geo_point.as_deref().and_then(|point| {
+            let (lat, lon) = point.split_once(',')?;
+            Some((lat.trim().parse().ok()?, lon.trim().parse().ok()?))
+        });
+        let coordinates = match (self.latitude, self.longitude) {
+            (Some(lat), Some(lon)) => Some((lat, lon)),
+            _ => geo_point,
+        }
+        .filter(|&(lat, lon)| validate_coordinates(Some(lat), Some(lon)).is_ok());
+
+        let source_id = non_blank(self.source_id)
+            .unwrap_or_else(|| {
+                let (lat, lon) = coordinates.unwrap_or_default();
+                format!("{}|{}|{:.5}|{:.5}", name.to_lowercase(), city.as_deref().unwrap_or_default().to_lowercase(), lat, lon)
+            });
+
+        Some(ImportPlace {
+            source_id,
+            name,
+            city,
+            cuisine,
+            latitude: coordinates.map(|(lat, _)| lat),
+            longitude: coordinates.map(|(_, lon)| lon),
+            address: street_address(self.housenumber, self.street),
+            postcode: non_blank(self.postcode),
+            opening_hours: non_blank(self.opening_hours),
+            website: non_blank(self.website),
+        })
+    }
+}
+
 /// OpenDataSoft API response structure
 #[derive(Debug, Deserialize)]
 pub struct OpenDataSoftResponse {
@@ -13,6 +198,13 @@ pub struct OpenDataSoftResponse {
 #[derive(Debug, Deserialize)]
 pub struct OpenDataSoftRecord {
     pub fields: OpenDataSoftFields,
+    pub geometry: Option<OpenDataSoftGeometry>,
+}
+
+/// GeoJSON point of a record, as `[longitude, latitude]`
+#[derive(Debug, Deserialize)]
+pub struct OpenDataSoftGeometry {
+    pub coordinates: Vec<f64>,
 }
 
 /// OpenDataSoft fields structure
@@ -24,8 +216,21 @@ pub struct OpenDataSoftFields {
     #[allow(dead_code)]
     #[serde(rename = "type")]
     pub restaurant_type: Option<String>,
-    #[allow(dead_code)]
     pub cuisine: Option<String>,
+    /// Location as `[latitude, longitude]`
+    pub meta_geo_point: Option<Vec<f64>>,
+    /// OSM element, as `node/123456`
+    #[serde(alias = "osm_id")]
+    pub meta_osm_id: Option<String>,
+    #[serde(alias = "addr:housenumber")]
+    pub addr_housenumber: Option<String>,
+    #[serde(alias = "addr:street")]
+    pub addr_street: Option<String>,
+    #[serde(alias = "addr:postcode")]
+    pub addr_postcode: Option<String>,
+    pub opening_hours: Option<String>,
+    #[serde(alias = "contact_website")]
+    pub website: Option<String>,
 }
 
 /// Request structure for autocomplete search
@@ -36,8 +241,22 @@ pub struct AutocompleteRequest {
     pub search_term: String,
 }
 
+/// Where a suggestion comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "restaurant")]
+pub enum SuggestionOrigin {
+    /// Already one of the user's restaurants (already visited)
+    Restaurant,
+    /// Already on the user's wishlist
+    Wishlist,
+    /// Found by the autocomplete provider
+    #[default]
+    External,
+}
+
 /// Response structure for autocomplete suggestions
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct AutocompleteSuggestion {
     /// Restaurant name
     #[schema(example = "Pizza Mario")]
@@ -45,6 +264,36 @@ pub struct AutocompleteSuggestion {
     /// City where the restaurant is located
     #[schema(example = "Paris")]
     pub city: String,
+    /// Cuisines served, to pre-fill cuisine tags on creation
+    #[schema(example = json!(["pizza", "italian"]))]
+    pub cuisines: Vec<String>,
+    /// Latitude (WGS84), when the source knows it
+    #[schema(example = 48.8566)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84), when the source knows it
+    #[schema(example = 2.3522)]
+    pub longitude: Option<f64>,
+    /// Street address
+    #[schema(example = "12 Rue de la Paix")]
+    pub address: Option<String>,
+    /// Postal code
+    #[schema(example = "75002")]
+    pub postcode: Option<String>,
+    /// Opening hours, in the OSM `opening_hours` syntax
+    #[schema(example = "Tu-Sa 12:00-14:00,19:00-22:30")]
+    pub opening_hours: Option<String>,
+    /// Website
+    #[schema(example = "https://pizzamario.fr")]
+    pub website: Option<String>,
+    /// Stable ID of the upstream record, to pass as `source_id` when
+    /// creating a restaurant or wishlist item from this suggestion
+    #[schema(example = "osm:node/123456")]
+    pub source_id: Option<String>,
+    /// Whether the user already has this restaurant or wishlist item
+    pub origin: SuggestionOrigin,
+    /// ID of the user's restaurant or wishlist item, for own suggestions
+    #[schema(example = json!(null))]
+    pub existing_id: Option<i32>,
 }
 
 /// Response containing list of autocomplete suggestions
@@ -54,8 +303,236 @@ pub struct AutocompleteResponse {
     pub suggestions: Vec<AutocompleteSuggestion>,
 }
 
+/// Counters of the autocomplete cache since the server started
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AutocompleteCacheStats {
+    /// Searches answered from a cached result for the same term
+    #[schema(example = 812)]
+    pub hits: u64,
+    /// Searches answered by filtering the cached result of a shorter term
+    #[schema(example = 354)]
+    pub prefix_hits: u64,
+    /// Searches sent to the provider
+    #[schema(example = 231)]
+    pub misses: u64,
+    /// Searches that waited on an identical search already in flight
+    #[schema(example = 17)]
+    pub coalesced: u64,
+    /// Provider failures answered with an expired cached result
+    #[schema(example = 3)]
+    pub stale_served: u64,
+    /// Entries dropped to stay within capacity
+    #[schema(example = 0)]
+    pub evictions: u64,
+    /// Entries currently cached
+    #[schema(example = 231)]
+    pub entries: usize,
+    /// Maximum number of entries
+    #[schema(example = 1000)]
+    pub capacity: usize,
+}
+
 impl From<(String, String)> for AutocompleteSuggestion {
     fn from((name, city): (String, String)) -> Self {
-        Self { name, city }
+        Self {
+            name,
+            city,
+            cuisines: Vec::new(),
+            latitude: None,
+            longitude: None,
+            address: None,
+            postcode: None,
+            opening_hours: None,
+            website: None,
+            source_id: None,
+            origin: SuggestionOrigin::External,
+            existing_id: None,
+        }
+    }
+}
+
+impl From<Restaurant> for AutocompleteSuggestion {
+    fn from(restaurant: Restaurant) -> Self {
+        Self {
+            name: restaurant.name,
+            city: restaurant.city,
+            cuisines: Vec::new(),
+            latitude: restaurant.latitude,
+            longitude: restaurant.longitude,
+            address: restaurant.address,
+            postcode: None,
+            opening_hours: None,
+            website: None,
+            source_id: restaurant.source_id,
+            origin: SuggestionOrigin::Restaurant,
+            existing_id: Some(restaurant.id.0),
+        }
+    }
+}
+
+impl From<WishlistItem> for AutocompleteSuggestion {
+    fn from(item: WishlistItem) -> Self {
+        Self {
+            name: item.name,
+            city: item.city,
+            cuisines: Vec::new(),
+            latitude: item.latitude,
+            longitude: item.longitude,
+            address: item.address,
+            postcode: None,
+            opening_hours: None,
+            website: None,
+            source_id: item.source_id,
+            origin: SuggestionOrigin::Wishlist,
+            existing_id: Some(item.id.0),
+        }
+    }
+}
+
+impl From<Place> for AutocompleteSuggestion {
+    fn from(place: Place) -> Self {
+        Self {
+            source_id: Some(source_id(&place.source, &place.source_id)),
+            name: place.name,
+            city: place.city.unwrap_or_else(|| UNKNOWN_CITY.to_string()),
+            cuisines: place.cuisine.as_deref().map(parse_cuisines).unwrap_or_default(),
+            latitude: place.latitude,
+            longitude: place.longitude,
+            address: place.address,
+            postcode: place.postcode,
+            opening_hours: place.opening_hours,
+            website: place.website,
+            origin: SuggestionOrigin::External,
+            existing_id: None,
+        }
+    }
+}
+
+impl OpenDataSoftFields {
+    /// Cuisines listed in the record (OSM `cuisine` tag, ";"-separated)
+    pub fn cuisines(&self) -> Vec<String> {
+        self.cuisine.as_deref().map(parse_cuisines).unwrap_or_default()
     }
-}
\ No newline at end of file
+
+    /// Street address of the record, when its OSM tags have one
+    pub fn address(&self) -> Option<String> {
+        street_address(self.addr_housenumber.clone(), self.addr_street.clone())
+    }
+
+    /// Source ID of the record, from its OSM element
+    pub fn source_id(&self) -> Option<String> {
+        non_blank(self.meta_osm_id.clone()).map(|id| source_id("osm", &id))
+    }
+}
+
+impl OpenDataSoftRecord {
+    /// Coordinates of the record as `(latitude, longitude)`, from the
+    /// geometry or else the geo point field
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let from_geometry = self.geometry.as_ref().and_then(|geometry| match geometry.coordinates[..] {
+            [lon, lat] => Some((lat, lon)),
+            _ => None,
+        });
+        let from_fields = || match self.fields.meta_geo_point.as_deref() {
+            Some(&[lat, lon]) => Some((lat, lon)),
+            _ => None,
+        };
+
+        from_geometry
+            .or_else(from_fields)
+            .filter(|&(lat, lon)| validate_coordinates(Some(lat), Some(lon)).is_ok())
+    }
+}
+
+/// Lowercase, strip French accents and punctuation, collapse spaces, so
+/// that "Crêperie  l'Hermine" and "creperie l hermine" compare equal
+pub fn normalize_name(value: &str) -> String {
+    let folded: String = value
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' | 'á' | 'ã' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' | 'í' => 'i',
+            'ô' | 'ö' | 'ó' | 'õ' => 'o',
+            'ù' | 'û' | 'ü' | 'ú' => 'u',
+            'ç' => 'c',
+            'ÿ' => 'y',
+            'ñ' => 'n',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// How well a suggestion matches a normalized term, lower is better
+fn match_rank(term: &str, suggestion: &AutocompleteSuggestion) -> u8 {
+    let name = normalize_name(&suggestion.name);
+    if name == term {
+        0
+    } else if name.starts_with(term) {
+        1
+    } else if name.split(' ').any(|word| word.starts_with(term)) {
+        2
+    } else if name.contains(term) {
+        3
+    } else if normalize_name(&suggestion.city).contains(term) {
+        4
+    } else {
+        // Matched by the provider on another field or a close spelling
+        5
+    }
+}
+
+/// Merge the user's own restaurants and wishlist items with the provider's
+/// suggestions. Suggestions with the same source ID, or the same normalized
+/// name and city, are kept once, preferring the user's own entry; the result is ordered by match
+/// quality, own entries first among equals.
+pub fn merge_suggestions(
+    term: &str,
+    own: Vec<AutocompleteSuggestion>,
+    external: Vec<AutocompleteSuggestion>,
+    limit: usize,
+) -> Vec<AutocompleteSuggestion> {
+    let term = normalize_name(term);
+    let mut merged: Vec<AutocompleteSuggestion> = Vec::new();
+    let mut keys: Vec<String> = Vec::new();
+
+    let mut candidates = own;
+    candidates.sort_by_key(|s| s.origin);
+    for suggestion in candidates.into_iter().chain(external) {
+        let key = format!("{}|{}", normalize_name(&suggestion.name), normalize_name(&suggestion.city));
+        let duplicate = merged.iter().zip(&keys).position(|(kept, k)| {
+            *k == key
+                || (kept.source_id.is_some() && kept.source_id == suggestion.source_id)
+        });
+        match duplicate {
+            Some(index) => {
+                // Keep the user's entry, completed with what the provider knows
+                let kept = &mut merged[index];
+                if kept.cuisines.is_empty() {
+                    kept.cuisines = suggestion.cuisines;
+                }
+                if kept.latitude.is_none() && suggestion.latitude.is_some() {
+                    kept.latitude = suggestion.latitude;
+                    kept.longitude = suggestion.longitude;
+                }
+                kept.address = kept.address.take().or(suggestion.address);
+                kept.postcode = kept.postcode.take().or(suggestion.postcode);
+                kept.opening_hours = kept.opening_hours.take().or(suggestion.opening_hours);
+                kept.website = kept.website.take().or(suggestion.website);
+                kept.source_id = kept.source_id.take().or(suggestion.source_id);
+            }
+            None => {
+                keys.push(key);
+                merged.push(suggestion);
+            }
+        }
+    }
+
+    // Stable sort: the provider's own ordering breaks the remaining ties
+    merged.sort_by_key(|s| (match_rank(&term, s), s.origin));
+    merged.truncate(limit);
+    merged
+}
@@ -0,0 +1,236 @@
+use crate::domain::restaurant::RestaurantResponse;
+use crate::domain::user::User;
+use crate::domain::wishlist::{WishlistItemResponse, WishlistPriority};
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Output format of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "csv")]
+pub enum ExportFormat {
+    /// A single JSON document
+    #[default]
+    Json,
+    /// One CSV row per account, restaurant and wishlist item
+    Csv,
+}
+
+impl ExportFormat {
+    /// MIME type of the response body
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    /// File extension of the downloaded file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Query parameters for the export endpoint
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct ExportQuery {
+    /// Output format (defaults to json)
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Account metadata included in an export
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExportAccount {
+    /// User ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// Username
+    #[schema(example = "john_doe")]
+    pub username: String,
+    /// Email address
+    #[schema(example = "john@example.com")]
+    pub email: String,
+    /// Account creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// When the export was generated
+    pub exported_at: DateTime<Utc>,
+}
+
+impl From<&User> for ExportAccount {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id.0,
+            username: user.username.clone(),
+            email: user.email.clone(),
+            created_at: user.created_at,
+            exported_at: Utc::now(),
+        }
+    }
+}
+
+/// Shape of a JSON export (documentation only, the document is streamed)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExportDocument {
+    /// Account metadata
+    pub account: ExportAccount,
+    /// Every restaurant of the user, oldest first
+    pub restaurants: Vec<RestaurantResponse>,
+    /// Every wishlist item of the user, oldest first
+    pub wishlist: Vec<WishlistItemResponse>,
+}
+
+/// Columns of a CSV export
+const CSV_COLUMNS: [&str; 13] = [
+    "type", "id", "name", "city", "rating", "is_favorite", "priority", "notes",
+    "address", "latitude", "longitude", "created_at", "updated_at",
+];
+
+/// Characters that make spreadsheets evaluate a cell as a formula
+pub const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Prefix values starting with a formula character with a quote so
+/// spreadsheets do not evaluate them; plain numbers such as negative
+/// coordinates are left alone. Imports strip the quote again, so values
+/// already starting with a quote get one too.
+fn guard_formula(value: &str) -> String {
+    let formula = value.starts_with(FORMULA_PREFIXES) && value.parse::<f64>().is_err();
+    if formula || value.starts_with('\'') {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write one CSV record, CRLF-terminated
+fn csv_row<I, S>(fields: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let invalid = |e: String| AppError::Internal(format!("Failed to write CSV: {}", e));
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+    writer
+        .write_record(fields.into_iter().map(|field| guard_formula(field.as_ref())))
+        .map_err(|e| invalid(e.to_string()))?;
+    let bytes = writer.into_inner().map_err(|e| invalid(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| invalid(e.to_string()))
+}
+
+/// Header row of a CSV export
+pub fn csv_header() -> Result<String> {
+    csv_row(CSV_COLUMNS)
+}
+
+fn optional_field<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn priority_name(priority: WishlistPriority) -> &'static str {
+    match priority {
+        WishlistPriority::Low => "low",
+        WishlistPriority::Medium => "medium",
+        WishlistPriority::High => "high",
+    }
+}
+
+impl ExportAccount {
+    /// CSV row for the account: username in `name`, email in `notes`
+    pub fn to_csv_row(&self) -> Result<String> {
+        csv_row([
+            "account".into(),
+            self.id.to_string(),
+            self.username.clone(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            self.email.clone(),
+            String::new(),
+            String::new(),
+            String::new(),
+            self.created_at.to_rfc3339(),
+            self.exported_at.to_rfc3339(),
+        ])
+    }
+}
+
+impl RestaurantResponse {
+    /// CSV row for the restaurant, with an empty rating when unrated
+    pub fn to_csv_row(&self) -> Result<String> {
+        csv_row([
+            "restaurant".into(),
+            self.id.to_string(),
+            self.name.clone(),
+            self.city.clone(),
+            if self.rating > 0 { self.rating.to_string() } else { String::new() },
+            self.is_favorite.to_string(),
+            String::new(),
+            self.description.clone().unwrap_or_default(),
+            self.address.clone().unwrap_or_default(),
+            optional_field(self.latitude),
+            optional_field(self.longitude),
+            self.created_at.to_rfc3339(),
+            self.updated_at.to_rfc3339(),
+        ])
+    }
+}
+
+impl WishlistItemResponse {
+    /// CSV row for the wishlist item
+    pub fn to_csv_row(&self) -> Result<String> {
+        csv_row([
+            "wishlist".into(),
+            self.id.to_string(),
+            self.name.clone(),
+            self.city.clone(),
+            String::new(),
+            String::new(),
+            priority_name(self.priority).into(),
+            self.notes.clone().unwrap_or_default(),
+            self.address.clone().unwrap_or_default(),
+            optional_field(self.latitude),
+            optional_field(self.longitude),
+            self.created_at.to_rfc3339(),
+            self.updated_at.to_rfc3339(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_cells_spreadsheets_would_evaluate() {
+        for value in ["=1+1", "+33 6 12", "-cmd", "@SUM(A1)", "\tParis", "\r=1"] {
+            assert_eq!(guard_formula(value), format!("'{}", value), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn leaves_numbers_and_plain_text_alone() {
+        for value in ["-1.5", "+2", "48.8566", "Chez Paulette", "", "a=b"] {
+            assert_eq!(guard_formula(value), value, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn guards_values_already_starting_with_a_quote() {
+        assert_eq!(guard_formula("'=1+1"), "''=1+1");
+        assert_eq!(guard_formula("'tis"), "''tis");
+    }
+
+    #[test]
+    fn quotes_fields_with_separators() {
+        let row = csv_row(["a,b", "say \"hi\"", "two\nlines", "=1"]).expect("row");
+        assert_eq!(row, "\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",'=1\r\n");
+    }
+}
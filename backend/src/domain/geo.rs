@@ -0,0 +1,225 @@
+use crate::domain::pagination::MAX_PAGE_SIZE;
+use crate::error::{AppError, Result};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Mean Earth radius used for distances
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Length of one degree of latitude
+const KM_PER_DEGREE: f64 = 111.195;
+
+/// Default search radius for "near me" queries
+pub const DEFAULT_RADIUS_KM: f64 = 5.0;
+
+/// Maximum search radius for "near me" queries
+pub const MAX_RADIUS_KM: f64 = 200.0;
+
+/// Default number of results of location queries
+const DEFAULT_LOCATION_LIMIT: i64 = 50;
+
+/// Check that coordinates are given together and within WGS84 bounds
+pub fn validate_coordinates(latitude: Option<f64>, longitude: Option<f64>) -> Result<()> {
+    match (latitude, longitude) {
+        (None, None) => Ok(()),
+        (Some(lat), Some(lon)) => {
+            if !lat.is_finite() || !(-90.0..=90.0).contains(&lat) {
+                return Err(AppError::Validation("Latitude must be between -90 and 90".into()));
+            }
+            if !lon.is_finite() || !(-180.0..=180.0).contains(&lon) {
+                return Err(AppError::Validation("Longitude must be between -180 and 180".into()));
+            }
+            Ok(())
+        }
+        _ => Err(AppError::Validation(
+            "Latitude and longitude must be provided together".into(),
+        )),
+    }
+}
+
+/// Validate a coordinates update: both fields are omitted, or both are
+/// given (both null clears the location)
+pub fn coordinates_update(
+    latitude: Option<Option<f64>>,
+    longitude: Option<Option<f64>>,
+) -> Result<Option<Option<(f64, f64)>>> {
+    match (latitude, longitude) {
+        (None, None) => Ok(None),
+        (Some(lat), Some(lon)) => {
+            validate_coordinates(lat, lon)?;
+            Ok(Some(lat.zip(lon)))
+        }
+        _ => Err(AppError::Validation(
+            "Latitude and longitude must be updated together".into(),
+        )),
+    }
+}
+
+/// Trim an address, treating blank addresses as absent
+pub fn normalize_address(address: Option<String>) -> Result<Option<String>> {
+    let Some(address) = address else {
+        return Ok(None);
+    };
+    let address = address.trim();
+    if address.chars().count() > 500 {
+        return Err(AppError::Validation("Address must be at most 500 characters".into()));
+    }
+    Ok((!address.is_empty()).then(|| address.to_string()))
+}
+
+fn validate_limit(limit: Option<i64>) -> Result<i64> {
+    let limit = limit.unwrap_or(DEFAULT_LOCATION_LIMIT);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(AppError::Validation(format!(
+            "Limit must be between 1 and {}",
+            MAX_PAGE_SIZE
+        )));
+    }
+    Ok(limit)
+}
+
+/// Latitude/longitude rectangle. When `min_lon > max_lon` the box crosses
+/// the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+}
+
+impl BoundingBox {
+    /// Smallest box containing the circle of `radius_km` around a point
+    pub fn around(lat: f64, lon: f64, radius_km: f64) -> Self {
+        let delta_lat = radius_km / KM_PER_DEGREE;
+        let min_lat = (lat - delta_lat).max(-90.0);
+        let max_lat = (lat + delta_lat).min(90.0);
+
+        // Near the poles the circle covers every longitude
+        let widest = lat.abs() + delta_lat;
+        if widest >= 90.0 {
+            return Self { min_lat, max_lat, min_lon: -180.0, max_lon: 180.0 };
+        }
+
+        let delta_lon = radius_km / (KM_PER_DEGREE * widest.to_radians().cos());
+        if delta_lon >= 180.0 {
+            return Self { min_lat, max_lat, min_lon: -180.0, max_lon: 180.0 };
+        }
+
+        let wrap = |lon: f64| {
+            if lon < -180.0 {
+                lon + 360.0
+            } else if lon > 180.0 {
+                lon - 360.0
+            } else {
+                lon
+            }
+        };
+
+        Self {
+            min_lat,
+            max_lat,
+            min_lon: wrap(lon - delta_lon),
+            max_lon: wrap(lon + delta_lon),
+        }
+    }
+
+    /// Whether the box wraps around the 180th meridian
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_lon > self.max_lon
+    }
+}
+
+/// Validated radius search
+#[derive(Debug, Clone)]
+pub struct NearbySearch {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_km: f64,
+    pub bounds: BoundingBox,
+    pub limit: i64,
+}
+
+/// Query parameters for "near me" searches
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct NearbyQuery {
+    /// Latitude of the center point
+    #[schema(example = 48.8566)]
+    pub lat: f64,
+    /// Longitude of the center point
+    #[schema(example = 2.3522)]
+    pub lon: f64,
+    /// Search radius in kilometers (defaults to 5, at most 200)
+    #[schema(example = 2.5)]
+    pub radius_km: Option<f64>,
+    /// Maximum number of results (defaults to 50, at most 100)
+    #[schema(example = 20)]
+    pub limit: Option<i64>,
+}
+
+impl NearbyQuery {
+    /// Validate the query
+    pub fn to_command(&self) -> Result<NearbySearch> {
+        validate_coordinates(Some(self.lat), Some(self.lon))?;
+
+        let radius_km = self.radius_km.unwrap_or(DEFAULT_RADIUS_KM);
+        if !radius_km.is_finite() || radius_km <= 0.0 || radius_km > MAX_RADIUS_KM {
+            return Err(AppError::Validation(format!(
+                "Radius must be greater than 0 and at most {} km",
+                MAX_RADIUS_KM
+            )));
+        }
+
+        Ok(NearbySearch {
+            latitude: self.lat,
+            longitude: self.lon,
+            radius_km,
+            bounds: BoundingBox::around(self.lat, self.lon, radius_km),
+            limit: validate_limit(self.limit)?,
+        })
+    }
+}
+
+/// Query parameters for map (bounding-box) searches
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WithinQuery {
+    /// Southern edge
+    #[schema(example = 48.80)]
+    pub min_lat: f64,
+    /// Western edge (greater than max_lon when the box crosses the antimeridian)
+    #[schema(example = 2.25)]
+    pub min_lon: f64,
+    /// Northern edge
+    #[schema(example = 48.90)]
+    pub max_lat: f64,
+    /// Eastern edge
+    #[schema(example = 2.42)]
+    pub max_lon: f64,
+    /// Maximum number of results (defaults to 50, at most 100)
+    #[schema(example = 50)]
+    pub limit: Option<i64>,
+}
+
+impl WithinQuery {
+    /// Validate the query
+    pub fn to_command(&self) -> Result<(BoundingBox, i64)> {
+        validate_coordinates(Some(self.min_lat), Some(self.min_lon))?;
+        validate_coordinates(Some(self.max_lat), Some(self.max_lon))?;
+        if self.min_lat > self.max_lat {
+            return Err(AppError::Validation("min_lat must not be greater than max_lat".into()));
+        }
+
+        let bounds = BoundingBox {
+            min_lat: self.min_lat,
+            max_lat: self.max_lat,
+            min_lon: self.min_lon,
+            max_lon: self.max_lon,
+        };
+        Ok((bounds, validate_limit(self.limit)?))
+    }
+}
+
+/// Round a distance for display (meter precision)
+pub fn round_distance(distance_km: f64) -> f64 {
+    (distance_km * 1000.0).round() / 1000.0
+}
@@ -0,0 +1,516 @@
+use crate::domain::export::FORMULA_PREFIXES;
+use crate::domain::restaurant::{CreateRestaurant, CreateRestaurantRequest};
+use crate::domain::user::UserId;
+use crate::domain::wishlist::{CreateWishlistItem, CreateWishlistRequest, WishlistPriority};
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// Maximum size of an uploaded import file
+pub const MAX_IMPORT_BYTES: usize = 5 * 1024 * 1024;
+
+/// Maximum number of rows in one import
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+/// Collection an imported row is added to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "restaurants")]
+pub enum ImportTarget {
+    /// Restaurants already visited
+    #[default]
+    Restaurants,
+    /// Wishlist items
+    Wishlist,
+}
+
+/// Query parameters for the import endpoint
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct ImportQuery {
+    /// Validate and report without saving anything
+    #[serde(default)]
+    #[schema(example = true)]
+    pub dry_run: bool,
+    /// Collection for rows that do not name one (CSV without a `type` column, bare JSON arrays)
+    #[serde(default)]
+    pub target: ImportTarget,
+}
+
+/// Multipart body of an import (documentation only)
+#[derive(Debug, ToSchema)]
+pub struct ImportUpload {
+    /// CSV file with a header row (type, name, city, rating, is_favorite,
+    /// description or notes, priority, address, latitude, longitude), or JSON file (an export document or
+    /// an array of objects shaped like create requests)
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// Validated entry ready to be saved
+#[derive(Debug, Clone)]
+pub enum ImportEntry {
+    Restaurant(CreateRestaurant),
+    Wishlist(CreateWishlistItem),
+}
+
+/// One row of an import file after parsing and validation
+#[derive(Debug)]
+pub struct ImportRow {
+    /// Line number (CSV) or 1-based position (JSON) in the file
+    pub row: usize,
+    pub target: ImportTarget,
+    pub name: Option<String>,
+    pub city: Option<String>,
+    pub entry: Result<ImportEntry>,
+}
+
+/// What happened to a valid entry when saving it
+#[derive(Debug, Clone, Copy)]
+pub enum ImportOutcome {
+    /// Saved with this ID
+    Created(i32),
+    /// Matches the restaurant or wishlist item with this ID
+    Duplicate(i32),
+}
+
+/// Status of a row in an import report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportRowStatus {
+    /// The row was saved (or would be, in a dry run)
+    Created,
+    /// The row duplicates an existing entry or an earlier row
+    Skipped,
+    /// The row is invalid
+    Failed,
+}
+
+/// Report for one row of an import
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportRowReport {
+    /// Line number (CSV) or 1-based position (JSON) in the file
+    #[schema(example = 2)]
+    pub row: usize,
+    /// Collection the row belongs to
+    pub target: ImportTarget,
+    /// Restaurant name, when present
+    #[schema(example = "Le Comptoir du Relais")]
+    pub name: Option<String>,
+    /// City, when present
+    #[schema(example = "Paris")]
+    pub city: Option<String>,
+    /// What happened to the row
+    pub status: ImportRowStatus,
+    /// ID of the created entry, or of the entry it duplicates (absent in dry runs for created rows)
+    #[schema(example = 12)]
+    pub id: Option<i32>,
+    /// Reason for skipped and failed rows
+    #[schema(example = "Rating must be between 1 and 5")]
+    pub message: Option<String>,
+}
+
+/// Result of an import
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportReport {
+    /// Whether this was a dry run (nothing saved)
+    #[schema(example = false)]
+    pub dry_run: bool,
+    /// Number of created rows
+    #[schema(example = 40)]
+    pub created: usize,
+    /// Number of duplicate rows
+    #[schema(example = 2)]
+    pub skipped: usize,
+    /// Number of invalid rows
+    #[schema(example = 1)]
+    pub failed: usize,
+    /// Per-row details, in file order
+    pub rows: Vec<ImportRowReport>,
+}
+
+/// Format of an uploaded file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// Guess the format from the upload's file name and content type, then its content
+    pub fn detect(filename: Option<&str>, content_type: Option<&str>, data: &[u8]) -> Self {
+        let filename = filename.unwrap_or_default().to_lowercase();
+        let content_type = content_type.unwrap_or_default().to_lowercase();
+
+        if filename.ends_with(".json") || content_type.contains("json") {
+            return ImportFormat::Json;
+        }
+        if filename.ends_with(".csv") || content_type.contains("csv") {
+            return ImportFormat::Csv;
+        }
+
+        match data.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') | Some(b'[') => ImportFormat::Json,
+            _ => ImportFormat::Csv,
+        }
+    }
+}
+
+/// Undo the formula guard applied by CSV exports ("'=..." back to "=...",
+/// "''..." back to "'...")
+fn unguard(value: &str) -> &str {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) || rest.starts_with('\'') => rest,
+        _ => value,
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(|v| unguard(v.trim()))
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn require(value: &Option<String>, field: &str) -> Result<String> {
+    value
+        .clone()
+        .ok_or_else(|| AppError::Validation(format!("Missing {}", field)))
+}
+
+fn restaurant_entry(request: CreateRestaurantRequest, owner_id: UserId) -> Result<ImportEntry> {
+    Ok(ImportEntry::Restaurant(request.to_command(owner_id)?))
+}
+
+fn wishlist_entry(request: CreateWishlistRequest, owner_id: UserId) -> Result<ImportEntry> {
+    Ok(ImportEntry::Wishlist(request.to_command(owner_id)?))
+}
+
+fn parse_target(value: Option<&str>, default: ImportTarget) -> Result<Option<ImportTarget>> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") => Ok(Some(default)),
+        Some("restaurant") | Some("restaurants") => Ok(Some(ImportTarget::Restaurants)),
+        Some("wishlist") => Ok(Some(ImportTarget::Wishlist)),
+        // Account metadata written by exports
+        Some("account") => Ok(None),
+        Some(other) => Err(AppError::Validation(format!("Unknown type: {}", other))),
+    }
+}
+
+fn parse_bool(value: Option<&str>) -> Result<bool> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("false") | Some("no") | Some("0") => Ok(false),
+        Some("true") | Some("yes") | Some("1") => Ok(true),
+        Some(other) => Err(AppError::Validation(format!("Invalid is_favorite: {}", other))),
+    }
+}
+
+fn parse_rating(value: Option<&str>) -> Result<Option<i16>> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(raw) => raw
+            .parse::<i16>()
+            .map(Some)
+            .map_err(|_| AppError::Validation(format!("Invalid rating: {}", raw))),
+    }
+}
+
+fn parse_priority(value: Option<&str>) -> Result<WishlistPriority> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") => Ok(WishlistPriority::default()),
+        Some("low") => Ok(WishlistPriority::Low),
+        Some("medium") => Ok(WishlistPriority::Medium),
+        Some("high") => Ok(WishlistPriority::High),
+        Some(other) => Err(AppError::Validation(format!("Invalid priority: {}", other))),
+    }
+}
+
+fn parse_coordinate(value: Option<&str>, field: &str) -> Result<Option<f64>> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(raw) => raw
+            .parse::<f64>()
+            .map(Some)
+            .map_err(|_| AppError::Validation(format!("Invalid {}: {}", field, raw))),
+    }
+}
+
+/// Location columns of a CSV row
+struct CsvLocation {
+    address: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl CsvLocation {
+    fn parse(address: Option<&str>, latitude: Option<&str>, longitude: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            address: non_empty(address),
+            latitude: parse_coordinate(latitude, "latitude")?,
+            longitude: parse_coordinate(longitude, "longitude")?,
+        })
+    }
+}
+
+fn csv_restaurant(
+    name: &Option<String>,
+    city: &Option<String>,
+    notes: &Option<String>,
+    rating: Option<&str>,
+    is_favorite: Option<&str>,
+    location: CsvLocation,
+) -> Result<CreateRestaurantRequest> {
+    Ok(CreateRestaurantRequest {
+        name: require(name, "name")?,
+        city: require(city, "city")?,
+        rating: parse_rating(rating)?,
+        description: notes.clone(),
+        is_favorite: parse_bool(is_favorite)?,
+        address: location.address,
+        latitude: location.latitude,
+        longitude: location.longitude,
+        source_id: None,
+        tag_ids: Vec::new(),
+        cuisines: Vec::new(),
+    })
+}
+
+fn csv_wishlist(
+    name: &Option<String>,
+    city: &Option<String>,
+    notes: &Option<String>,
+    priority: Option<&str>,
+    location: CsvLocation,
+) -> Result<CreateWishlistRequest> {
+    Ok(CreateWishlistRequest {
+        name: require(name, "name")?,
+        city: require(city, "city")?,
+        notes: notes.clone(),
+        priority: parse_priority(priority)?,
+        address: location.address,
+        latitude: location.latitude,
+        longitude: location.longitude,
+        source_id: None,
+        tag_ids: Vec::new(),
+        cuisines: Vec::new(),
+    })
+}
+
+/// Parse a CSV file with a header row. Recognized columns: type, name, city,
+/// rating, is_favorite, description, notes, priority, address, latitude and
+/// longitude; others are ignored.
+pub fn parse_csv(data: &[u8], default_target: ImportTarget, owner_id: UserId) -> Result<Vec<ImportRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(data);
+
+    let headers: HashMap<String, usize> = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(format!("Invalid CSV header: {}", e)))?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (h.trim_start_matches('\u{feff}').to_lowercase(), i))
+        .collect();
+
+    if !headers.contains_key("name") || !headers.contains_key("city") {
+        return Err(AppError::BadRequest("CSV must have name and city columns".into()));
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| AppError::BadRequest(format!("Invalid CSV: {}", e)))?;
+        let row = record.position().map(|p| p.line() as usize).unwrap_or(rows.len() + 2);
+        let get = |column: &str| headers.get(column).and_then(|&i| record.get(i));
+
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        let name = non_empty(get("name"));
+        let city = non_empty(get("city"));
+        let notes = non_empty(get("notes")).or_else(|| non_empty(get("description")));
+
+        let target = match parse_target(get("type"), default_target) {
+            Ok(Some(target)) => target,
+            Ok(None) => continue,
+            Err(err) => {
+                rows.push(ImportRow { row, target: default_target, name, city, entry: Err(err) });
+                continue;
+            }
+        };
+
+        let location = CsvLocation::parse(get("address"), get("latitude"), get("longitude"));
+        let entry = location.and_then(|location| match target {
+            ImportTarget::Restaurants => csv_restaurant(&name, &city, &notes, get("rating"), get("is_favorite"), location)
+                .and_then(|request| restaurant_entry(request, owner_id)),
+            ImportTarget::Wishlist => csv_wishlist(&name, &city, &notes, get("priority"), location)
+                .and_then(|request| wishlist_entry(request, owner_id)),
+        });
+
+        rows.push(ImportRow { row, target, name, city, entry });
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::BadRequest(format!(
+                "Imports are limited to {} rows",
+                MAX_IMPORT_ROWS
+            )));
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Parse one JSON object into an entry
+fn parse_json_item(
+    row: usize,
+    target: ImportTarget,
+    mut item: Value,
+    owner_id: UserId,
+) -> ImportRow {
+    let text = |item: &Value, field: &str| non_empty(item.get(field).and_then(Value::as_str));
+    let name = text(&item, "name");
+    let city = text(&item, "city");
+
+    // Trim the fields the duplicate check relies on
+    if let Some(object) = item.as_object_mut() {
+        if let Some(name) = &name {
+            object.insert("name".into(), Value::String(name.clone()));
+        }
+        if let Some(city) = &city {
+            object.insert("city".into(), Value::String(city.clone()));
+        }
+        // Exports use 0 for unrated restaurants
+        if object.get("rating").and_then(Value::as_i64) == Some(0) {
+            object.insert("rating".into(), Value::Null);
+        }
+    }
+
+    let invalid = |e: serde_json::Error| AppError::Validation(e.to_string());
+    let entry = match target {
+        ImportTarget::Restaurants => serde_json::from_value::<CreateRestaurantRequest>(item)
+            .map_err(invalid)
+            .and_then(|request| restaurant_entry(request, owner_id)),
+        ImportTarget::Wishlist => serde_json::from_value::<CreateWishlistRequest>(item)
+            .map_err(invalid)
+            .and_then(|request| wishlist_entry(request, owner_id)),
+    };
+
+    ImportRow { row, target, name, city, entry }
+}
+
+/// Parse a JSON file: either an export document (`restaurants` and `wishlist`
+/// arrays) or a bare array of objects added to the default target
+pub fn parse_json(data: &[u8], default_target: ImportTarget, owner_id: UserId) -> Result<Vec<ImportRow>> {
+    let document: Value = serde_json::from_slice(data)
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let sections: Vec<(ImportTarget, Vec<Value>)> = match document {
+        Value::Array(items) => vec![(default_target, items)],
+        Value::Object(mut object) => {
+            let mut take = |key: &str| match object.remove(key) {
+                Some(Value::Array(items)) => Ok(items),
+                None => Ok(Vec::new()),
+                Some(_) => Err(AppError::BadRequest(format!("`{}` must be an array", key))),
+            };
+            vec![
+                (ImportTarget::Restaurants, take("restaurants")?),
+                (ImportTarget::Wishlist, take("wishlist")?),
+            ]
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "JSON must be an array or an object with restaurants and wishlist arrays".into(),
+            ));
+        }
+    };
+
+    let count: usize = sections.iter().map(|(_, items)| items.len()).sum();
+    if count > MAX_IMPORT_ROWS {
+        return Err(AppError::BadRequest(format!(
+            "Imports are limited to {} rows",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let mut rows = Vec::with_capacity(count);
+    for (target, items) in sections {
+        for item in items {
+            rows.push(parse_json_item(rows.len() + 1, target, item, owner_id));
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::export::csv_header;
+    use crate::domain::restaurant::RestaurantResponse;
+    use chrono::Utc;
+
+    fn restaurant(name: &str, description: &str) -> RestaurantResponse {
+        RestaurantResponse {
+            id: 1,
+            name: name.to_string(),
+            city: "Paris".to_string(),
+            rating: 4,
+            visit_rating: None,
+            description: Some(description.to_string()),
+            is_favorite: false,
+            address: None,
+            latitude: Some(48.8566),
+            longitude: Some(-2.35),
+            source_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Export a restaurant to CSV and import it back
+    fn round_trip(name: &str, description: &str) -> CreateRestaurant {
+        let csv = csv_header().expect("header")
+            + &restaurant(name, description).to_csv_row().expect("row");
+        let mut rows = parse_csv(csv.as_bytes(), ImportTarget::Restaurants, UserId(1)).expect("parse");
+        assert_eq!(rows.len(), 1);
+
+        match rows.remove(0).entry.expect("valid row") {
+            ImportEntry::Restaurant(restaurant) => restaurant,
+            ImportEntry::Wishlist(_) => panic!("expected a restaurant"),
+        }
+    }
+
+    #[test]
+    fn formula_guard_is_undone_on_import() {
+        for value in ["=HYPERLINK(\"http://x\")", "+33 6 12 34 56 78", "-cmd", "@SUM(A1)", "\tdine in", "\rcarriage"] {
+            let imported = round_trip("Chez Paulette", value);
+            assert_eq!(imported.description.as_deref(), Some(value), "{:?}", value);
+        }
+
+        let imported = round_trip("=Le Comptoir", "plain");
+        assert_eq!(imported.name, "=Le Comptoir");
+    }
+
+    #[test]
+    fn values_starting_with_a_quote_survive_the_round_trip() {
+        for value in ["'=1+1", "'tis the season", "''"] {
+            let imported = round_trip("Chez Paulette", value);
+            assert_eq!(imported.description.as_deref(), Some(value), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn negative_coordinates_are_not_guarded() {
+        let imported = round_trip("Chez Paulette", "plain");
+        assert_eq!(imported.latitude, Some(48.8566));
+        assert_eq!(imported.longitude, Some(-2.35));
+    }
+
+    #[test]
+    fn quotes_in_files_from_elsewhere_are_kept() {
+        let csv = "name,city,notes\n'Chez Paulette,Paris,'quoted\n";
+        let rows = parse_csv(csv.as_bytes(), ImportTarget::Restaurants, UserId(1)).expect("parse");
+        assert_eq!(rows[0].name.as_deref(), Some("'Chez Paulette"));
+    }
+}
@@ -0,0 +1,451 @@
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::user::UserId;
+use crate::domain::wishlist::WishlistId;
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Maximum number of entries in a list
+pub const MAX_LIST_ENTRIES: i64 = 500;
+
+/// Unique identifier for a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct ListId(pub i32);
+
+/// Unique identifier for a list entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct ListEntryId(pub i32);
+
+/// Curated list of restaurants and wishlist items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct List {
+    pub id: ListId,
+    pub owner_id: UserId,
+    pub name: String,
+    pub description: Option<String>,
+    pub share_token: Option<String>,
+    pub entry_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What a list entry points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "restaurant")]
+pub enum ListEntryKind {
+    /// A restaurant of the owner
+    Restaurant,
+    /// A wishlist item of the owner
+    Wishlist,
+}
+
+/// Entry of a list, with the details of the restaurant or wishlist item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListEntry {
+    pub id: ListEntryId,
+    pub list_id: ListId,
+    pub kind: ListEntryKind,
+    pub item_id: i32,
+    pub position: i32,
+    pub note: Option<String>,
+    pub name: String,
+    pub city: String,
+    pub rating: Option<i16>,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Validate and normalize a list name
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 100 {
+        return Err(AppError::Validation(
+            "List name must be between 1 and 100 characters".into(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Trim a note or description, treating blank values as absent
+fn normalize_text(value: Option<String>, field: &str, max: usize) -> Result<Option<String>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let value = value.trim();
+    if value.chars().count() > max {
+        return Err(AppError::Validation(format!(
+            "{} must be at most {} characters",
+            field, max
+        )));
+    }
+    Ok((!value.is_empty()).then(|| value.to_string()))
+}
+
+fn validate_position(position: Option<i32>) -> Result<Option<i32>> {
+    if let Some(position) = position
+        && position < 0
+    {
+        return Err(AppError::Validation("Position cannot be negative".into()));
+    }
+    Ok(position)
+}
+
+/// Command to create a new list
+#[derive(Debug, Clone)]
+pub struct CreateList {
+    pub owner_id: UserId,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Request to create a new list
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateListRequest {
+    /// List name (1-100 characters)
+    #[schema(example = "Best ramen in Lyon")]
+    pub name: String,
+    /// Description shown on the list (optional)
+    #[schema(example = "Where to go when it rains")]
+    pub description: Option<String>,
+}
+
+impl CreateListRequest {
+    /// Convert to domain command
+    pub fn to_command(self, owner_id: UserId) -> Result<CreateList> {
+        Ok(CreateList {
+            owner_id,
+            name: validate_name(&self.name)?,
+            description: normalize_text(self.description, "Description", 2000)?,
+        })
+    }
+}
+
+/// Command to update a list
+#[derive(Debug, Clone)]
+pub struct UpdateList {
+    pub id: ListId,
+    pub owner_id: UserId,
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+}
+
+/// Request to update a list
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateListRequest {
+    /// New list name (optional)
+    #[schema(example = "Best ramen in Lyon")]
+    pub name: Option<String>,
+    /// New description (optional)
+    #[schema(example = "Updated for 2026")]
+    pub description: Option<Option<String>>,
+}
+
+impl UpdateListRequest {
+    /// Convert to domain command
+    pub fn to_command(self, id: ListId, owner_id: UserId) -> Result<UpdateList> {
+        let name = match self.name {
+            Some(name) => Some(validate_name(&name)?),
+            None => None,
+        };
+
+        Ok(UpdateList {
+            id,
+            owner_id,
+            name,
+            description: self
+                .description
+                .map(|description| normalize_text(description, "Description", 2000))
+                .transpose()?,
+        })
+    }
+}
+
+/// Command to add an entry to a list
+#[derive(Debug, Clone)]
+pub struct AddListEntry {
+    pub list_id: ListId,
+    pub owner_id: UserId,
+    pub restaurant_id: Option<RestaurantId>,
+    pub wishlist_item_id: Option<WishlistId>,
+    pub note: Option<String>,
+    pub position: Option<i32>,
+}
+
+/// Request to add a restaurant or wishlist item to a list
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AddListEntryRequest {
+    /// Restaurant to add (exactly one of restaurant_id and wishlist_item_id)
+    #[schema(example = 12)]
+    pub restaurant_id: Option<i32>,
+    /// Wishlist item to add (exactly one of restaurant_id and wishlist_item_id)
+    #[schema(example = json!(null))]
+    pub wishlist_item_id: Option<i32>,
+    /// Note shown next to the entry (optional)
+    #[schema(example = "Order the tonkotsu")]
+    pub note: Option<String>,
+    /// 0-based position in the list (defaults to the end)
+    #[schema(example = 0)]
+    pub position: Option<i32>,
+}
+
+impl AddListEntryRequest {
+    /// Convert to domain command
+    pub fn to_command(self, list_id: ListId, owner_id: UserId) -> Result<AddListEntry> {
+        if self.restaurant_id.is_some() == self.wishlist_item_id.is_some() {
+            return Err(AppError::Validation(
+                "Provide exactly one of restaurant_id and wishlist_item_id".into(),
+            ));
+        }
+
+        Ok(AddListEntry {
+            list_id,
+            owner_id,
+            restaurant_id: self.restaurant_id.map(RestaurantId),
+            wishlist_item_id: self.wishlist_item_id.map(WishlistId),
+            note: normalize_text(self.note, "Note", 1000)?,
+            position: validate_position(self.position)?,
+        })
+    }
+}
+
+/// Command to update a list entry
+#[derive(Debug, Clone)]
+pub struct UpdateListEntry {
+    pub id: ListEntryId,
+    pub list_id: ListId,
+    pub owner_id: UserId,
+    pub note: Option<Option<String>>,
+    pub position: Option<i32>,
+}
+
+/// Request to update a list entry
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateListEntryRequest {
+    /// New note (optional)
+    #[schema(example = "Closed on Mondays")]
+    pub note: Option<Option<String>>,
+    /// New 0-based position, other entries shift to make room (optional)
+    #[schema(example = 2)]
+    pub position: Option<i32>,
+}
+
+impl UpdateListEntryRequest {
+    /// Convert to domain command
+    pub fn to_command(self, id: ListEntryId, list_id: ListId, owner_id: UserId) -> Result<UpdateListEntry> {
+        Ok(UpdateListEntry {
+            id,
+            list_id,
+            owner_id,
+            note: self
+                .note
+                .map(|note| normalize_text(note, "Note", 1000))
+                .transpose()?,
+            position: validate_position(self.position)?,
+        })
+    }
+}
+
+/// Request to reorder every entry of a list at once
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ReorderListRequest {
+    /// IDs of all the list's entries, in their new order
+    #[schema(example = json!([3, 1, 2]))]
+    pub entry_ids: Vec<i32>,
+}
+
+impl ReorderListRequest {
+    /// Get the entry IDs, rejecting duplicates
+    pub fn entry_ids(&self) -> Result<Vec<ListEntryId>> {
+        let mut entry_ids = Vec::with_capacity(self.entry_ids.len());
+        for id in &self.entry_ids {
+            if entry_ids.contains(&ListEntryId(*id)) {
+                return Err(AppError::Validation(format!("Duplicate entry ID: {}", id)));
+            }
+            entry_ids.push(ListEntryId(*id));
+        }
+        Ok(entry_ids)
+    }
+}
+
+/// Response for a list
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListResponse {
+    /// List ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// List name
+    #[schema(example = "Best ramen in Lyon")]
+    pub name: String,
+    /// Description
+    #[schema(example = "Where to go when it rains")]
+    pub description: Option<String>,
+    /// Share token while the list is published
+    #[schema(example = "q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub share_token: Option<String>,
+    /// Public URL path while the list is published
+    #[schema(example = "/api/v1/public/lists/q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub share_path: Option<String>,
+    /// Number of entries
+    #[schema(example = 5)]
+    pub entry_count: i64,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<List> for ListResponse {
+    fn from(list: List) -> Self {
+        Self {
+            id: list.id.0,
+            name: list.name,
+            description: list.description,
+            share_path: list
+                .share_token
+                .as_ref()
+                .map(|token| format!("/api/v1/public/lists/{}", token)),
+            share_token: list.share_token,
+            entry_count: list.entry_count,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+        }
+    }
+}
+
+/// Response for a list entry
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListEntryResponse {
+    /// Entry ID
+    #[schema(example = 3)]
+    pub id: i32,
+    /// Whether the entry is a restaurant or a wishlist item
+    pub kind: ListEntryKind,
+    /// ID of the restaurant or wishlist item
+    #[schema(example = 12)]
+    pub item_id: i32,
+    /// 0-based position in the list
+    #[schema(example = 0)]
+    pub position: i32,
+    /// Note shown next to the entry
+    #[schema(example = "Order the tonkotsu")]
+    pub note: Option<String>,
+    /// Name of the restaurant or wishlist item
+    #[schema(example = "Ramen Ya")]
+    pub name: String,
+    /// City
+    #[schema(example = "Lyon")]
+    pub city: String,
+    /// Rating from 1 to 5 (restaurants only)
+    #[schema(example = 5)]
+    pub rating: Option<i16>,
+    /// Street address
+    #[schema(example = "12 Rue Mercière")]
+    pub address: Option<String>,
+    /// Latitude (WGS84)
+    #[schema(example = 45.7623)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84)
+    #[schema(example = 4.8325)]
+    pub longitude: Option<f64>,
+    /// When the entry was added
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ListEntry> for ListEntryResponse {
+    fn from(entry: ListEntry) -> Self {
+        Self {
+            id: entry.id.0,
+            kind: entry.kind,
+            item_id: entry.item_id,
+            position: entry.position,
+            note: entry.note,
+            name: entry.name,
+            city: entry.city,
+            rating: entry.rating,
+            address: entry.address,
+            latitude: entry.latitude,
+            longitude: entry.longitude,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// A list with its entries, in order
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListDetailResponse {
+    #[serde(flatten)]
+    pub list: ListResponse,
+    /// Entries ordered by position
+    pub entries: Vec<ListEntryResponse>,
+}
+
+/// Entry of a published list (no internal IDs)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicListEntryResponse {
+    /// Whether the entry is a restaurant or a wishlist item
+    pub kind: ListEntryKind,
+    /// 0-based position in the list
+    #[schema(example = 0)]
+    pub position: i32,
+    /// Note shown next to the entry
+    #[schema(example = "Order the tonkotsu")]
+    pub note: Option<String>,
+    /// Name of the restaurant or wishlist item
+    #[schema(example = "Ramen Ya")]
+    pub name: String,
+    /// City
+    #[schema(example = "Lyon")]
+    pub city: String,
+    /// Rating from 1 to 5 (restaurants only)
+    #[schema(example = 5)]
+    pub rating: Option<i16>,
+    /// Street address
+    #[schema(example = "12 Rue Mercière")]
+    pub address: Option<String>,
+    /// Latitude (WGS84)
+    #[schema(example = 45.7623)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84)
+    #[schema(example = 4.8325)]
+    pub longitude: Option<f64>,
+}
+
+impl From<ListEntry> for PublicListEntryResponse {
+    fn from(entry: ListEntry) -> Self {
+        Self {
+            kind: entry.kind,
+            position: entry.position,
+            note: entry.note,
+            name: entry.name,
+            city: entry.city,
+            rating: entry.rating,
+            address: entry.address,
+            latitude: entry.latitude,
+            longitude: entry.longitude,
+        }
+    }
+}
+
+/// A published list, as seen without logging in
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicListResponse {
+    /// List name
+    #[schema(example = "Best ramen in Lyon")]
+    pub name: String,
+    /// Description
+    #[schema(example = "Where to go when it rains")]
+    pub description: Option<String>,
+    /// Username of the curator
+    #[schema(example = "john_doe")]
+    pub curator: String,
+    /// Entries ordered by position
+    pub entries: Vec<PublicListEntryResponse>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,69 @@
+use crate::error::{AppError, Result};
+use std::str::FromStr;
+
+/// How outgoing emails are delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MailerKind {
+    /// A real SMTP server
+    Smtp,
+    /// One `.eml` file per email in a directory, for local testing
+    File,
+    /// Recipient and subject written to the application log, for development
+    Log,
+}
+
+impl FromStr for MailerKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "smtp" => Ok(Self::Smtp),
+            "file" => Ok(Self::File),
+            "log" => Ok(Self::Log),
+            other => Err(AppError::Internal(format!(
+                "Unknown mailer '{}' (expected smtp, file or log)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Plain text email to a single recipient
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl MailMessage {
+    /// Email with the link to verify an address
+    pub fn email_verification(to: &str, username: &str, link: &str) -> Self {
+        Self {
+            to: to.to_string(),
+            subject: "Confirmez votre adresse e-mail".into(),
+            body: format!(
+                "Bonjour {},\n\n\
+                 Confirmez votre adresse e-mail pour votre compte Gourmestre en ouvrant ce lien :\n\n\
+                 {}\n\n\
+                 Le lien est valable 48 heures.\n",
+                username, link
+            ),
+        }
+    }
+
+    /// Email with the link to choose a new password
+    pub fn password_reset(to: &str, username: &str, link: &str) -> Self {
+        Self {
+            to: to.to_string(),
+            subject: "Réinitialisation de votre mot de passe".into(),
+            body: format!(
+                "Bonjour {},\n\n\
+                 Pour choisir un nouveau mot de passe Gourmestre, ouvrez ce lien :\n\n\
+                 {}\n\n\
+                 Le lien est valable une heure. Si vous n'avez rien demandé, ignorez cet e-mail.\n",
+                username, link
+            ),
+        }
+    }
+}
@@ -2,4 +2,20 @@ pub mod user;
 pub mod restaurant; 
 pub mod wishlist;
 pub mod session;
-pub mod autocomplete;
\ No newline at end of file
+pub mod autocomplete;
+pub mod visit;
+pub mod tag;
+pub mod pagination;
+pub mod export;
+pub mod import;
+pub mod geo;
+pub mod list;
+pub mod social;
+pub mod api_token;
+pub mod user_token;
+pub mod mail;
+pub mod password;
+pub mod two_factor;
+pub mod rate_limit;
+pub mod audit;
+pub mod token;
\ No newline at end of file
@@ -0,0 +1,304 @@
+use crate::domain::audit::SecurityEventResponse;
+use crate::domain::restaurant::RestaurantResponse;
+use crate::domain::social::{ActivityResponse, FollowResponse};
+use crate::domain::wishlist::WishlistItemResponse;
+use crate::error::{AppError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Default number of items per page
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// Maximum number of items per page
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Sort direction for listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "desc")]
+pub enum SortDirection {
+    /// Ascending order
+    Asc,
+    /// Descending order
+    Desc,
+}
+
+impl SortDirection {
+    /// SQL keyword for ORDER BY
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Comparison operator selecting the rows that come after a cursor
+    pub fn after_operator(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+/// Type of the sort key stored in a cursor, checked before the key is cast in SQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKeyKind {
+    /// Any text
+    Text,
+    /// A decimal number
+    Decimal,
+    /// A timestamp as rendered by Postgres (`2025-03-14 10:20:30.123456+00`)
+    Timestamp,
+    /// One of a fixed set of values, such as an enum label
+    OneOf(&'static [&'static str]),
+}
+
+impl SortKeyKind {
+    /// Whether a cursor key can be cast to this type
+    pub fn accepts(&self, key: &str) -> bool {
+        match self {
+            SortKeyKind::Text => true,
+            SortKeyKind::Decimal => key.parse::<f64>().is_ok_and(f64::is_finite),
+            SortKeyKind::Timestamp => {
+                DateTime::parse_from_str(key, "%Y-%m-%d %H:%M:%S%.f%#z").is_ok()
+            }
+            SortKeyKind::OneOf(values) => values.contains(&key),
+        }
+    }
+}
+
+/// A field a listing can be sorted by
+pub trait SortField: Copy {
+    /// Name used in query strings and cursors
+    fn as_str(&self) -> &'static str;
+
+    /// Direction used when the client does not specify one
+    fn default_direction(&self) -> SortDirection;
+
+    /// Type of the sort key, used to reject crafted cursors
+    fn key_kind(&self) -> SortKeyKind;
+}
+
+/// Position of the last item of a page, handed back to clients as an opaque token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Sort field the cursor was issued for
+    #[serde(rename = "s")]
+    pub sort: String,
+    /// Sort direction the cursor was issued for
+    #[serde(rename = "d")]
+    pub direction: SortDirection,
+    /// Sort key of the last item, as rendered by the database
+    #[serde(rename = "k")]
+    pub key: String,
+    /// ID of the last item, breaking ties between equal sort keys
+    pub id: i32,
+}
+
+impl Cursor {
+    /// Encode the cursor as an opaque URL-safe token
+    pub fn encode(&self) -> String {
+        // Serializing a plain struct of strings and integers cannot fail
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a token produced by `encode`
+    pub fn decode(token: &str) -> Result<Self> {
+        let invalid = || AppError::Validation("Invalid cursor".into());
+        let bytes = URL_SAFE_NO_PAD.decode(token.trim()).map_err(|_| invalid())?;
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+}
+
+/// Validated pagination and sorting parameters for a listing
+#[derive(Debug, Clone)]
+pub struct PageRequest<S> {
+    pub sort: S,
+    pub direction: SortDirection,
+    pub limit: i64,
+    pub after: Option<Cursor>,
+}
+
+impl<S: SortField> PageRequest<S> {
+    /// Validate raw query parameters
+    pub fn new(
+        sort: S,
+        direction: Option<SortDirection>,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<Self> {
+        let direction = direction.unwrap_or_else(|| sort.default_direction());
+
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        let after = match cursor.filter(|c| !c.trim().is_empty()) {
+            Some(token) => {
+                let cursor = Cursor::decode(token)?;
+                if cursor.sort != sort.as_str() || cursor.direction != direction {
+                    return Err(AppError::Validation(
+                        "Cursor does not match the requested sort".into(),
+                    ));
+                }
+                if !sort.key_kind().accepts(&cursor.key) {
+                    return Err(AppError::Validation("Invalid cursor".into()));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        Ok(Self { sort, direction, limit, after })
+    }
+
+    /// Build the cursor pointing after an item
+    pub fn cursor_after(&self, key: String, id: i32) -> Cursor {
+        Cursor {
+            sort: self.sort.as_str().to_string(),
+            direction: self.direction,
+            key,
+            id,
+        }
+    }
+}
+
+/// One page of a listing
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[aliases(
+    RestaurantPage = CursorPage<RestaurantResponse>,
+    WishlistPage = CursorPage<WishlistItemResponse>,
+    ActivityPage = CursorPage<ActivityResponse>,
+    FollowPage = CursorPage<FollowResponse>,
+    SecurityEventPage = CursorPage<SecurityEventResponse>
+)]
+pub struct CursorPage<T> {
+    /// Items of this page
+    pub items: Vec<T>,
+    /// Cursor to pass back to get the next page, absent on the last page
+    #[schema(example = "eyJzIjoibmFtZSIsImQiOiJhc2MiLCJrIjoiY2hleiBtYXJjZWwiLCJpZCI6MTJ9")]
+    pub next_cursor: Option<String>,
+    /// Total number of items matching the filters
+    #[schema(example = 42)]
+    pub total: i64,
+}
+
+impl<T> CursorPage<T> {
+    /// Convert the items of the page
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CursorPage<U> {
+        CursorPage {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total: self.total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::restaurant::RestaurantSort;
+    use crate::domain::wishlist::WishlistSort;
+
+    fn token(sort: &str, direction: SortDirection, key: &str) -> String {
+        Cursor { sort: sort.to_string(), direction, key: key.to_string(), id: 7 }.encode()
+    }
+
+    fn is_invalid<S>(result: Result<PageRequest<S>>) -> bool {
+        matches!(result, Err(AppError::Validation(_)))
+    }
+
+    #[test]
+    fn cursor_round_trips_through_its_token() {
+        let cursor = Cursor {
+            sort: "name".to_string(),
+            direction: SortDirection::Asc,
+            key: "chez l'ami jean".to_string(),
+            id: 42,
+        };
+
+        let decoded = Cursor::decode(&cursor.encode()).expect("decode");
+
+        assert_eq!(decoded.sort, "name");
+        assert_eq!(decoded.direction, SortDirection::Asc);
+        assert_eq!(decoded.key, "chez l'ami jean");
+        assert_eq!(decoded.id, 42);
+    }
+
+    #[test]
+    fn page_request_accepts_the_cursor_it_issued() {
+        let first = PageRequest::new(RestaurantSort::CreatedAt, None, Some(10), None).expect("first page");
+        let next = first.cursor_after("2025-03-14 10:20:30.123456+00".to_string(), 12).encode();
+
+        let second = PageRequest::new(RestaurantSort::CreatedAt, None, Some(10), Some(&next)).expect("next page");
+
+        let after = second.after.expect("cursor");
+        assert_eq!(after.key, "2025-03-14 10:20:30.123456+00");
+        assert_eq!(after.id, 12);
+        assert_eq!(second.direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn rejects_tokens_that_are_not_cursors() {
+        let not_json = URL_SAFE_NO_PAD.encode(b"{\"s\":\"name\"");
+        for token in ["not a cursor!", "e30", not_json.as_str()] {
+            assert!(matches!(Cursor::decode(token), Err(AppError::Validation(_))), "{}", token);
+        }
+    }
+
+    #[test]
+    fn rejects_cursors_issued_for_another_sort_or_direction() {
+        let by_name = token("name", SortDirection::Asc, "bistro");
+        assert!(is_invalid(PageRequest::new(RestaurantSort::City, None, None, Some(&by_name))));
+
+        let descending = token("name", SortDirection::Desc, "bistro");
+        assert!(is_invalid(PageRequest::new(RestaurantSort::Name, None, None, Some(&descending))));
+    }
+
+    #[test]
+    fn rejects_crafted_keys_of_the_wrong_type() {
+        for key in ["abc", "NaN", "inf", "1; DROP TABLE restaurants"] {
+            let cursor = token("rating", SortDirection::Desc, key);
+            assert!(is_invalid(PageRequest::new(RestaurantSort::Rating, None, None, Some(&cursor))), "{}", key);
+        }
+
+        for key in ["2025-03-14", "yesterday", "2025-03-14 10:20:30"] {
+            let cursor = token("created_at", SortDirection::Desc, key);
+            assert!(is_invalid(PageRequest::new(RestaurantSort::CreatedAt, None, None, Some(&cursor))), "{}", key);
+        }
+
+        let cursor = token("priority", SortDirection::Desc, "urgent");
+        assert!(is_invalid(PageRequest::new(WishlistSort::Priority, None, None, Some(&cursor))));
+    }
+
+    #[test]
+    fn sort_key_kinds_accept_keys_as_postgres_renders_them() {
+        assert!(SortKeyKind::Text.accepts("anything at all"));
+        assert!(SortKeyKind::Decimal.accepts("4.50"));
+        assert!(SortKeyKind::Decimal.accepts("0"));
+        assert!(SortKeyKind::Timestamp.accepts("2025-03-14 10:20:30.123456+00"));
+        assert!(SortKeyKind::Timestamp.accepts("2025-03-14 10:20:30+02"));
+        assert!(SortKeyKind::OneOf(&["low", "medium", "high"]).accepts("high"));
+        assert!(!SortKeyKind::OneOf(&["low", "medium", "high"]).accepts("HIGH"));
+    }
+
+    #[test]
+    fn validates_the_limit_and_ignores_a_blank_cursor() {
+        assert!(is_invalid(PageRequest::new(RestaurantSort::Name, None, Some(0), None)));
+        assert!(is_invalid(PageRequest::new(RestaurantSort::Name, None, Some(MAX_PAGE_SIZE + 1), None)));
+
+        let page = PageRequest::new(RestaurantSort::Name, None, None, Some("  ")).expect("page");
+        assert_eq!(page.limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(page.direction, SortDirection::Asc);
+        assert!(page.after.is_none());
+    }
+}
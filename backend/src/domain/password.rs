@@ -0,0 +1,59 @@
+use crate::domain::user::Password;
+use crate::error::{AppError, Result};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Longest password accepted, so hashing stays cheap
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Leaked passwords refused by the policy, bundled with the binary
+static COMMON_PASSWORDS: LazyLock<HashSet<String>> = LazyLock::new(|| {
+    include_str!("../../data/common_passwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect()
+});
+
+/// Rules a new password must follow
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Refuse passwords from the bundled list of common passwords
+    pub reject_common: bool,
+    /// Refuse passwords containing the username, or contained in it
+    pub reject_username: bool,
+}
+
+impl PasswordPolicy {
+    /// Check a new password of a user
+    pub fn validate(&self, password: String, username: &str) -> Result<Password> {
+        let length = password.chars().count();
+        if length < self.min_length || length > MAX_PASSWORD_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Password must be between {} and {} characters long",
+                self.min_length, MAX_PASSWORD_LENGTH
+            )));
+        }
+
+        let lowered = password.to_lowercase();
+        if self.reject_common && COMMON_PASSWORDS.contains(lowered.as_str()) {
+            return Err(AppError::Validation(
+                "This password is too common, choose another one".into(),
+            ));
+        }
+
+        let username = username.to_lowercase();
+        if self.reject_username
+            && !username.is_empty()
+            && (lowered.contains(&username) || username.contains(&lowered))
+        {
+            return Err(AppError::Validation(
+                "Password must not contain the username".into(),
+            ));
+        }
+
+        Password::new(password)
+    }
+}
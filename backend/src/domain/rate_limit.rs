@@ -0,0 +1,149 @@
+use crate::error::{AppError, Result};
+use std::str::FromStr;
+
+/// Where rate limit buckets are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitStoreKind {
+    /// In the process memory, for a single backend instance
+    Memory,
+}
+
+impl FromStr for RateLimitStoreKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "memory" => Ok(Self::Memory),
+            other => Err(AppError::Internal(format!(
+                "Unknown rate limit store '{}' (expected memory)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Token bucket settings: up to `burst` requests at once, refilled at
+/// `per_minute` requests a minute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// A zero rate or burst turns the limit off
+    pub fn is_enabled(&self) -> bool {
+        self.per_minute > 0 && self.burst > 0
+    }
+
+    /// Tokens added back each second
+    pub fn refill_per_sec(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+}
+
+/// Endpoints sharing a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    /// Login, registration and password reset, where passwords get guessed
+    Auth,
+    /// Autocomplete, called on every keystroke
+    Autocomplete,
+    /// Every other API endpoint
+    Api,
+}
+
+impl RouteGroup {
+    /// Group of a request path, `None` outside the API
+    pub fn for_path(path: &str) -> Option<Self> {
+        let path = path.strip_prefix("/api/v1")?;
+
+        if matches!(
+            path,
+            "/auth/login"
+                | "/auth/login/2fa"
+                | "/auth/register"
+                | "/auth/password-reset"
+                | "/auth/password-reset/confirm"
+                | "/auth/verify-email/resend"
+        ) {
+            Some(RouteGroup::Auth)
+        } else if path.starts_with("/autocomplete/") {
+            Some(RouteGroup::Autocomplete)
+        } else {
+            Some(RouteGroup::Api)
+        }
+    }
+
+    /// Short name used in bucket keys and logs
+    pub fn name(self) -> &'static str {
+        match self {
+            RouteGroup::Auth => "auth",
+            RouteGroup::Autocomplete => "autocomplete",
+            RouteGroup::Api => "api",
+        }
+    }
+}
+
+/// Outcome of taking a token from a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    /// The bucket is empty; a token comes back after this many seconds
+    Limited { retry_after_secs: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_auth_routes_where_passwords_get_guessed() {
+        for path in [
+            "/api/v1/auth/login",
+            "/api/v1/auth/login/2fa",
+            "/api/v1/auth/register",
+            "/api/v1/auth/password-reset",
+            "/api/v1/auth/password-reset/confirm",
+            "/api/v1/auth/verify-email/resend",
+        ] {
+            assert_eq!(RouteGroup::for_path(path), Some(RouteGroup::Auth), "{}", path);
+        }
+    }
+
+    #[test]
+    fn other_auth_routes_count_as_api() {
+        for path in ["/api/v1/auth/me", "/api/v1/auth/logout", "/api/v1/auth/login/extra"] {
+            assert_eq!(RouteGroup::for_path(path), Some(RouteGroup::Api), "{}", path);
+        }
+    }
+
+    #[test]
+    fn groups_autocomplete_routes() {
+        assert_eq!(
+            RouteGroup::for_path("/api/v1/autocomplete/restaurants"),
+            Some(RouteGroup::Autocomplete)
+        );
+        assert_eq!(
+            RouteGroup::for_path("/api/v1/autocomplete/wishlist"),
+            Some(RouteGroup::Autocomplete)
+        );
+        // Only the routes below it
+        assert_eq!(RouteGroup::for_path("/api/v1/autocomplete"), Some(RouteGroup::Api));
+        assert_eq!(RouteGroup::for_path("/api/v1/autocompleted"), Some(RouteGroup::Api));
+    }
+
+    #[test]
+    fn ignores_paths_outside_the_api() {
+        assert_eq!(RouteGroup::for_path("/health"), None);
+        assert_eq!(RouteGroup::for_path("/swagger-ui/index.html"), None);
+        assert_eq!(RouteGroup::for_path("/autocomplete/restaurants"), None);
+    }
+
+    #[test]
+    fn zero_rate_or_burst_disables_the_limit() {
+        assert!(RateLimit { per_minute: 10, burst: 5 }.is_enabled());
+        assert!(!RateLimit { per_minute: 0, burst: 5 }.is_enabled());
+        assert!(!RateLimit { per_minute: 10, burst: 0 }.is_enabled());
+    }
+}
@@ -1,3 +1,7 @@
+use crate::domain::autocomplete::normalize_source_id;
+use crate::domain::geo::{coordinates_update, normalize_address, validate_coordinates};
+use crate::domain::pagination::{PageRequest, SortDirection, SortField, SortKeyKind};
+use crate::domain::tag::{normalize_cuisines, parse_tag_filter, TagId};
 use crate::domain::user::UserId;
 use crate::error::{AppError, Result};
 use chrono::{DateTime, Utc};
@@ -16,9 +20,16 @@ pub struct Restaurant {
     pub owner_id: UserId,
     pub name: String,
     pub city: String,
+    /// Rating given by hand
     pub rating: Option<i16>,
+    /// Mean rating of the rated visits
+    pub visit_rating: Option<f64>,
     pub description: Option<String>,
     pub is_favorite: bool,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub source_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -53,6 +64,14 @@ pub struct CreateRestaurant {
     pub rating: Option<i16>,
     pub description: Option<String>,
     pub is_favorite: bool,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub source_id: Option<String>,
+    /// Existing tags to attach
+    pub tag_ids: Vec<TagId>,
+    /// Cuisine tags to find or create and attach
+    pub cuisines: Vec<String>,
 }
 
 /// Request to create a new restaurant
@@ -74,6 +93,26 @@ pub struct CreateRestaurantRequest {
     #[serde(default)]
     #[schema(default = false)]
     pub is_favorite: bool,
+    /// Street address (optional)
+    #[schema(example = "9 Carrefour de l'Odéon")]
+    pub address: Option<String>,
+    /// Latitude (WGS84), given together with longitude
+    #[schema(example = 48.8521)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84), given together with latitude
+    #[schema(example = 2.3389)]
+    pub longitude: Option<f64>,
+    /// Upstream record of the autocomplete suggestion this comes from
+    #[schema(example = "osm:node/123456")]
+    pub source_id: Option<String>,
+    /// IDs of existing tags to attach
+    #[serde(default)]
+    #[schema(example = json!([1, 4]))]
+    pub tag_ids: Vec<i32>,
+    /// Cuisines from the autocomplete suggestion, attached as cuisine tags
+    #[serde(default)]
+    #[schema(example = json!(["pizza", "italian"]))]
+    pub cuisines: Vec<String>,
 }
 
 impl CreateRestaurantRequest {
@@ -83,6 +122,7 @@ impl CreateRestaurantRequest {
         if let Some(rating) = self.rating {
             Rating::new(rating)?;
         }
+        validate_coordinates(self.latitude, self.longitude)?;
 
         Ok(CreateRestaurant {
             owner_id,
@@ -91,10 +131,61 @@ impl CreateRestaurantRequest {
             rating: self.rating,
             description: self.description,
             is_favorite: self.is_favorite,
+            address: normalize_address(self.address)?,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            source_id: normalize_source_id(self.source_id)?,
+            tag_ids: self.tag_ids.into_iter().map(TagId).collect(),
+            cuisines: normalize_cuisines(self.cuisines),
         })
     }
 }
 
+/// Field restaurant listings can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[schema(example = "name")]
+pub enum RestaurantSort {
+    /// Restaurant name (case-insensitive)
+    Name,
+    /// City (case-insensitive)
+    City,
+    /// Rating, unrated restaurants last when descending
+    Rating,
+    /// Creation date
+    #[default]
+    CreatedAt,
+    /// Last modification date
+    UpdatedAt,
+}
+
+impl SortField for RestaurantSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RestaurantSort::Name => "name",
+            RestaurantSort::City => "city",
+            RestaurantSort::Rating => "rating",
+            RestaurantSort::CreatedAt => "created_at",
+            RestaurantSort::UpdatedAt => "updated_at",
+        }
+    }
+
+    fn default_direction(&self) -> SortDirection {
+        match self {
+            RestaurantSort::Name | RestaurantSort::City => SortDirection::Asc,
+            _ => SortDirection::Desc,
+        }
+    }
+
+    fn key_kind(&self) -> SortKeyKind {
+        match self {
+            RestaurantSort::Name | RestaurantSort::City => SortKeyKind::Text,
+            RestaurantSort::Rating => SortKeyKind::Decimal,
+            RestaurantSort::CreatedAt | RestaurantSort::UpdatedAt => SortKeyKind::Timestamp,
+        }
+    }
+}
+
 /// Query parameters for restaurant listing
 #[derive(Debug, Clone, Default, Deserialize, ToSchema)]
 pub struct RestaurantQuery {
@@ -102,9 +193,88 @@ pub struct RestaurantQuery {
     #[serde(default)]
     #[schema(example = true)]
     pub favorites: Option<bool>,
-    /// Maximum number of results to return
+    /// Maximum number of results to return (defaults to 20)
     #[schema(example = 10, minimum = 1, maximum = 100)]
     pub limit: Option<i64>,
+    /// Comma-separated tag IDs, only restaurants carrying all of them are returned
+    #[schema(example = "1,4")]
+    pub tags: Option<String>,
+    /// Sort field (defaults to created_at)
+    pub sort: Option<RestaurantSort>,
+    /// Sort direction (defaults to asc for name and city, desc otherwise)
+    pub direction: Option<SortDirection>,
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+}
+
+impl RestaurantQuery {
+    /// Parse the tag filter
+    pub fn tag_ids(&self) -> Result<Vec<TagId>> {
+        parse_tag_filter(self.tags.as_deref())
+    }
+
+    /// Validate the pagination and sorting parameters
+    pub fn page(&self) -> Result<PageRequest<RestaurantSort>> {
+        PageRequest::new(
+            self.sort.unwrap_or_default(),
+            self.direction,
+            self.limit,
+            self.cursor.as_deref(),
+        )
+    }
+}
+
+/// Number of top cities reported in statistics
+pub const TOP_CITIES: usize = 5;
+
+/// Number of months covered by the monthly statistics, current month included
+pub const STATS_MONTHS: i32 = 12;
+
+/// Number of restaurants given a rating
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RatingBucket {
+    /// Rating (1-5)
+    #[schema(example = 4)]
+    pub rating: i16,
+    /// Number of restaurants with this rating
+    #[schema(example = 12)]
+    pub count: i64,
+}
+
+/// Number of restaurants in a city
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CityCount {
+    /// City, as first spelled by the user
+    #[schema(example = "Lyon")]
+    pub city: String,
+    /// Number of restaurants in this city
+    #[schema(example = 9)]
+    pub count: i64,
+}
+
+/// Number of restaurants added during a month
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MonthCount {
+    /// Month, as YYYY-MM
+    #[schema(example = "2024-05")]
+    pub month: String,
+    /// Number of restaurants added that month
+    #[schema(example = 3)]
+    pub count: i64,
+}
+
+/// How much of the wishlist ends up in the collection
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WishlistConversionStats {
+    /// Restaurants in the collection that were promoted from the wishlist
+    #[schema(example = 6)]
+    pub promoted: i64,
+    /// Items still waiting on the wishlist
+    #[schema(example = 14)]
+    pub pending: i64,
+    /// Share of promoted items among promoted and pending ones (0 to 1)
+    #[schema(example = 0.3)]
+    pub conversion_rate: f64,
 }
 
 /// Response for restaurant statistics
@@ -116,9 +286,30 @@ pub struct RestaurantStatsResponse {
     /// Number of favorite restaurants
     #[schema(example = 15)]
     pub total_favorites: i64,
-    /// Average rating across all restaurants
-    #[schema(example = "4.2")]
-    pub average_rating: String,
+    /// Share of favorite restaurants (0 to 1)
+    #[schema(example = 0.357)]
+    pub favorite_ratio: f64,
+    /// Number of logged visits across all restaurants
+    #[schema(example = 87)]
+    pub total_visits: i64,
+    /// Average rating across all restaurants, using the mean of the visits
+    /// of a restaurant when it has rated visits and its own rating otherwise.
+    /// Absent when no restaurant is rated.
+    #[schema(example = 4.2)]
+    pub average_rating: Option<f64>,
+    /// Number of restaurants per rating, from 1 to 5
+    pub rating_histogram: Vec<RatingBucket>,
+    /// Number of restaurants without a rating
+    #[schema(example = 5)]
+    pub unrated_restaurants: i64,
+    /// Number of restaurants per city, largest first
+    pub restaurants_per_city: Vec<CityCount>,
+    /// Cities with the most restaurants
+    pub top_cities: Vec<CityCount>,
+    /// Restaurants added per month over the last twelve months, oldest first
+    pub added_per_month: Vec<MonthCount>,
+    /// Wishlist conversion
+    pub wishlist_conversion: WishlistConversionStats,
 }
 
 /// Response for a single restaurant
@@ -133,15 +324,31 @@ pub struct RestaurantResponse {
     /// City location
     #[schema(example = "Paris")]
     pub city: String,
-    /// Rating (1-5 stars, 0 if no rating)
+    /// Rating given by hand (1-5 stars, 0 if no rating)
     #[schema(example = 4, minimum = 0, maximum = 5)]
     pub rating: i16,
+    /// Mean rating of the rated visits, to two decimals. Shown instead of
+    /// `rating` when present; absent while no visit is rated.
+    #[schema(example = 4.33, minimum = 1, maximum = 5)]
+    pub visit_rating: Option<f64>,
     /// Optional description or notes
     #[schema(example = "Great bistro with traditional French cuisine")]
     pub description: Option<String>,
     /// Whether marked as favorite
     #[schema(example = true)]
     pub is_favorite: bool,
+    /// Street address
+    #[schema(example = "9 Carrefour de l'Odéon")]
+    pub address: Option<String>,
+    /// Latitude (WGS84)
+    #[schema(example = 48.8521)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84)
+    #[schema(example = 2.3389)]
+    pub longitude: Option<f64>,
+    /// Upstream record this entry is linked to
+    #[schema(example = "osm:node/123456")]
+    pub source_id: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -155,14 +362,29 @@ impl From<Restaurant> for RestaurantResponse {
             name: restaurant.name,
             city: restaurant.city,
             rating: restaurant.rating.unwrap_or(0),
+            visit_rating: restaurant.visit_rating,
             description: restaurant.description,
             is_favorite: restaurant.is_favorite,
+            address: restaurant.address,
+            latitude: restaurant.latitude,
+            longitude: restaurant.longitude,
+            source_id: restaurant.source_id,
             created_at: restaurant.created_at,
             updated_at: restaurant.updated_at,
         }
     }
 }
 
+/// Restaurant found by a "near me" search
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NearbyRestaurantResponse {
+    #[serde(flatten)]
+    pub restaurant: RestaurantResponse,
+    /// Great-circle distance from the search point, in kilometers
+    #[schema(example = 1.284)]
+    pub distance_km: f64,
+}
+
 /// Command to update a restaurant
 #[derive(Debug, Clone)]
 pub struct UpdateRestaurant {
@@ -173,6 +395,8 @@ pub struct UpdateRestaurant {
     pub rating: Option<Option<i16>>, // None means don't update, Some(None) means clear rating
     pub description: Option<Option<String>>,
     pub is_favorite: Option<bool>,
+    pub address: Option<Option<String>>,
+    pub coordinates: Option<Option<(f64, f64)>>,
 }
 
 /// Request to update a restaurant
@@ -193,6 +417,15 @@ pub struct UpdateRestaurantRequest {
     /// New favorite status (optional)
     #[schema(example = false)]
     pub is_favorite: Option<bool>,
+    /// New street address (optional)
+    #[schema(example = "9 Carrefour de l'Odéon")]
+    pub address: Option<Option<String>>,
+    /// New latitude, updated together with longitude (optional)
+    #[schema(example = 48.8521)]
+    pub latitude: Option<Option<f64>>,
+    /// New longitude, updated together with latitude (optional)
+    #[schema(example = 2.3389)]
+    pub longitude: Option<Option<f64>>,
 }
 
 impl UpdateRestaurantRequest {
@@ -211,6 +444,171 @@ impl UpdateRestaurantRequest {
             rating: self.rating,
             description: self.description,
             is_favorite: self.is_favorite,
+            address: self.address.map(normalize_address).transpose()?,
+            coordinates: coordinates_update(self.latitude, self.longitude)?,
+        })
+    }
+}
+/// Request to search the current user's restaurants
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RestaurantSearchRequest {
+    /// Search terms, matched against name, city and description
+    /// (supports "quoted phrases", `or` and `-exclusion`)
+    #[schema(example = "crêperie bretonne")]
+    pub query: String,
+    /// Page number (1-based, defaults to 1, max 10000)
+    #[schema(example = 1, minimum = 1, maximum = 10000)]
+    pub page: Option<i64>,
+    /// Number of results per page (defaults to 20, max 100)
+    #[schema(example = 20, minimum = 1, maximum = 100)]
+    pub page_size: Option<i64>,
+}
+
+impl RestaurantSearchRequest {
+    /// Convert to domain command
+    pub fn to_command(self, owner_id: UserId) -> Result<RestaurantSearch> {
+        let query = self.query.trim().to_string();
+        if query.is_empty() {
+            return Err(AppError::Validation("Search query cannot be empty".into()));
+        }
+        if query.chars().count() > 200 {
+            return Err(AppError::Validation(
+                "Search query must be at most 200 characters".into(),
+            ));
+        }
+
+        // Bounded so that the offset cannot overflow
+        let page = self.page.unwrap_or(1);
+        if !(1..=10_000).contains(&page) {
+            return Err(AppError::Validation(
+                "Page must be between 1 and 10000".into(),
+            ));
+        }
+
+        let page_size = self.page_size.unwrap_or(20);
+        if !(1..=100).contains(&page_size) {
+            return Err(AppError::Validation(
+                "Page size must be between 1 and 100".into(),
+            ));
+        }
+
+        Ok(RestaurantSearch {
+            owner_id,
+            query,
+            page,
+            page_size,
         })
     }
-}
\ No newline at end of file
+}
+
+/// Command to search restaurants
+#[derive(Debug, Clone)]
+pub struct RestaurantSearch {
+    pub owner_id: UserId,
+    pub query: String,
+    pub page: i64,
+    pub page_size: i64,
+}
+
+impl RestaurantSearch {
+    /// Number of rows to skip for the requested page
+    pub fn offset(&self) -> i64 {
+        (self.page - 1) * self.page_size
+    }
+}
+
+/// A restaurant matched by a search, with its rank and highlighted fields
+#[derive(Debug, Clone)]
+pub struct RestaurantSearchHit {
+    pub restaurant: Restaurant,
+    pub rank: f32,
+    pub name_highlight: String,
+    pub city_highlight: String,
+    pub description_highlight: Option<String>,
+}
+
+/// Marker the database puts before a matched term. Control characters cannot
+/// be mistaken for markup, and the text is HTML-escaped before they are
+/// turned into `<mark>` tags.
+pub const HIGHLIGHT_START: char = '\u{2}';
+
+/// Marker the database puts after a matched term
+pub const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// Turn a snippet marked by the database into HTML safe to display
+fn highlight_html(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+/// Highlighted snippets as HTML: the text is escaped and matched terms are
+/// wrapped in `<mark>` tags
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RestaurantSearchHighlights {
+    /// Highlighted restaurant name
+    #[schema(example = "<mark>Crêperie</mark> du Port &amp; du Large")]
+    pub name: String,
+    /// Highlighted city
+    #[schema(example = "Saint-Malo")]
+    pub city: String,
+    /// Highlighted excerpt of the description
+    #[schema(example = "Les meilleures galettes <mark>bretonnes</mark>")]
+    pub description: Option<String>,
+}
+
+/// A single search result
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RestaurantSearchResult {
+    /// Matched restaurant
+    pub restaurant: RestaurantResponse,
+    /// Relevance score (higher is better)
+    #[schema(example = 0.6)]
+    pub rank: f32,
+    /// Highlighted snippets for display
+    pub highlights: RestaurantSearchHighlights,
+}
+
+impl From<RestaurantSearchHit> for RestaurantSearchResult {
+    fn from(hit: RestaurantSearchHit) -> Self {
+        Self {
+            restaurant: RestaurantResponse::from(hit.restaurant),
+            rank: hit.rank,
+            highlights: RestaurantSearchHighlights {
+                name: highlight_html(&hit.name_highlight),
+                city: highlight_html(&hit.city_highlight),
+                description: hit.description_highlight.as_deref().map(highlight_html),
+            },
+        }
+    }
+}
+
+/// Paginated search results
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RestaurantSearchResponse {
+    /// Results for the current page, best match first
+    pub results: Vec<RestaurantSearchResult>,
+    /// Total number of matching restaurants
+    #[schema(example = 3)]
+    pub total: i64,
+    /// Current page number (1-based)
+    #[schema(example = 1)]
+    pub page: i64,
+    /// Number of results per page
+    #[schema(example = 20)]
+    pub page_size: i64,
+    /// Total number of pages
+    #[schema(example = 1)]
+    pub total_pages: i64,
+}
@@ -1,6 +1,7 @@
 use crate::domain::user::UserId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Session entity
@@ -24,9 +25,59 @@ pub struct CreateSession {
     pub expires_at: DateTime<Utc>,
 }
 
+/// Where a login comes from, recorded with its session
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
 /// Session data stored in actix session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionData {
     pub user_id: i32,
     pub username: String,
-}
\ No newline at end of file
+    /// Row of the `sessions` table, checked on every request
+    pub session_id: Uuid,
+}
+
+/// One of the user's active logins
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SessionResponse {
+    /// Session ID
+    pub id: Uuid,
+    /// Address the login came from
+    #[schema(example = "203.0.113.7")]
+    pub ip_address: Option<String>,
+    /// Browser or app that logged in
+    #[schema(example = "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0")]
+    pub user_agent: Option<String>,
+    /// Login timestamp
+    pub created_at: DateTime<Utc>,
+    /// When the session stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session making the request
+    #[schema(example = true)]
+    pub current: bool,
+}
+
+impl SessionResponse {
+    pub fn from_session(session: Session, current_session_id: Uuid) -> Self {
+        Self {
+            current: session.id == current_session_id,
+            id: session.id,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            created_at: session.created_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// Result of logging out every other session
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RevokeSessionsResponse {
+    /// Number of other sessions that were logged out
+    #[schema(example = 2)]
+    pub revoked: u64,
+}
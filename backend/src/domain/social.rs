@@ -0,0 +1,212 @@
+use crate::domain::pagination::{PageRequest, SortDirection, SortField, SortKeyKind};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::user::UserId;
+use crate::domain::wishlist::WishlistId;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Unique identifier for an activity event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct ActivityId(pub i32);
+
+/// What happened in an activity event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "activity_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[schema(example = "restaurant_added")]
+pub enum ActivityKind {
+    /// A restaurant was added to the collection
+    RestaurantAdded,
+    /// A restaurant was given a new rating
+    RestaurantRated,
+    /// A restaurant was added to the wishlist
+    WishlistAdded,
+}
+
+/// Activity event, as shown in followers' feeds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub id: ActivityId,
+    pub user_id: UserId,
+    pub username: String,
+    pub kind: ActivityKind,
+    pub restaurant_id: Option<RestaurantId>,
+    pub wishlist_item_id: Option<WishlistId>,
+    pub name: String,
+    pub city: String,
+    pub rating: Option<i16>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Command for recording an activity event
+#[derive(Debug, Clone)]
+pub struct RecordActivity {
+    pub user_id: UserId,
+    pub kind: ActivityKind,
+    pub restaurant_id: Option<RestaurantId>,
+    pub wishlist_item_id: Option<WishlistId>,
+    pub name: String,
+    pub city: String,
+    pub rating: Option<i16>,
+}
+
+/// Another user, as listed among followers or followed users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Follow {
+    pub user_id: UserId,
+    pub username: String,
+    pub followed_at: DateTime<Utc>,
+}
+
+/// Public view of a user profile
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    pub user_id: UserId,
+    pub username: String,
+    pub is_private: bool,
+    pub follower_count: i64,
+    pub following_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Social listings are always newest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Chronological;
+
+impl SortField for Chronological {
+    fn as_str(&self) -> &'static str {
+        "created_at"
+    }
+
+    fn default_direction(&self) -> SortDirection {
+        SortDirection::Desc
+    }
+
+    fn key_kind(&self) -> SortKeyKind {
+        SortKeyKind::Timestamp
+    }
+}
+
+/// Pagination parameters for feeds and follower listings
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct SocialPageQuery {
+    /// Maximum number of results to return (defaults to 20)
+    #[schema(example = 20, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+}
+
+impl SocialPageQuery {
+    /// Validate the pagination parameters
+    pub fn page(&self) -> Result<PageRequest<Chronological>> {
+        PageRequest::new(Chronological, None, self.limit, self.cursor.as_deref())
+    }
+}
+
+/// Request to change the privacy setting
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PrivacySettingsRequest {
+    /// Private profiles cannot be followed and their activity is hidden
+    #[schema(example = true)]
+    pub is_private: bool,
+}
+
+/// Current privacy setting
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PrivacySettingsResponse {
+    /// Private profiles cannot be followed and their activity is hidden.
+    /// Profiles are private until their owner makes them public.
+    #[schema(example = true)]
+    pub is_private: bool,
+}
+
+/// Response for an activity event
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ActivityResponse {
+    /// Activity ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// Username of the user who did it
+    #[schema(example = "marie")]
+    pub username: String,
+    /// What happened
+    pub kind: ActivityKind,
+    /// Restaurant concerned, for restaurant events
+    #[schema(example = 12)]
+    pub restaurant_id: Option<i32>,
+    /// Wishlist item concerned, for wishlist events
+    #[schema(example = json!(null))]
+    pub wishlist_item_id: Option<i32>,
+    /// Restaurant name when the event happened
+    #[schema(example = "Chez Marcel")]
+    pub name: String,
+    /// City when the event happened
+    #[schema(example = "Lyon")]
+    pub city: String,
+    /// Rating given, for rated or added restaurants
+    #[schema(example = 5)]
+    pub rating: Option<i16>,
+    /// When it happened
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ActivityEvent> for ActivityResponse {
+    fn from(event: ActivityEvent) -> Self {
+        Self {
+            id: event.id.0,
+            username: event.username,
+            kind: event.kind,
+            restaurant_id: event.restaurant_id.map(|id| id.0),
+            wishlist_item_id: event.wishlist_item_id.map(|id| id.0),
+            name: event.name,
+            city: event.city,
+            rating: event.rating,
+            created_at: event.created_at,
+        }
+    }
+}
+
+/// Response for a follower or followed user
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FollowResponse {
+    /// Username
+    #[schema(example = "marie")]
+    pub username: String,
+    /// When the follow started
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<Follow> for FollowResponse {
+    fn from(follow: Follow) -> Self {
+        Self {
+            username: follow.username,
+            followed_at: follow.followed_at,
+        }
+    }
+}
+
+/// Response for a user profile
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserProfileResponse {
+    /// Username
+    #[schema(example = "marie")]
+    pub username: String,
+    /// Whether the profile is private
+    #[schema(example = false)]
+    pub is_private: bool,
+    /// Number of followers
+    #[schema(example = 12)]
+    pub follower_count: i64,
+    /// Number of followed users
+    #[schema(example = 8)]
+    pub following_count: i64,
+    /// Whether the current user follows this profile
+    #[schema(example = true)]
+    pub is_followed: bool,
+    /// Account creation timestamp
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,207 @@
+use crate::domain::user::UserId;
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Unique identifier for a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TagId(pub i32);
+
+/// Category of a tag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "tag_kind", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[schema(example = "cuisine")]
+pub enum TagKind {
+    /// Cuisine taxonomy (italian, japanese, crêpes...)
+    Cuisine,
+    /// Type of establishment (restaurant, café, bar...)
+    Type,
+    /// Free-form user tag
+    #[default]
+    Custom,
+}
+
+/// Tag entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: TagId,
+    pub owner_id: UserId,
+    pub name: String,
+    pub kind: TagKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Validate and normalize a tag name
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 50 {
+        return Err(AppError::Validation(
+            "Tag name must be between 1 and 50 characters".into(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Split an OpenStreetMap `cuisine` value ("pizza;italian") into tag names
+pub fn parse_cuisines(raw: &str) -> Vec<String> {
+    let mut cuisines: Vec<String> = Vec::new();
+    for value in raw.split([';', ',']) {
+        let value = value.trim().replace('_', " ").to_lowercase();
+        if !value.is_empty() && value.chars().count() <= 50 && !cuisines.contains(&value) {
+            cuisines.push(value);
+        }
+    }
+    cuisines
+}
+
+/// Clean up the cuisine names sent with an autocomplete suggestion, dropping
+/// empty, over-long and repeated ones
+pub fn normalize_cuisines(cuisines: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for cuisine in cuisines {
+        let name = cuisine.trim();
+        if !name.is_empty()
+            && name.chars().count() <= 50
+            && !normalized.iter().any(|n| n.to_lowercase() == name.to_lowercase())
+        {
+            normalized.push(name.to_string());
+        }
+    }
+    normalized
+}
+
+/// Parse a comma-separated list of tag IDs from a query string ("1,4,7")
+pub fn parse_tag_filter(raw: Option<&str>) -> Result<Vec<TagId>> {
+    let Some(raw) = raw else {
+        return Ok(Vec::new());
+    };
+
+    let mut tag_ids = Vec::new();
+    for value in raw.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+        let id = value
+            .parse::<i32>()
+            .map_err(|_| AppError::Validation(format!("Invalid tag ID: {}", value)))?;
+        if !tag_ids.contains(&TagId(id)) {
+            tag_ids.push(TagId(id));
+        }
+    }
+    Ok(tag_ids)
+}
+
+/// Command to create a new tag
+#[derive(Debug, Clone)]
+pub struct CreateTag {
+    pub owner_id: UserId,
+    pub name: String,
+    pub kind: TagKind,
+}
+
+/// Request to create a new tag
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateTagRequest {
+    /// Tag name (1-50 characters, unique per user)
+    #[schema(example = "date night")]
+    pub name: String,
+    /// Tag category (defaults to custom)
+    #[serde(default)]
+    #[schema(default = "custom")]
+    pub kind: TagKind,
+}
+
+impl CreateTagRequest {
+    /// Convert to domain command
+    pub fn to_command(self, owner_id: UserId) -> Result<CreateTag> {
+        Ok(CreateTag {
+            owner_id,
+            name: validate_name(&self.name)?,
+            kind: self.kind,
+        })
+    }
+}
+
+/// Command to update a tag
+#[derive(Debug, Clone)]
+pub struct UpdateTag {
+    pub id: TagId,
+    pub owner_id: UserId,
+    pub name: Option<String>,
+    pub kind: Option<TagKind>,
+}
+
+/// Request to update a tag
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateTagRequest {
+    /// New tag name (optional)
+    #[schema(example = "brunch")]
+    pub name: Option<String>,
+    /// New tag category (optional)
+    #[schema(example = "custom")]
+    pub kind: Option<TagKind>,
+}
+
+impl UpdateTagRequest {
+    /// Convert to domain command
+    pub fn to_command(self, id: TagId, owner_id: UserId) -> Result<UpdateTag> {
+        let name = match self.name {
+            Some(name) => Some(validate_name(&name)?),
+            None => None,
+        };
+
+        Ok(UpdateTag {
+            id,
+            owner_id,
+            name,
+            kind: self.kind,
+        })
+    }
+}
+
+/// Request to replace the tags of a restaurant or wishlist item
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SetTagsRequest {
+    /// IDs of the tags to attach (replaces any existing tags)
+    #[schema(example = json!([1, 4]))]
+    pub tag_ids: Vec<i32>,
+}
+
+impl SetTagsRequest {
+    /// Get the deduplicated tag IDs
+    pub fn tag_ids(&self) -> Vec<TagId> {
+        let mut tag_ids = Vec::new();
+        for id in &self.tag_ids {
+            if !tag_ids.contains(&TagId(*id)) {
+                tag_ids.push(TagId(*id));
+            }
+        }
+        tag_ids
+    }
+}
+
+/// Response for a single tag
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TagResponse {
+    /// Tag ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// Tag name
+    #[schema(example = "italian")]
+    pub name: String,
+    /// Tag category
+    pub kind: TagKind,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id.0,
+            name: tag.name,
+            kind: tag.kind,
+            created_at: tag.created_at,
+        }
+    }
+}
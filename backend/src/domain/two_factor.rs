@@ -0,0 +1,118 @@
+use crate::domain::user::UserId;
+use crate::domain::token::random_bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Number of recovery codes handed out at once
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Name shown for the account in authenticator apps
+pub const TOTP_ISSUER: &str = "Gourmestre";
+
+/// Seconds each TOTP code stays current
+pub const TOTP_STEP_SECS: u64 = 30;
+
+/// TOTP settings of a user
+#[derive(Debug, Clone)]
+pub struct TwoFactor {
+    pub user_id: UserId,
+    /// Base32 secret shared with the authenticator app
+    pub secret: String,
+    /// `None` while the setup waits for its first code
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub last_used_step: Option<i64>,
+}
+
+impl TwoFactor {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+/// Generate 160 random bits for a TOTP secret, the size RFC 4226 recommends
+pub fn generate_totp_secret() -> Vec<u8> {
+    random_bytes::<20>().to_vec()
+}
+
+/// Generate a recovery code such as `3f9a-0c2e-b71d-58e4-a0c9`. Its 80 random
+/// bits keep the fast hash it is stored under out of reach of brute force.
+pub fn generate_recovery_code() -> String {
+    random_bytes::<10>()
+        .chunks(2)
+        .map(|pair| format!("{:02x}{:02x}", pair[0], pair[1]))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Form of a recovery code that gets hashed, ignoring case, dashes and spaces
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Whether a code looks like a TOTP code rather than a recovery code
+pub fn is_totp_code(code: &str) -> bool {
+    code.len() == 6 && code.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Two-factor status of the logged in user
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorStatusResponse {
+    /// Whether logins ask for a code
+    #[schema(example = true)]
+    pub enabled: bool,
+    /// Unused recovery codes left
+    #[schema(example = 10)]
+    pub recovery_codes_remaining: i64,
+}
+
+/// Secret to add to an authenticator app
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorSetupResponse {
+    /// Base32 secret, for apps that cannot scan the URI
+    #[schema(example = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")]
+    pub secret: String,
+    /// `otpauth://` URI, usually shown as a QR code
+    #[schema(example = "otpauth://totp/Gourmestre:john_doe?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=Gourmestre")]
+    pub otpauth_uri: String,
+}
+
+/// A code from the authenticator app, or a recovery code
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TwoFactorCodeRequest {
+    #[schema(example = "123456")]
+    pub code: String,
+}
+
+/// Recovery codes, shown only once
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    #[schema(example = json!(["3f9a-0c2e-b71d-58e4-a0c9", "88c1-e0a4-5f27-d3b0-19fe"]))]
+    pub recovery_codes: Vec<String>,
+}
+
+/// Second step of a login with two-factor authentication
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct LoginTwoFactorRequest {
+    /// Challenge returned by the login
+    #[schema(example = "q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub challenge: String,
+    /// A code from the authenticator app, or a recovery code
+    #[schema(example = "123456")]
+    pub code: String,
+}
+
+/// Answer to a login whose password was right but which needs a code
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LoginChallengeResponse {
+    #[schema(example = "Two-factor code required")]
+    pub message: String,
+    #[schema(example = true)]
+    pub two_factor_required: bool,
+    /// Post it with the code to `/auth/login/2fa` within 5 minutes
+    #[schema(example = "q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub challenge: String,
+}
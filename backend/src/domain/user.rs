@@ -21,6 +21,8 @@ pub struct User {
     pub failed_login_attempts: i32,
     pub last_login: Option<DateTime<Utc>>,
     pub account_locked_until: Option<DateTime<Utc>>,
+    pub is_private: bool,
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 /// Validated username
@@ -64,14 +66,27 @@ impl Username {
 pub struct Email(String);
 
 impl Email {
-    /// Create a new email with basic validation
+    /// Create a new email with basic validation. Emails are compared
+    /// case-insensitively, so they are stored in lowercase.
     pub fn new(value: String) -> Result<Self> {
+        let value = value.trim().to_lowercase();
         if value.len() > 254 {
             return Err(AppError::Validation("Email is too long".into()));
         }
 
         // Basic email validation - in production, use a proper email validation library
-        if !value.contains('@') {
+        let valid = match value.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !domain.contains('@')
+                    && !value.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+        if !valid {
             return Err(AppError::Validation("Invalid email format".into()));
         }
 
@@ -94,12 +109,11 @@ impl Email {
 pub struct Password(String);
 
 impl Password {
-    /// Create a new password with validation
+    /// Create a new password with validation. New passwords must also
+    /// pass the `PasswordPolicy`.
     pub fn new(value: String) -> Result<Self> {
-        if value.len() < 4 {
-            return Err(AppError::Validation(
-                "Password must be at least 4 characters long".into(),
-            ));
+        if value.is_empty() {
+            return Err(AppError::Validation("Password cannot be empty".into()));
         }
 
         Ok(Self(value))
@@ -146,6 +160,9 @@ pub struct RegisterUserCommand {
     /// Username (3-50 characters, alphanumeric + underscore/dash)
     #[schema(example = "john_doe")]
     pub username: String,
+    /// Email address, to be verified
+    #[schema(example = "john@example.com")]
+    pub email: String,
     /// Password (minimum 8 characters)
     #[schema(example = "secretpassword123", min_length = 8)]
     pub password: String,
@@ -188,6 +205,12 @@ pub struct UserInfoResponse {
     /// Email address
     #[schema(example = "john@example.com")]
     pub email: String,
+    /// Whether the email address was verified
+    #[schema(example = true)]
+    pub email_verified: bool,
+    /// Whether the profile is private (cannot be followed, activity hidden)
+    #[schema(example = true)]
+    pub is_private: bool,
     /// Account creation timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -201,7 +224,47 @@ impl From<&User> for UserInfoResponse {
             id: user.id.0,
             username: user.username.clone(),
             email: user.email.clone(),
+            email_verified: user.email_verified_at.is_some(),
+            is_private: user.is_private,
             created_at: user.created_at,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Request to change the password of the logged in user
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    /// Password in use
+    #[schema(example = "secretpassword123")]
+    pub current_password: String,
+    /// New password, checked against the password policy
+    #[schema(example = "an0ther-long-passphrase")]
+    pub new_password: String,
+}
+
+/// Request to change the username of the logged in user
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChangeUsernameRequest {
+    /// New username (3-50 characters, alphanumeric + underscore/dash)
+    #[schema(example = "jane_doe")]
+    pub username: String,
+}
+
+/// Request to change the email address of the logged in user
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChangeEmailRequest {
+    /// New email address, to be verified
+    #[schema(example = "jane@example.com")]
+    pub email: String,
+    /// Password in use
+    #[schema(example = "secretpassword123")]
+    pub current_password: String,
+}
+
+/// Request to delete the account of the logged in user
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    /// Password in use
+    #[schema(example = "secretpassword123")]
+    pub password: String,
+}
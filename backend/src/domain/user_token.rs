@@ -0,0 +1,64 @@
+use crate::domain::user::UserId;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// What a single-use token handed to a user is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "user_token_purpose", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserTokenPurpose {
+    /// Proves the user owns their email address
+    EmailVerification,
+    /// Lets the user choose a new password
+    PasswordReset,
+    /// Lets a login whose password was right post its two-factor code
+    LoginChallenge,
+}
+
+impl UserTokenPurpose {
+    /// How long a token stays usable
+    pub fn lifetime(self) -> Duration {
+        match self {
+            UserTokenPurpose::EmailVerification => Duration::hours(48),
+            UserTokenPurpose::PasswordReset => Duration::hours(1),
+            UserTokenPurpose::LoginChallenge => Duration::minutes(5),
+        }
+    }
+}
+
+/// Command to store a new single-use token
+#[derive(Debug, Clone)]
+pub struct CreateUserToken {
+    pub user_id: UserId,
+    pub purpose: UserTokenPurpose,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Request to verify an email address
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct VerifyEmailRequest {
+    /// Token from the verification email
+    #[schema(example = "q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub token: String,
+}
+
+/// Request to receive a password reset email
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PasswordResetRequest {
+    /// Email address of the account
+    #[schema(example = "john@example.com")]
+    pub email: String,
+}
+
+/// Request to choose a new password with a reset token
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PasswordResetConfirmRequest {
+    /// Token from the password reset email
+    #[schema(example = "q1Xh6o7sR9-4Zb0nWcY2kA8uJ3vT5mEfGdHiKlPrS0U")]
+    pub token: String,
+    /// New password
+    #[schema(example = "newsecretpassword123")]
+    pub new_password: String,
+}
@@ -0,0 +1,242 @@
+use crate::domain::restaurant::{Rating, RestaurantId};
+use crate::domain::user::UserId;
+use crate::error::{AppError, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Unique identifier for a visit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct VisitId(pub i32);
+
+/// A single meal at a restaurant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Visit {
+    pub id: VisitId,
+    pub restaurant_id: RestaurantId,
+    pub owner_id: UserId,
+    pub visited_on: NaiveDate,
+    pub rating: Option<i16>,
+    pub party_size: Option<i16>,
+    pub spend: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Maximum length of the notes about a visit
+const MAX_NOTES_LENGTH: usize = 2000;
+
+/// Largest spend the NUMERIC(10, 2) column holds
+const MAX_SPEND: f64 = 99_999_999.99;
+
+/// Validate the optional details shared by create and update requests
+fn validate_details(
+    visited_on: Option<NaiveDate>,
+    rating: Option<i16>,
+    party_size: Option<i16>,
+    spend: Option<f64>,
+    notes: Option<&str>,
+) -> Result<()> {
+    if let Some(visited_on) = visited_on
+        && visited_on > Utc::now().date_naive()
+    {
+        return Err(AppError::Validation("Visit date cannot be in the future".into()));
+    }
+
+    if let Some(rating) = rating {
+        Rating::new(rating)?;
+    }
+
+    if let Some(party_size) = party_size
+        && !(1..=100).contains(&party_size)
+    {
+        return Err(AppError::Validation(
+            "Party size must be between 1 and 100".into(),
+        ));
+    }
+
+    if let Some(spend) = spend
+        && !(0.0..=MAX_SPEND).contains(&spend)
+    {
+        return Err(AppError::Validation(format!(
+            "Spend must be between 0 and {}",
+            MAX_SPEND
+        )));
+    }
+
+    if let Some(notes) = notes
+        && notes.chars().count() > MAX_NOTES_LENGTH
+    {
+        return Err(AppError::Validation(format!(
+            "Notes must be at most {} characters",
+            MAX_NOTES_LENGTH
+        )));
+    }
+
+    Ok(())
+}
+
+/// Command to log a new visit
+#[derive(Debug, Clone)]
+pub struct CreateVisit {
+    pub restaurant_id: RestaurantId,
+    pub owner_id: UserId,
+    pub visited_on: NaiveDate,
+    pub rating: Option<i16>,
+    pub party_size: Option<i16>,
+    pub spend: Option<f64>,
+    pub notes: Option<String>,
+}
+
+/// Request to log a new visit
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateVisitRequest {
+    /// Date of the meal (defaults to today)
+    #[schema(example = "2025-03-14")]
+    pub visited_on: Option<NaiveDate>,
+    /// Rating for this visit from 1 to 5 stars (optional)
+    #[schema(example = 4, minimum = 1, maximum = 5)]
+    pub rating: Option<i16>,
+    /// Number of people at the table (optional)
+    #[schema(example = 2, minimum = 1, maximum = 100)]
+    pub party_size: Option<i16>,
+    /// Total amount spent (optional)
+    #[schema(example = 64.5, minimum = 0, maximum = 99999999.99)]
+    pub spend: Option<f64>,
+    /// Notes about the meal (optional, at most 2000 characters)
+    #[schema(example = "Tried the tasting menu, the dessert was outstanding")]
+    pub notes: Option<String>,
+}
+
+impl CreateVisitRequest {
+    /// Convert to domain command
+    pub fn to_command(self, restaurant_id: RestaurantId, owner_id: UserId) -> Result<CreateVisit> {
+        validate_details(
+            self.visited_on,
+            self.rating,
+            self.party_size,
+            self.spend,
+            self.notes.as_deref(),
+        )?;
+
+        Ok(CreateVisit {
+            restaurant_id,
+            owner_id,
+            visited_on: self.visited_on.unwrap_or_else(|| Utc::now().date_naive()),
+            rating: self.rating,
+            party_size: self.party_size,
+            spend: self.spend,
+            notes: self.notes,
+        })
+    }
+}
+
+/// Command to update a visit
+#[derive(Debug, Clone)]
+pub struct UpdateVisit {
+    pub id: VisitId,
+    pub restaurant_id: RestaurantId,
+    pub owner_id: UserId,
+    pub visited_on: Option<NaiveDate>,
+    pub rating: Option<Option<i16>>,
+    pub party_size: Option<Option<i16>>,
+    pub spend: Option<Option<f64>>,
+    pub notes: Option<Option<String>>,
+}
+
+/// Request to update a visit
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateVisitRequest {
+    /// New visit date (optional)
+    #[schema(example = "2025-03-15")]
+    pub visited_on: Option<NaiveDate>,
+    /// New rating (None = don't update, Some(None) = clear rating, Some(Some(value)) = set rating)
+    #[schema(example = 5, minimum = 1, maximum = 5)]
+    pub rating: Option<Option<i16>>,
+    /// New party size (None = don't update, Some(None) = clear, Some(Some(value)) = set)
+    #[schema(example = 4, minimum = 1, maximum = 100)]
+    pub party_size: Option<Option<i16>>,
+    /// New spend (None = don't update, Some(None) = clear, Some(Some(value)) = set)
+    #[schema(example = 120.0, minimum = 0, maximum = 99999999.99)]
+    pub spend: Option<Option<f64>>,
+    /// New notes (None = don't update, Some(None) = clear notes, Some(Some(value)) = set notes)
+    #[schema(example = "Went back for the dessert")]
+    pub notes: Option<Option<String>>,
+}
+
+impl UpdateVisitRequest {
+    /// Convert to domain command
+    pub fn to_command(
+        self,
+        id: VisitId,
+        restaurant_id: RestaurantId,
+        owner_id: UserId,
+    ) -> Result<UpdateVisit> {
+        validate_details(
+            self.visited_on,
+            self.rating.flatten(),
+            self.party_size.flatten(),
+            self.spend.flatten(),
+            self.notes.as_ref().and_then(|notes| notes.as_deref()),
+        )?;
+
+        Ok(UpdateVisit {
+            id,
+            restaurant_id,
+            owner_id,
+            visited_on: self.visited_on,
+            rating: self.rating,
+            party_size: self.party_size,
+            spend: self.spend,
+            notes: self.notes,
+        })
+    }
+}
+
+/// Response for a single visit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VisitResponse {
+    /// Visit ID
+    #[schema(example = 1)]
+    pub id: i32,
+    /// Restaurant ID
+    #[schema(example = 1)]
+    pub restaurant_id: i32,
+    /// Date of the meal
+    #[schema(example = "2025-03-14")]
+    pub visited_on: NaiveDate,
+    /// Rating for this visit (1-5 stars)
+    #[schema(example = 4, minimum = 1, maximum = 5)]
+    pub rating: Option<i16>,
+    /// Number of people at the table
+    #[schema(example = 2)]
+    pub party_size: Option<i16>,
+    /// Total amount spent
+    #[schema(example = 64.5)]
+    pub spend: Option<f64>,
+    /// Notes about the meal
+    #[schema(example = "Tried the tasting menu, the dessert was outstanding")]
+    pub notes: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Visit> for VisitResponse {
+    fn from(visit: Visit) -> Self {
+        Self {
+            id: visit.id.0,
+            restaurant_id: visit.restaurant_id.0,
+            visited_on: visit.visited_on,
+            rating: visit.rating,
+            party_size: visit.party_size,
+            spend: visit.spend,
+            notes: visit.notes,
+            created_at: visit.created_at,
+            updated_at: visit.updated_at,
+        }
+    }
+}
@@ -1,4 +1,9 @@
+use crate::domain::autocomplete::normalize_source_id;
+use crate::domain::geo::{coordinates_update, normalize_address, validate_coordinates};
+use crate::domain::pagination::{PageRequest, SortDirection, SortField, SortKeyKind};
+use crate::domain::tag::{normalize_cuisines, parse_tag_filter, TagId};
 use crate::domain::user::UserId;
+use crate::error::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -17,12 +22,16 @@ pub struct WishlistItem {
     pub city: String,
     pub notes: Option<String>,
     pub priority: WishlistPriority,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub source_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 /// Priority level for wishlist items
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "wishlist_priority", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 #[schema(example = "medium")]
@@ -30,17 +39,12 @@ pub enum WishlistPriority {
     /// Low priority - nice to try someday
     Low,
     /// Medium priority - should visit soon
+    #[default]
     Medium,
     /// High priority - must visit as soon as possible
     High,
 }
 
-impl Default for WishlistPriority {
-    fn default() -> Self {
-        Self::Medium
-    }
-}
-
 /// Command to create a new wishlist item
 #[derive(Debug, Clone)]
 pub struct CreateWishlistItem {
@@ -49,6 +53,14 @@ pub struct CreateWishlistItem {
     pub city: String,
     pub notes: Option<String>,
     pub priority: WishlistPriority,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub source_id: Option<String>,
+    /// Existing tags to attach
+    pub tag_ids: Vec<TagId>,
+    /// Cuisine tags to find or create and attach
+    pub cuisines: Vec<String>,
 }
 
 /// Request to create a new wishlist item
@@ -67,17 +79,90 @@ pub struct CreateWishlistRequest {
     #[serde(default)]
     #[schema(default = "medium")]
     pub priority: WishlistPriority,
+    /// Street address (optional)
+    #[schema(example = "27 Rue Malar")]
+    pub address: Option<String>,
+    /// Latitude (WGS84), given together with longitude
+    #[schema(example = 48.8596)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84), given together with latitude
+    #[schema(example = 2.3057)]
+    pub longitude: Option<f64>,
+    /// Upstream record of the autocomplete suggestion this comes from
+    #[schema(example = "osm:node/123456")]
+    pub source_id: Option<String>,
+    /// IDs of existing tags to attach
+    #[serde(default)]
+    #[schema(example = json!([2]))]
+    pub tag_ids: Vec<i32>,
+    /// Cuisines from the autocomplete suggestion, attached as cuisine tags
+    #[serde(default)]
+    #[schema(example = json!(["french"]))]
+    pub cuisines: Vec<String>,
 }
 
 impl CreateWishlistRequest {
     /// Convert to domain command
-    pub fn to_command(self, owner_id: UserId) -> CreateWishlistItem {
-        CreateWishlistItem {
+    pub fn to_command(self, owner_id: UserId) -> Result<CreateWishlistItem> {
+        validate_coordinates(self.latitude, self.longitude)?;
+
+        Ok(CreateWishlistItem {
             owner_id,
             name: self.name,
             city: self.city,
             notes: self.notes,
             priority: self.priority,
+            address: normalize_address(self.address)?,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            source_id: normalize_source_id(self.source_id)?,
+            tag_ids: self.tag_ids.into_iter().map(TagId).collect(),
+            cuisines: normalize_cuisines(self.cuisines),
+        })
+    }
+}
+
+/// Field wishlist listings can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[schema(example = "priority")]
+pub enum WishlistSort {
+    /// Restaurant name (case-insensitive)
+    Name,
+    /// City (case-insensitive)
+    City,
+    /// Priority level
+    #[default]
+    Priority,
+    /// Creation date
+    CreatedAt,
+    /// Last modification date
+    UpdatedAt,
+}
+
+impl SortField for WishlistSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WishlistSort::Name => "name",
+            WishlistSort::City => "city",
+            WishlistSort::Priority => "priority",
+            WishlistSort::CreatedAt => "created_at",
+            WishlistSort::UpdatedAt => "updated_at",
+        }
+    }
+
+    fn default_direction(&self) -> SortDirection {
+        match self {
+            WishlistSort::Name | WishlistSort::City => SortDirection::Asc,
+            _ => SortDirection::Desc,
+        }
+    }
+
+    fn key_kind(&self) -> SortKeyKind {
+        match self {
+            WishlistSort::Name | WishlistSort::City => SortKeyKind::Text,
+            WishlistSort::Priority => SortKeyKind::OneOf(&["low", "medium", "high"]),
+            WishlistSort::CreatedAt | WishlistSort::UpdatedAt => SortKeyKind::Timestamp,
         }
     }
 }
@@ -88,9 +173,35 @@ pub struct WishlistQuery {
     /// Filter by priority level
     #[schema(example = "high")]
     pub priority: Option<WishlistPriority>,
-    /// Maximum number of results to return
+    /// Maximum number of results to return (defaults to 20)
     #[schema(example = 10, minimum = 1, maximum = 100)]
     pub limit: Option<i64>,
+    /// Comma-separated tag IDs, only items carrying all of them are returned
+    #[schema(example = "2")]
+    pub tags: Option<String>,
+    /// Sort field (defaults to priority)
+    pub sort: Option<WishlistSort>,
+    /// Sort direction (defaults to asc for name and city, desc otherwise)
+    pub direction: Option<SortDirection>,
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+}
+
+impl WishlistQuery {
+    /// Parse the tag filter
+    pub fn tag_ids(&self) -> Result<Vec<TagId>> {
+        parse_tag_filter(self.tags.as_deref())
+    }
+
+    /// Validate the pagination and sorting parameters
+    pub fn page(&self) -> Result<PageRequest<WishlistSort>> {
+        PageRequest::new(
+            self.sort.unwrap_or_default(),
+            self.direction,
+            self.limit,
+            self.cursor.as_deref(),
+        )
+    }
 }
 
 /// Response for wishlist count
@@ -118,6 +229,18 @@ pub struct WishlistItemResponse {
     pub notes: Option<String>,
     /// Priority level
     pub priority: WishlistPriority,
+    /// Street address
+    #[schema(example = "27 Rue Malar")]
+    pub address: Option<String>,
+    /// Latitude (WGS84)
+    #[schema(example = 48.8596)]
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84)
+    #[schema(example = 2.3057)]
+    pub longitude: Option<f64>,
+    /// Upstream record this entry is linked to
+    #[schema(example = "osm:node/123456")]
+    pub source_id: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -132,12 +255,26 @@ impl From<WishlistItem> for WishlistItemResponse {
             city: item.city,
             notes: item.notes,
             priority: item.priority,
+            address: item.address,
+            latitude: item.latitude,
+            longitude: item.longitude,
+            source_id: item.source_id,
             created_at: item.created_at,
             updated_at: item.updated_at,
         }
     }
 }
 
+/// Wishlist item found by a "near me" search
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NearbyWishlistItemResponse {
+    #[serde(flatten)]
+    pub item: WishlistItemResponse,
+    /// Great-circle distance from the search point, in kilometers
+    #[schema(example = 0.742)]
+    pub distance_km: f64,
+}
+
 /// Command to update a wishlist item
 #[derive(Debug, Clone)]
 pub struct UpdateWishlistItem {
@@ -147,6 +284,8 @@ pub struct UpdateWishlistItem {
     pub city: Option<String>,
     pub notes: Option<Option<String>>,
     pub priority: Option<WishlistPriority>,
+    pub address: Option<Option<String>>,
+    pub coordinates: Option<Option<(f64, f64)>>,
 }
 
 /// Request to update a wishlist item
@@ -164,18 +303,29 @@ pub struct UpdateWishlistRequest {
     /// New priority level (optional)
     #[schema(example = "high")]
     pub priority: Option<WishlistPriority>,
+    /// New street address (optional)
+    #[schema(example = "27 Rue Malar")]
+    pub address: Option<Option<String>>,
+    /// New latitude, updated together with longitude (optional)
+    #[schema(example = 48.8596)]
+    pub latitude: Option<Option<f64>>,
+    /// New longitude, updated together with latitude (optional)
+    #[schema(example = 2.3057)]
+    pub longitude: Option<Option<f64>>,
 }
 
 impl UpdateWishlistRequest {
     /// Convert to domain command
-    pub fn to_command(self, id: WishlistId, owner_id: UserId) -> UpdateWishlistItem {
-        UpdateWishlistItem {
+    pub fn to_command(self, id: WishlistId, owner_id: UserId) -> Result<UpdateWishlistItem> {
+        Ok(UpdateWishlistItem {
             id,
             owner_id,
             name: self.name,
             city: self.city,
             notes: self.notes,
             priority: self.priority,
-        }
+            address: self.address.map(normalize_address).transpose()?,
+            coordinates: coordinates_update(self.latitude, self.longitude)?,
+        })
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use actix_web::http::header;
 use actix_web::{HttpResponse, ResponseError};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -46,6 +47,11 @@ pub enum AppError {
     #[schema(example = "An unexpected error occurred")]
     Internal(String),
 
+    /// Too many requests, retry after the given number of seconds
+    #[error("Too many requests, retry in {0} seconds")]
+    #[schema(example = 30)]
+    RateLimited(u64),
+
     /// Bad request format
     #[error("Bad request: {0}")]
     #[schema(example = "Invalid JSON format")]
@@ -107,6 +113,7 @@ impl ResponseError for AppError {
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::Conflict(_) => StatusCode::CONFLICT,
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::Bcrypt(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Json(_) => StatusCode::BAD_REQUEST,
@@ -115,7 +122,12 @@ impl ResponseError for AppError {
     }
 
     fn error_response(&self) -> HttpResponse {
-        HttpResponse::build(self.status_code()).json(serde_json::json!({
+        let mut response = HttpResponse::build(self.status_code());
+        if let AppError::RateLimited(retry_after_secs) = self {
+            response.insert_header((header::RETRY_AFTER, retry_after_secs.to_string()));
+        }
+
+        response.json(serde_json::json!({
             "error": self.to_string(),
             "status": self.status_code().as_u16()
         }))
@@ -0,0 +1,61 @@
+use crate::domain::api_token::{ApiTokenId, CreateApiTokenRequest};
+use crate::middleware::client::client_info;
+use crate::services::api_token_service::ApiTokenService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Managing tokens takes a login session: a token cannot mint or revoke tokens
+pub struct ApiTokenHandler {
+    api_token_service: Arc<ApiTokenService>,
+}
+
+impl ApiTokenHandler {
+    pub fn new(api_token_service: Arc<ApiTokenService>) -> Self {
+        Self { api_token_service }
+    }
+
+    /// Create a new API token
+    pub async fn create(
+        &self,
+        req: web::Json<CreateApiTokenRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let token = self.api_token_service
+            .create_token(req.into_inner(), user.id(), &client_info(&http_req))
+            .await?;
+
+        Ok(HttpResponse::Created().json(token))
+    }
+
+    /// Get API tokens of the current user
+    pub async fn get_tokens(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let tokens = self.api_token_service.get_tokens(user.id()).await?;
+        Ok(HttpResponse::Ok().json(tokens))
+    }
+
+    /// Revoke an API token
+    pub async fn revoke(
+        &self,
+        path: web::Path<i32>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let token_id = ApiTokenId(path.into_inner());
+
+        self.api_token_service
+            .revoke_token(token_id, user.id(), &client_info(&http_req))
+            .await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "API token revoked successfully"})))
+    }
+}
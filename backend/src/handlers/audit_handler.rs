@@ -0,0 +1,31 @@
+use crate::domain::audit::SecurityEventQuery;
+use crate::services::audit_service::AuditService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+/// The security history takes a login session, like the other security settings
+pub struct AuditHandler {
+    audit_service: Arc<AuditService>,
+}
+
+impl AuditHandler {
+    pub fn new(audit_service: Arc<AuditService>) -> Self {
+        Self { audit_service }
+    }
+
+    /// Get the security events of the current user, newest first
+    pub async fn list_events(
+        &self,
+        query: web::Query<SecurityEventQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let events = self.audit_service
+            .list_events(user.id(), query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(events))
+    }
+}
@@ -1,11 +1,16 @@
-use crate::domain::user::{RegisterUserCommand, LoginCommand, UserInfoResponse, UserId};
-use crate::domain::session::SessionData;
-use crate::services::auth_service::AuthService;
+use crate::domain::user::{RegisterUserCommand, LoginCommand, UserInfoResponse};
+use crate::domain::session::{SessionData, RevokeSessionsResponse};
+use crate::domain::user_token::{VerifyEmailRequest, PasswordResetRequest, PasswordResetConfirmRequest};
+use crate::domain::two_factor::{LoginTwoFactorRequest, LoginChallengeResponse};
+use crate::middleware::auth::{AuthenticatedUser, OptionalUser};
+use crate::middleware::client::client_info;
+use crate::services::auth_service::{AuthService, LoginOutcome};
 use crate::error::{AppError, Result};
-use actix_web::{web, HttpResponse, HttpRequest};
+use actix_web::{web, HttpRequest, HttpResponse};
 use actix_session::Session;
 use serde_json::json;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub struct AuthHandler {
     auth_service: Arc<AuthService>,
@@ -25,35 +30,51 @@ impl AuthHandler {
         Ok(HttpResponse::Created().json(response))
     }
 
-    /// Login user
+    /// Login user, or ask for a two-factor code
     pub async fn login(
         &self,
         req: web::Json<LoginCommand>,
+        http_req: HttpRequest,
         session: Session,
     ) -> Result<HttpResponse> {
-        let (session_data, _user) = self.auth_service.login(req.into_inner()).await?;
-        
-        // Store session data
-        session.insert("user_data", &session_data)
-            .map_err(|_| AppError::Internal("Failed to create session".into()))?;
+        let outcome = self.auth_service
+            .login(req.into_inner(), client_info(&http_req))
+            .await?;
 
-        Ok(HttpResponse::Ok().json(json!({
-            "message": "Login successful",
-            "user": {
-                "id": session_data.user_id,
-                "username": session_data.username
+        match outcome {
+            LoginOutcome::LoggedIn(session_data, _user) => start_session(&session, session_data),
+            LoginOutcome::TwoFactorRequired { challenge } => {
+                Ok(HttpResponse::Ok().json(LoginChallengeResponse {
+                    message: "Two-factor code required".into(),
+                    two_factor_required: true,
+                    challenge,
+                }))
             }
-        })))
+        }
+    }
+
+    /// Finish a login with a two-factor code
+    pub async fn login_two_factor(
+        &self,
+        req: web::Json<LoginTwoFactorRequest>,
+        http_req: HttpRequest,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        let (session_data, _user) = self.auth_service
+            .login_two_factor(req.into_inner(), client_info(&http_req))
+            .await?;
+
+        start_session(&session, session_data)
     }
 
     /// Logout user
     pub async fn logout(
         &self,
+        http_req: HttpRequest,
         session: Session,
     ) -> Result<HttpResponse> {
         if let Ok(Some(session_data)) = session.get::<SessionData>("user_data") {
-            let user_id = UserId(session_data.user_id);
-            self.auth_service.logout(user_id).await?;
+            self.auth_service.logout(&session_data, &client_info(&http_req)).await?;
         }
 
         session.purge();
@@ -63,32 +84,22 @@ impl AuthHandler {
     /// Get current user info
     pub async fn get_user_info(
         &self,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let session_data = session.get::<SessionData>("user_data")
-            .map_err(|_| AppError::Authentication("Invalid session".into()))?
-            .ok_or_else(|| AppError::Authentication("Not authenticated".into()))?;
-
-        let user_id = UserId(session_data.user_id);
-        let user_info = self.auth_service.get_user_info(user_id).await?;
-
-        Ok(HttpResponse::Ok().json(user_info))
+        Ok(HttpResponse::Ok().json(UserInfoResponse::from(&user.user)))
     }
 
     /// Check authentication status
     pub async fn check_auth(
         &self,
-        session: Session,
+        user: OptionalUser,
     ) -> Result<HttpResponse> {
-        let session_data = session.get::<SessionData>("user_data")
-            .map_err(|_| AppError::Authentication("Invalid session".into()))?;
-
-        match session_data {
-            Some(data) => Ok(HttpResponse::Ok().json(json!({
+        match user.0 {
+            Some(user) => Ok(HttpResponse::Ok().json(json!({
                 "authenticated": true,
                 "user": {
-                    "id": data.user_id,
-                    "username": data.username
+                    "id": user.user.id.0,
+                    "username": user.user.username
                 }
             }))),
             None => Ok(HttpResponse::Ok().json(json!({
@@ -96,13 +107,112 @@ impl AuthHandler {
             })))
         }
     }
+
+    /// Verify an email address with the token from the verification email
+    pub async fn verify_email(
+        &self,
+        req: web::Json<VerifyEmailRequest>,
+    ) -> Result<HttpResponse> {
+        self.auth_service.verify_email(req.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(json!({"message": "Email verified successfully"})))
+    }
+
+    /// Send the verification email again
+    pub async fn resend_verification(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        self.auth_service.resend_verification(&user.user).await?;
+        Ok(HttpResponse::Ok().json(json!({"message": "Verification email sent"})))
+    }
+
+    /// Ask for a password reset email
+    pub async fn request_password_reset(
+        &self,
+        req: web::Json<PasswordResetRequest>,
+    ) -> Result<HttpResponse> {
+        self.auth_service.request_password_reset(req.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(json!({
+            "message": "If an account uses this email, a reset link has been sent"
+        })))
+    }
+
+    /// Choose a new password with the token from the reset email
+    pub async fn confirm_password_reset(
+        &self,
+        req: web::Json<PasswordResetConfirmRequest>,
+        http_req: HttpRequest,
+    ) -> Result<HttpResponse> {
+        self.auth_service
+            .confirm_password_reset(req.into_inner(), client_info(&http_req))
+            .await?;
+        Ok(HttpResponse::Ok().json(json!({"message": "Password reset successfully"})))
+    }
+
+    /// List the current user's active sessions
+    pub async fn list_sessions(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let sessions = self.auth_service.list_sessions(user.session()?).await?;
+        Ok(HttpResponse::Ok().json(sessions))
+    }
+
+    /// Log out one session, possibly the current one
+    pub async fn revoke_session(
+        &self,
+        path: web::Path<Uuid>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        let current = user.session()?;
+        let session_id = path.into_inner();
+
+        self.auth_service
+            .revoke_session(current, session_id, &client_info(&http_req))
+            .await?;
+
+        if session_id == current.session_id {
+            session.purge();
+        }
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Session revoked successfully"})))
+    }
+
+    /// Log out every session but the current one
+    pub async fn revoke_other_sessions(
+        &self,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        let (session_data, revoked) = self.auth_service
+            .revoke_other_sessions(user.session()?, client_info(&http_req))
+            .await?;
+
+        session.renew();
+        session.insert("user_data", &session_data)
+            .map_err(|_| AppError::Internal("Failed to update session".into()))?;
+
+        Ok(HttpResponse::Ok().json(RevokeSessionsResponse { revoked }))
+    }
 }
 
-// Helper function to extract user ID from session
-pub fn get_user_id_from_session(session: &Session) -> Result<UserId> {
-    let session_data = session.get::<SessionData>("user_data")
-        .map_err(|_| AppError::Authentication("Invalid session".into()))?
-        .ok_or_else(|| AppError::Authentication("Not authenticated".into()))?;
+/// Store a completed login in the session cookie
+fn start_session(session: &Session, session_data: SessionData) -> Result<HttpResponse> {
+    // Never keep a previous login's data alongside the new one
+    session.renew();
 
-    Ok(UserId(session_data.user_id))
-}
\ No newline at end of file
+    // Store session data
+    session.insert("user_data", &session_data)
+        .map_err(|_| AppError::Internal("Failed to create session".into()))?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Login successful",
+        "user": {
+            "id": session_data.user_id,
+            "username": session_data.username
+        }
+    })))
+}
@@ -1,5 +1,6 @@
-use crate::domain::autocomplete::{AutocompleteRequest, AutocompleteResponse};
+use crate::domain::autocomplete::AutocompleteRequest;
 use crate::error::Result;
+use crate::middleware::auth::OptionalUser;
 use crate::services::autocomplete_service::AutocompleteService;
 use actix_web::{web, HttpResponse};
 use std::sync::Arc;
@@ -18,10 +19,12 @@ impl AutocompleteHandler {
     pub async fn search_restaurants(
         &self,
         req: web::Json<AutocompleteRequest>,
+        user: OptionalUser,
     ) -> Result<HttpResponse> {
         info!("Processing restaurant autocomplete request: '{}'", req.search_term);
 
-        let response = self.service.search_restaurants(&req.search_term).await?;
+        let user_id = user.id();
+        let response = self.service.search_restaurants(&req.search_term, user_id).await?;
         
         Ok(HttpResponse::Ok().json(response))
     }
@@ -30,11 +33,18 @@ impl AutocompleteHandler {
     pub async fn search_wishlist(
         &self,
         req: web::Json<AutocompleteRequest>,
+        user: OptionalUser,
     ) -> Result<HttpResponse> {
         info!("Processing wishlist autocomplete request: '{}'", req.search_term);
 
-        let response = self.service.search_restaurants(&req.search_term).await?;
+        let user_id = user.id();
+        let response = self.service.search_restaurants(&req.search_term, user_id).await?;
         
         Ok(HttpResponse::Ok().json(response))
     }
+
+    /// Report the autocomplete cache counters
+    pub async fn cache_stats(&self) -> Result<HttpResponse> {
+        Ok(HttpResponse::Ok().json(self.service.cache_stats()))
+    }
 }
\ No newline at end of file
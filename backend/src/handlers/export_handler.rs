@@ -0,0 +1,44 @@
+use crate::domain::export::ExportQuery;
+use crate::services::export_service::ExportService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use chrono::Utc;
+use std::sync::Arc;
+
+pub struct ExportHandler {
+    export_service: Arc<ExportService>,
+}
+
+impl ExportHandler {
+    pub fn new(export_service: Arc<ExportService>) -> Self {
+        Self { export_service }
+    }
+
+    /// Download the current user's whole collection
+    pub async fn export(
+        &self,
+        query: web::Query<ExportQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let format = query.into_inner().format;
+
+        let body = self.export_service.export(user_id, format).await?;
+
+        let filename = format!(
+            "gourmestre-export-{}.{}",
+            Utc::now().format("%Y-%m-%d"),
+            format.extension()
+        );
+
+        Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(filename)],
+            })
+            .streaming(body))
+    }
+}
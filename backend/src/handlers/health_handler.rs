@@ -1,6 +1,5 @@
 use actix_web::{HttpResponse, Result};
 use serde_json::json;
-use utoipa::path;
 
 /// Health check endpoint
 #[utoipa::path(
@@ -0,0 +1,80 @@
+use crate::domain::import::{ImportQuery, MAX_IMPORT_BYTES};
+use crate::services::import_service::ImportService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::{AppError, Result};
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse};
+use futures_util::TryStreamExt;
+use std::sync::Arc;
+
+pub struct ImportHandler {
+    import_service: Arc<ImportService>,
+}
+
+/// File read from a multipart upload
+struct Upload {
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: Vec<u8>,
+}
+
+/// Read the `file` field of a multipart body, enforcing the size limit
+async fn read_upload(mut payload: Multipart) -> Result<Upload> {
+    let invalid = |e: actix_multipart::MultipartError| AppError::BadRequest(format!("Invalid upload: {}", e));
+
+    while let Some(mut field) = payload.try_next().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(str::to_string);
+        let content_type = field.content_type().map(|mime| mime.to_string());
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.try_next().await.map_err(invalid)? {
+            if data.len() + chunk.len() > MAX_IMPORT_BYTES {
+                return Err(AppError::BadRequest(format!(
+                    "Import files are limited to {} MB",
+                    MAX_IMPORT_BYTES / (1024 * 1024)
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        return Ok(Upload { filename, content_type, data });
+    }
+
+    Err(AppError::BadRequest("Missing `file` field".into()))
+}
+
+impl ImportHandler {
+    pub fn new(import_service: Arc<ImportService>) -> Self {
+        Self { import_service }
+    }
+
+    /// Import restaurants and wishlist items from an uploaded file
+    pub async fn import(
+        &self,
+        query: web::Query<ImportQuery>,
+        payload: Multipart,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let upload = read_upload(payload).await?;
+
+        let report = self.import_service
+            .import(
+                user_id,
+                &upload.data,
+                upload.filename.as_deref(),
+                upload.content_type.as_deref(),
+                query.into_inner(),
+            )
+            .await?;
+
+        Ok(HttpResponse::Ok().json(report))
+    }
+}
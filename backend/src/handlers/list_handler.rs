@@ -0,0 +1,196 @@
+use crate::domain::list::{
+    ListId, ListEntryId, CreateListRequest, UpdateListRequest, AddListEntryRequest,
+    UpdateListEntryRequest, ReorderListRequest,
+};
+use crate::services::list_service::ListService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct ListHandler {
+    list_service: Arc<ListService>,
+}
+
+impl ListHandler {
+    pub fn new(list_service: Arc<ListService>) -> Self {
+        Self { list_service }
+    }
+
+    /// Create a new list
+    pub async fn create(
+        &self,
+        req: web::Json<CreateListRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list = self.list_service
+            .create_list(req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Created().json(list))
+    }
+
+    /// Get lists for current user
+    pub async fn get_lists(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let lists = self.list_service.get_lists(user_id).await?;
+        Ok(HttpResponse::Ok().json(lists))
+    }
+
+    /// Get a list with its entries
+    pub async fn get_list(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let list = self.list_service.get_list(list_id, user_id).await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+
+    /// Update a list
+    pub async fn update(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<UpdateListRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let list = self.list_service
+            .update_list(list_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+
+    /// Delete a list
+    pub async fn delete(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        self.list_service.delete_list(list_id, user_id).await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "List deleted successfully"})))
+    }
+
+    /// Publish a list under a new share token
+    pub async fn share(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let list = self.list_service.share_list(list_id, user_id).await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+
+    /// Stop publishing a list
+    pub async fn unshare(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let list = self.list_service.unshare_list(list_id, user_id).await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+
+    /// Add an entry to a list
+    pub async fn add_entry(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<AddListEntryRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let entry = self.list_service
+            .add_entry(list_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Created().json(entry))
+    }
+
+    /// Update a list entry
+    pub async fn update_entry(
+        &self,
+        path: web::Path<(i32, i32)>,
+        req: web::Json<UpdateListEntryRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let (list_id, entry_id) = path.into_inner();
+
+        let entry = self.list_service
+            .update_entry(ListEntryId(entry_id), ListId(list_id), req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(entry))
+    }
+
+    /// Remove an entry from a list
+    pub async fn remove_entry(
+        &self,
+        path: web::Path<(i32, i32)>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let (list_id, entry_id) = path.into_inner();
+
+        self.list_service
+            .remove_entry(ListEntryId(entry_id), ListId(list_id), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Entry removed from list"})))
+    }
+
+    /// Reorder the entries of a list
+    pub async fn reorder(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<ReorderListRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let list_id = ListId(path.into_inner());
+
+        let list = self.list_service
+            .reorder_entries(list_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+
+    /// Get a published list (no authentication)
+    pub async fn get_public_list(
+        &self,
+        path: web::Path<String>,
+    ) -> Result<HttpResponse> {
+        let list = self.list_service
+            .get_public_list(&path.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(list))
+    }
+}
@@ -2,4 +2,14 @@ pub mod auth_handler;
 pub mod restaurant_handler;
 pub mod wishlist_handler;
 pub mod health_handler;
-pub mod autocomplete_handler;
\ No newline at end of file
+pub mod autocomplete_handler;
+pub mod visit_handler;
+pub mod tag_handler;
+pub mod export_handler;
+pub mod import_handler;
+pub mod list_handler;
+pub mod social_handler;
+pub mod api_token_handler;
+pub mod user_handler;
+pub mod two_factor_handler;
+pub mod audit_handler;
\ No newline at end of file
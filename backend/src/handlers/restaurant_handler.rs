@@ -1,11 +1,12 @@
 use crate::domain::restaurant::{
-    RestaurantId, CreateRestaurantRequest, UpdateRestaurantRequest, RestaurantQuery
+    RestaurantId, CreateRestaurantRequest, UpdateRestaurantRequest, RestaurantQuery,
+    RestaurantSearchRequest
 };
 use crate::services::restaurant_service::RestaurantService;
-use crate::handlers::auth_handler::get_user_id_from_session;
-use crate::error::{AppError, Result};
-use actix_web::{web, HttpResponse, HttpRequest};
-use actix_session::Session;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::domain::geo::{NearbyQuery, WithinQuery};
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -22,9 +23,9 @@ impl RestaurantHandler {
     pub async fn create(
         &self,
         req: web::Json<CreateRestaurantRequest>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurant = self.restaurant_service
             .create_restaurant(req.into_inner(), user_id)
             .await?;
@@ -36,9 +37,9 @@ impl RestaurantHandler {
     pub async fn get_restaurants(
         &self,
         query: web::Query<RestaurantQuery>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurants = self.restaurant_service
             .get_restaurants(user_id, query.into_inner())
             .await?;
@@ -50,9 +51,9 @@ impl RestaurantHandler {
     pub async fn get_restaurant(
         &self,
         path: web::Path<i32>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurant_id = RestaurantId(path.into_inner());
         
         let restaurant = self.restaurant_service
@@ -67,9 +68,9 @@ impl RestaurantHandler {
         &self,
         path: web::Path<i32>,
         req: web::Json<UpdateRestaurantRequest>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurant_id = RestaurantId(path.into_inner());
         
         let restaurant = self.restaurant_service
@@ -83,9 +84,9 @@ impl RestaurantHandler {
     pub async fn delete(
         &self,
         path: web::Path<i32>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurant_id = RestaurantId(path.into_inner());
         
         self.restaurant_service
@@ -98,20 +99,51 @@ impl RestaurantHandler {
     /// Get restaurant statistics
     pub async fn get_stats(
         &self,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let stats = self.restaurant_service.get_stats(user_id).await?;
         Ok(HttpResponse::Ok().json(stats))
     }
 
+    /// Get restaurants near a point
+    pub async fn get_nearby(
+        &self,
+        query: web::Query<NearbyQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let results = self.restaurant_service
+            .get_nearby(user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(results))
+    }
+
+    /// Get restaurants inside a bounding box
+    pub async fn get_within(
+        &self,
+        query: web::Query<WithinQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let results = self.restaurant_service
+            .get_within(user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(results))
+    }
+
     /// Get favorite restaurants
     pub async fn get_favorites(
         &self,
-        session: Session,
+        query: web::Query<RestaurantQuery>,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
-        let restaurants = self.restaurant_service.get_favorites(user_id).await?;
+        let user_id = user.id();
+        let restaurants = self.restaurant_service
+            .get_favorites(user_id, query.into_inner())
+            .await?;
         Ok(HttpResponse::Ok().json(restaurants))
     }
 
@@ -119,28 +151,26 @@ impl RestaurantHandler {
     pub async fn get_recent(
         &self,
         query: web::Query<RestaurantQuery>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let restaurants = self.restaurant_service
             .get_recent(user_id, query.limit)
             .await?;
         Ok(HttpResponse::Ok().json(restaurants))
     }
 
-    /// Search restaurants (placeholder for future implementation)
+    /// Search restaurants
     pub async fn search(
         &self,
-        query: web::Query<serde_json::Value>,
-        session: Session,
+        req: web::Json<RestaurantSearchRequest>,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let _user_id = get_user_id_from_session(&session)?;
-        
-        // For now, return empty search results
-        // This can be expanded later with actual search functionality
-        Ok(HttpResponse::Ok().json(json!({
-            "results": [],
-            "message": "Search functionality to be implemented"
-        })))
+        let user_id = user.id();
+        let results = self.restaurant_service
+            .search(req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(results))
     }
 }
\ No newline at end of file
@@ -0,0 +1,139 @@
+use crate::domain::social::{PrivacySettingsRequest, SocialPageQuery};
+use crate::services::social_service::SocialService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct SocialHandler {
+    social_service: Arc<SocialService>,
+}
+
+impl SocialHandler {
+    pub fn new(social_service: Arc<SocialService>) -> Self {
+        Self { social_service }
+    }
+
+    /// Get a user profile
+    pub async fn get_profile(
+        &self,
+        path: web::Path<String>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let profile = self.social_service
+            .get_profile(&path.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(profile))
+    }
+
+    /// Follow a user
+    pub async fn follow(
+        &self,
+        path: web::Path<String>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let profile = self.social_service
+            .follow(&path.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(profile))
+    }
+
+    /// Stop following a user
+    pub async fn unfollow(
+        &self,
+        path: web::Path<String>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        self.social_service
+            .unfollow(&path.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "User unfollowed successfully"})))
+    }
+
+    /// List the followers of a user
+    pub async fn get_followers(
+        &self,
+        path: web::Path<String>,
+        query: web::Query<SocialPageQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let followers = self.social_service
+            .get_followers(&path.into_inner(), user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(followers))
+    }
+
+    /// List the users a user follows
+    pub async fn get_following(
+        &self,
+        path: web::Path<String>,
+        query: web::Query<SocialPageQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let following = self.social_service
+            .get_following(&path.into_inner(), user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(following))
+    }
+
+    /// List the recent activity of a user
+    pub async fn get_user_activity(
+        &self,
+        path: web::Path<String>,
+        query: web::Query<SocialPageQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let activity = self.social_service
+            .get_user_activity(&path.into_inner(), user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(activity))
+    }
+
+    /// Get the activity feed of the current user
+    pub async fn get_feed(
+        &self,
+        query: web::Query<SocialPageQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let feed = self.social_service
+            .get_feed(user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(feed))
+    }
+
+    /// Get the privacy setting of the current user
+    pub async fn get_privacy(&self, user: AuthenticatedUser) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let settings = self.social_service.get_privacy(user_id).await?;
+        Ok(HttpResponse::Ok().json(settings))
+    }
+
+    /// Change the privacy setting of the current user
+    pub async fn set_privacy(
+        &self,
+        req: web::Json<PrivacySettingsRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let settings = self.social_service
+            .set_privacy(req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(settings))
+    }
+}
@@ -0,0 +1,140 @@
+use crate::domain::tag::{TagId, CreateTagRequest, UpdateTagRequest, SetTagsRequest};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::wishlist::WishlistId;
+use crate::services::tag_service::TagService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct TagHandler {
+    tag_service: Arc<TagService>,
+}
+
+impl TagHandler {
+    pub fn new(tag_service: Arc<TagService>) -> Self {
+        Self { tag_service }
+    }
+
+    /// Create a new tag
+    pub async fn create(
+        &self,
+        req: web::Json<CreateTagRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let tag = self.tag_service
+            .create_tag(req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Created().json(tag))
+    }
+
+    /// Get tags for current user
+    pub async fn get_tags(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let tags = self.tag_service.get_tags(user_id).await?;
+        Ok(HttpResponse::Ok().json(tags))
+    }
+
+    /// Update a tag
+    pub async fn update(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<UpdateTagRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let tag_id = TagId(path.into_inner());
+
+        let tag = self.tag_service
+            .update_tag(tag_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(tag))
+    }
+
+    /// Delete a tag
+    pub async fn delete(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let tag_id = TagId(path.into_inner());
+
+        self.tag_service.delete_tag(tag_id, user_id).await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Tag deleted successfully"})))
+    }
+
+    /// Get the tags of a restaurant
+    pub async fn get_restaurant_tags(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let restaurant_id = RestaurantId(path.into_inner());
+
+        let tags = self.tag_service
+            .get_restaurant_tags(restaurant_id, user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(tags))
+    }
+
+    /// Replace the tags of a restaurant
+    pub async fn set_restaurant_tags(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<SetTagsRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let restaurant_id = RestaurantId(path.into_inner());
+
+        let tags = self.tag_service
+            .set_restaurant_tags(restaurant_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(tags))
+    }
+
+    /// Get the tags of a wishlist item
+    pub async fn get_wishlist_item_tags(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let item_id = WishlistId(path.into_inner());
+
+        let tags = self.tag_service
+            .get_wishlist_item_tags(item_id, user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(tags))
+    }
+
+    /// Replace the tags of a wishlist item
+    pub async fn set_wishlist_item_tags(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<SetTagsRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let item_id = WishlistId(path.into_inner());
+
+        let tags = self.tag_service
+            .set_wishlist_item_tags(item_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(tags))
+    }
+}
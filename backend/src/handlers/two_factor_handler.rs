@@ -0,0 +1,87 @@
+use crate::domain::two_factor::TwoFactorCodeRequest;
+use crate::middleware::client::client_info;
+use crate::services::auth_service::AuthService;
+use crate::services::two_factor_service::TwoFactorService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Managing two-factor authentication takes a login session, like API tokens
+pub struct TwoFactorHandler {
+    two_factor_service: Arc<TwoFactorService>,
+    auth_service: Arc<AuthService>,
+}
+
+impl TwoFactorHandler {
+    pub fn new(two_factor_service: Arc<TwoFactorService>, auth_service: Arc<AuthService>) -> Self {
+        Self { two_factor_service, auth_service }
+    }
+
+    /// Get the two-factor status of the current user
+    pub async fn status(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let status = self.two_factor_service.status(user.id()).await?;
+        Ok(HttpResponse::Ok().json(status))
+    }
+
+    /// Start enrolling an authenticator app
+    pub async fn setup(
+        &self,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let setup = self.two_factor_service.setup(&user.user).await?;
+        Ok(HttpResponse::Ok().json(setup))
+    }
+
+    /// Confirm the enrollment with a first code
+    pub async fn enable(
+        &self,
+        req: web::Json<TwoFactorCodeRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let codes = self.two_factor_service
+            .enable(&user.user, req.into_inner(), &client_info(&http_req))
+            .await?;
+
+        Ok(HttpResponse::Ok().json(codes))
+    }
+
+    /// Turn two-factor authentication off
+    pub async fn disable(
+        &self,
+        req: web::Json<TwoFactorCodeRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let client = client_info(&http_req);
+        self.auth_service.check_two_factor_code(&user.user, &req.code, &client).await?;
+        self.two_factor_service.disable(&user.user, &client).await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Two-factor authentication disabled"})))
+    }
+
+    /// Replace the recovery codes
+    pub async fn regenerate_recovery_codes(
+        &self,
+        req: web::Json<TwoFactorCodeRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        self.auth_service
+            .check_two_factor_code(&user.user, &req.code, &client_info(&http_req))
+            .await?;
+        let codes = self.two_factor_service.regenerate_recovery_codes(&user.user).await?;
+
+        Ok(HttpResponse::Ok().json(codes))
+    }
+}
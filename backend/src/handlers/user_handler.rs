@@ -0,0 +1,94 @@
+use crate::domain::user::{
+    ChangePasswordRequest, ChangeUsernameRequest, ChangeEmailRequest, DeleteAccountRequest,
+};
+use crate::domain::session::SessionData;
+use crate::middleware::client::client_info;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::services::user_service::UserService;
+use crate::error::{AppError, Result};
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_session::Session;
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct UserHandler {
+    user_service: Arc<UserService>,
+}
+
+impl UserHandler {
+    pub fn new(user_service: Arc<UserService>) -> Self {
+        Self { user_service }
+    }
+
+    /// Change the password, logging out every other session
+    pub async fn change_password(
+        &self,
+        req: web::Json<ChangePasswordRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        let session_data = self.user_service
+            .change_password(&user.user, user.session()?, req.into_inner(), client_info(&http_req))
+            .await?;
+
+        session.renew();
+        session.insert("user_data", &session_data)
+            .map_err(|_| AppError::Internal("Failed to update session".into()))?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Password changed successfully"})))
+    }
+
+    /// Change the username
+    pub async fn change_username(
+        &self,
+        req: web::Json<ChangeUsernameRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        let current = user.session()?;
+        let response = self.user_service
+            .change_username(&user.user, req.into_inner(), &client_info(&http_req))
+            .await?;
+
+        // Keep the username stored in the cookie in step
+        let session_data = SessionData { username: response.username.clone(), ..current.clone() };
+        session.insert("user_data", &session_data)
+            .map_err(|_| AppError::Internal("Failed to update session".into()))?;
+
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Change the email address
+    pub async fn change_email(
+        &self,
+        req: web::Json<ChangeEmailRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        let response = self.user_service
+            .change_email(&user.user, req.into_inner(), &client_info(&http_req))
+            .await?;
+
+        Ok(HttpResponse::Ok().json(response))
+    }
+
+    /// Delete the account and log out
+    pub async fn delete_account(
+        &self,
+        req: web::Json<DeleteAccountRequest>,
+        http_req: HttpRequest,
+        user: AuthenticatedUser,
+        session: Session,
+    ) -> Result<HttpResponse> {
+        user.session()?;
+        self.user_service
+            .delete_account(&user.user, req.into_inner(), &client_info(&http_req))
+            .await?;
+
+        session.purge();
+        Ok(HttpResponse::Ok().json(json!({"message": "Account deleted successfully"})))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::domain::visit::{VisitId, CreateVisitRequest, UpdateVisitRequest};
+use crate::domain::restaurant::RestaurantId;
+use crate::services::visit_service::VisitService;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::error::Result;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct VisitHandler {
+    visit_service: Arc<VisitService>,
+}
+
+impl VisitHandler {
+    pub fn new(visit_service: Arc<VisitService>) -> Self {
+        Self { visit_service }
+    }
+
+    /// Log a new visit
+    pub async fn create(
+        &self,
+        path: web::Path<i32>,
+        req: web::Json<CreateVisitRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let restaurant_id = RestaurantId(path.into_inner());
+
+        let visit = self.visit_service
+            .create_visit(restaurant_id, req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Created().json(visit))
+    }
+
+    /// Get visits for a restaurant
+    pub async fn get_visits(
+        &self,
+        path: web::Path<i32>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let restaurant_id = RestaurantId(path.into_inner());
+
+        let visits = self.visit_service
+            .get_visits(restaurant_id, user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(visits))
+    }
+
+    /// Get a single visit
+    pub async fn get_visit(
+        &self,
+        path: web::Path<(i32, i32)>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let (restaurant_id, visit_id) = path.into_inner();
+
+        let visit = self.visit_service
+            .get_visit(RestaurantId(restaurant_id), VisitId(visit_id), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(visit))
+    }
+
+    /// Update a visit
+    pub async fn update(
+        &self,
+        path: web::Path<(i32, i32)>,
+        req: web::Json<UpdateVisitRequest>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let (restaurant_id, visit_id) = path.into_inner();
+
+        let visit = self.visit_service
+            .update_visit(RestaurantId(restaurant_id), VisitId(visit_id), req.into_inner(), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(visit))
+    }
+
+    /// Delete a visit
+    pub async fn delete(
+        &self,
+        path: web::Path<(i32, i32)>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let (restaurant_id, visit_id) = path.into_inner();
+
+        self.visit_service
+            .delete_visit(RestaurantId(restaurant_id), VisitId(visit_id), user_id)
+            .await?;
+
+        Ok(HttpResponse::Ok().json(json!({"message": "Visit deleted successfully"})))
+    }
+}
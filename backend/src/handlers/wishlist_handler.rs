@@ -2,10 +2,10 @@ use crate::domain::wishlist::{
     WishlistId, CreateWishlistRequest, UpdateWishlistRequest, WishlistQuery, WishlistPriority
 };
 use crate::services::wishlist_service::WishlistService;
-use crate::handlers::auth_handler::get_user_id_from_session;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::domain::geo::{NearbyQuery, WithinQuery};
 use crate::error::{AppError, Result};
-use actix_web::{web, HttpResponse, HttpRequest};
-use actix_session::Session;
+use actix_web::{web, HttpResponse};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -22,9 +22,9 @@ impl WishlistHandler {
     pub async fn create(
         &self,
         req: web::Json<CreateWishlistRequest>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let item = self.wishlist_service
             .create_item(req.into_inner(), user_id)
             .await?;
@@ -36,9 +36,9 @@ impl WishlistHandler {
     pub async fn get_items(
         &self,
         query: web::Query<WishlistQuery>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let items = self.wishlist_service
             .get_items(user_id, query.into_inner())
             .await?;
@@ -50,9 +50,9 @@ impl WishlistHandler {
     pub async fn get_item(
         &self,
         path: web::Path<i32>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let item_id = WishlistId(path.into_inner());
         
         let item = self.wishlist_service
@@ -67,9 +67,9 @@ impl WishlistHandler {
         &self,
         path: web::Path<i32>,
         req: web::Json<UpdateWishlistRequest>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let item_id = WishlistId(path.into_inner());
         
         let item = self.wishlist_service
@@ -83,9 +83,9 @@ impl WishlistHandler {
     pub async fn delete(
         &self,
         path: web::Path<i32>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let item_id = WishlistId(path.into_inner());
         
         self.wishlist_service
@@ -98,20 +98,48 @@ impl WishlistHandler {
     /// Get wishlist count
     pub async fn get_count(
         &self,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let count = self.wishlist_service.get_count(user_id).await?;
         Ok(HttpResponse::Ok().json(count))
     }
 
+    /// Get wishlist items near a point
+    pub async fn get_nearby(
+        &self,
+        query: web::Query<NearbyQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let results = self.wishlist_service
+            .get_nearby(user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(results))
+    }
+
+    /// Get wishlist items inside a bounding box
+    pub async fn get_within(
+        &self,
+        query: web::Query<WithinQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<HttpResponse> {
+        let user_id = user.id();
+        let results = self.wishlist_service
+            .get_within(user_id, query.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(results))
+    }
+
     /// Promote wishlist item to restaurant
     pub async fn promote(
         &self,
         path: web::Path<i32>,
-        session: Session,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let item_id = WishlistId(path.into_inner());
         
         self.wishlist_service
@@ -127,9 +155,10 @@ impl WishlistHandler {
     pub async fn get_by_priority(
         &self,
         path: web::Path<String>,
-        session: Session,
+        query: web::Query<WishlistQuery>,
+        user: AuthenticatedUser,
     ) -> Result<HttpResponse> {
-        let user_id = get_user_id_from_session(&session)?;
+        let user_id = user.id();
         let priority_str = path.into_inner();
         
         let priority = match priority_str.as_str() {
@@ -140,7 +169,7 @@ impl WishlistHandler {
         };
         
         let items = self.wishlist_service
-            .get_by_priority(user_id, priority)
+            .get_by_priority(user_id, priority, query.into_inner())
             .await?;
 
         Ok(HttpResponse::Ok().json(items))
@@ -1,3 +1,4 @@
+pub mod api_docs;
 pub mod config;
 pub mod database;
 pub mod domain;
@@ -5,4 +6,6 @@ pub mod error;
 pub mod repositories;
 pub mod services;
 pub mod handlers;
-pub mod routes;
\ No newline at end of file
+pub mod middleware;
+pub mod routes;
+pub mod models;
\ No newline at end of file
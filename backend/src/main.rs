@@ -1,4 +1,4 @@
-use actix_web::{web, App, HttpServer, middleware::Logger};
+use actix_web::{web, App, HttpServer, middleware::{from_fn, Logger}};
 use actix_cors::Cors;
 use actix_session::{SessionMiddleware, storage::CookieSessionStore};
 use actix_web::cookie::Key;
@@ -6,38 +6,66 @@ use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use api_docs::ApiDoc;
-
-mod api_docs;
-mod config;
-mod database;
-mod domain;
-mod error;
-mod repositories;
-mod services;
-mod handlers;
-mod routes;
-mod models;
-
-use config::Config;
-use repositories::{
+use gourmestre::api_docs::ApiDoc;
+use gourmestre::{database, routes};
+use gourmestre::config::Config;
+use gourmestre::middleware::session::validate_session;
+use gourmestre::middleware::rate_limit::rate_limit;
+use gourmestre::middleware::audit::audit_deletes;
+use gourmestre::middleware::client::ProxyPolicy;
+use gourmestre::repositories::{
     user_repository::PostgresUserRepository,
     restaurant_repository::PostgresRestaurantRepository,
     wishlist_repository::PostgresWishlistRepository,
     session_repository::PostgresSessionRepository,
+    visit_repository::PostgresVisitRepository,
+    tag_repository::PostgresTagRepository,
+    import_repository::PostgresImportRepository,
+    list_repository::PostgresListRepository,
+    activity_repository::PostgresActivityRepository,
+    place_repository::PostgresPlaceRepository,
+    api_token_repository::PostgresApiTokenRepository,
+    user_token_repository::PostgresUserTokenRepository,
+    two_factor_repository::PostgresTwoFactorRepository,
+    audit_repository::PostgresAuditRepository,
 };
-use services::{
+use gourmestre::services::{
     auth_service::AuthService,
     restaurant_service::RestaurantService,
     wishlist_service::WishlistService,
     user_service::UserService,
     autocomplete_service::AutocompleteService,
+    autocomplete_provider,
+    autocomplete_cache::AutocompleteCache,
+    job_runner::JobRunner,
+    api_token_service::ApiTokenService,
+    two_factor_service::TwoFactorService,
+    audit_service::AuditService,
+    mailer,
+    password_hasher::PasswordHashing,
+    rate_limiter::{self, RateLimiter},
+    visit_service::VisitService,
+    tag_service::TagService,
+    export_service::ExportService,
+    import_service::ImportService,
+    list_service::ListService,
+    social_service::SocialService,
 };
-use handlers::{
+use gourmestre::handlers::{
     auth_handler::AuthHandler,
     restaurant_handler::RestaurantHandler,
     wishlist_handler::WishlistHandler,
     autocomplete_handler::AutocompleteHandler,
+    visit_handler::VisitHandler,
+    tag_handler::TagHandler,
+    export_handler::ExportHandler,
+    import_handler::ImportHandler,
+    list_handler::ListHandler,
+    social_handler::SocialHandler,
+    api_token_handler::ApiTokenHandler,
+    two_factor_handler::TwoFactorHandler,
+    audit_handler::AuditHandler,
+    user_handler::UserHandler,
 };
 
 #[actix_web::main]
@@ -58,23 +86,97 @@ async fn main() -> std::io::Result<()> {
     let restaurant_repo = Arc::new(PostgresRestaurantRepository::new(pool.clone()));
     let wishlist_repo = Arc::new(PostgresWishlistRepository::new(pool.clone()));
     let session_repo = Arc::new(PostgresSessionRepository::new(pool.clone()));
+    let visit_repo = Arc::new(PostgresVisitRepository::new(pool.clone()));
+    let tag_repo = Arc::new(PostgresTagRepository::new(pool.clone()));
+    let import_repo = Arc::new(PostgresImportRepository::new(pool.clone()));
+    let list_repo = Arc::new(PostgresListRepository::new(pool.clone()));
+    let activity_repo = Arc::new(PostgresActivityRepository::new(pool.clone()));
+    let place_repo = Arc::new(PostgresPlaceRepository::new(pool.clone()));
+    let api_token_repo = Arc::new(PostgresApiTokenRepository::new(pool.clone()));
+    let user_token_repo = Arc::new(PostgresUserTokenRepository::new(pool.clone()));
+    let two_factor_repo = Arc::new(PostgresTwoFactorRepository::new(pool.clone()));
+    let audit_repo = Arc::new(PostgresAuditRepository::new(pool.clone()));
+
+    let proxy_policy = ProxyPolicy::from_config(&config);
+    let rate_limit_store = rate_limiter::store_from_config(&config);
+    let rate_limiter = Arc::new(RateLimiter::from_config(&config, rate_limit_store.clone()));
+
+    // Start maintenance jobs
+    JobRunner::from_config(
+        &config,
+        user_repo.clone(),
+        session_repo.clone(),
+        user_token_repo.clone(),
+        rate_limit_store,
+        audit_repo.clone(),
+    )
+    .start();
 
     // Create services
+    let audit_service = Arc::new(AuditService::new(audit_repo));
+    let two_factor_service = Arc::new(TwoFactorService::new(two_factor_repo, audit_service.clone()));
     let auth_service = Arc::new(AuthService::new(
         user_repo.clone(),
         session_repo.clone(),
+        user_token_repo,
+        mailer::from_config(&config).expect("Failed to configure mailer"),
+        two_factor_service.clone(),
+        audit_service.clone(),
+        PasswordHashing::from_config(&config).expect("Failed to configure password hashing"),
         config.clone(),
     ));
-    let restaurant_service = Arc::new(RestaurantService::new(restaurant_repo));
-    let wishlist_service = Arc::new(WishlistService::new(wishlist_repo));
-    let user_service = Arc::new(UserService::new(user_repo));
-    let autocomplete_service = Arc::new(AutocompleteService::new());
+    let api_token_service = Arc::new(ApiTokenService::new(
+        api_token_repo,
+        user_repo.clone(),
+        audit_service.clone(),
+    ));
+    let tag_service = Arc::new(TagService::new(
+        tag_repo,
+        restaurant_repo.clone(),
+        wishlist_repo.clone(),
+    ));
+    let social_service = Arc::new(SocialService::new(user_repo.clone(), activity_repo));
+    let restaurant_service = Arc::new(RestaurantService::new(
+        restaurant_repo.clone(),
+        tag_service.clone(),
+        social_service.clone(),
+    ));
+    let export_service = Arc::new(ExportService::new(
+        user_repo.clone(),
+        restaurant_repo.clone(),
+        wishlist_repo.clone(),
+    ));
+    let import_service = Arc::new(ImportService::new(import_repo));
+    let list_service = Arc::new(ListService::new(list_repo, user_repo.clone()));
+    let visit_service = Arc::new(VisitService::new(visit_repo, restaurant_repo.clone()));
+    let wishlist_service = Arc::new(WishlistService::new(
+        wishlist_repo.clone(),
+        tag_service.clone(),
+        social_service.clone(),
+    ));
+    let user_service = Arc::new(UserService::new(user_repo, auth_service.clone(), audit_service.clone()));
+    let autocomplete_service = Arc::new(AutocompleteService::new(
+        autocomplete_provider::from_config(&config, place_repo),
+        Arc::new(AutocompleteCache::from_config(&config)),
+        restaurant_repo,
+        wishlist_repo,
+    ));
 
     // Create handlers
-    let auth_handler = Arc::new(AuthHandler::new(auth_service));
+    let auth_handler = Arc::new(AuthHandler::new(auth_service.clone()));
+    let api_token_handler = Arc::new(ApiTokenHandler::new(api_token_service.clone()));
+    let user_handler = Arc::new(UserHandler::new(user_service));
+    let two_factor_handler = Arc::new(TwoFactorHandler::new(two_factor_service, auth_service.clone()));
+    let audit_handler = Arc::new(AuditHandler::new(audit_service.clone()));
     let restaurant_handler = Arc::new(RestaurantHandler::new(restaurant_service));
     let wishlist_handler = Arc::new(WishlistHandler::new(wishlist_service));
     let autocomplete_handler = Arc::new(AutocompleteHandler::new(autocomplete_service));
+    let visit_handler = Arc::new(VisitHandler::new(visit_service));
+    let tag_handler = Arc::new(TagHandler::new(tag_service));
+    let export_handler = Arc::new(ExportHandler::new(export_service));
+    let import_handler = Arc::new(ImportHandler::new(import_service));
+    let list_handler = Arc::new(ListHandler::new(list_service));
+    let social_handler = Arc::new(SocialHandler::new(social_service));
 
     // Create session key
     let session_key = Key::from(config.session_key.as_bytes());
@@ -93,11 +195,15 @@ async fn main() -> std::io::Result<()> {
             .allowed_origin("http://localhost:3000")
             .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
             .allowed_headers(vec!["Content-Type", "Authorization", "X-Requested-With"])
+            .expose_headers(vec!["Retry-After"])
             .supports_credentials()
             .max_age(3600);
 
         App::new()
-            // Add middleware
+            // Add middleware (the last one added runs first)
+            .wrap(from_fn(audit_deletes))
+            .wrap(from_fn(validate_session))
+            .wrap(from_fn(rate_limit))
             .wrap(Logger::default())
             .wrap(cors)
             .wrap(
@@ -113,10 +219,25 @@ async fn main() -> std::io::Result<()> {
             
             // Add application data
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(proxy_policy))
+            .app_data(web::Data::from(rate_limiter.clone()))
+            .app_data(web::Data::from(auth_service.clone()))
+            .app_data(web::Data::from(api_token_service.clone()))
+            .app_data(web::Data::from(audit_service.clone()))
             .app_data(web::Data::from(auth_handler.clone()))
+            .app_data(web::Data::from(api_token_handler.clone()))
+            .app_data(web::Data::from(user_handler.clone()))
+            .app_data(web::Data::from(two_factor_handler.clone()))
+            .app_data(web::Data::from(audit_handler.clone()))
             .app_data(web::Data::from(restaurant_handler.clone()))
             .app_data(web::Data::from(wishlist_handler.clone()))
             .app_data(web::Data::from(autocomplete_handler.clone()))
+            .app_data(web::Data::from(visit_handler.clone()))
+            .app_data(web::Data::from(tag_handler.clone()))
+            .app_data(web::Data::from(export_handler.clone()))
+            .app_data(web::Data::from(import_handler.clone()))
+            .app_data(web::Data::from(list_handler.clone()))
+            .app_data(web::Data::from(social_handler.clone()))
             
             // Add Swagger UI
             .service(
@@ -0,0 +1,59 @@
+use crate::domain::audit::SecurityEventType;
+use crate::middleware::client::client_info;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::services::audit_service::AuditService;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{web, HttpMessage};
+use serde_json::json;
+
+/// Record a `DataDeleted` security event for every successful delete of a
+/// restaurant, visit, wishlist item, tag or list.
+///
+/// Must run inside `validate_session`. Account changes are recorded by the
+/// services themselves, with more details than a path.
+pub async fn audit_deletes(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let kind = (req.method() == Method::DELETE)
+        .then(|| deleted_data_kind(req.path()))
+        .flatten();
+
+    let res = next.call(req).await?;
+
+    if let Some(kind) = kind
+        && res.status().is_success()
+        && let Some(audit) = res.request().app_data::<web::Data<AuditService>>()
+    {
+        // The handler has put its logged in user in the extensions
+        let user_id = res.request().extensions().get::<AuthenticatedUser>().map(AuthenticatedUser::id);
+        if let Some(user_id) = user_id {
+            let details = json!({"kind": kind, "path": res.request().path()});
+            let client = client_info(res.request());
+            audit.record(Some(user_id), SecurityEventType::DataDeleted, &client, Some(details)).await;
+        }
+    }
+
+    Ok(res)
+}
+
+/// What a `DELETE` on a path removes, if it is user data
+fn deleted_data_kind(path: &str) -> Option<&'static str> {
+    let segments: Vec<&str> = path
+        .strip_prefix("/api/v1/")?
+        .trim_end_matches('/')
+        .split('/')
+        .collect();
+
+    match segments.as_slice() {
+        ["restaurants", _] => Some("restaurant"),
+        ["restaurants", _, "visits", _] => Some("visit"),
+        ["wishlist", _] => Some("wishlist_item"),
+        ["tags", _] => Some("tag"),
+        ["lists", _] => Some("list"),
+        _ => None,
+    }
+}
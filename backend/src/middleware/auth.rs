@@ -0,0 +1,162 @@
+use crate::domain::api_token::{ApiTokenId, ApiTokenScope};
+use crate::domain::session::SessionData;
+use crate::domain::user::{User, UserId};
+use crate::error::{AppError, Result};
+use crate::services::api_token_service::ApiTokenService;
+use crate::services::auth_service::AuthService;
+use actix_session::SessionExt;
+use actix_web::dev::Payload;
+use actix_web::http::header;
+use actix_web::{web, FromRequest, HttpMessage, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use tracing::debug;
+
+/// The logged in user of a request.
+///
+/// Extracting it checks the login against the `sessions` or `api_tokens`
+/// table and the account against the `users` table, and answers 401 when
+/// there is no valid login. The result is cached in the request extensions,
+/// so a request looks the login up only once.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub user: User,
+    pub credential: Credential,
+}
+
+/// How a request proved who it comes from
+#[derive(Debug, Clone)]
+pub enum Credential {
+    /// Session cookie set at login
+    Session(SessionData),
+    /// `Authorization: Bearer` API token
+    ApiToken { id: ApiTokenId, scope: ApiTokenScope },
+}
+
+impl AuthenticatedUser {
+    pub fn id(&self) -> UserId {
+        self.user.id
+    }
+
+    /// The login session, for endpoints that API tokens cannot use
+    pub fn session(&self) -> Result<&SessionData> {
+        match &self.credential {
+            Credential::Session(data) => Ok(data),
+            Credential::ApiToken { .. } => Err(AppError::Authorization(
+                "This endpoint requires logging in, API tokens are not accepted".into(),
+            )),
+        }
+    }
+}
+
+/// The logged in user of a request, if any. Unlike `Option<AuthenticatedUser>`
+/// it still fails when the login cannot be checked, e.g. the database is down.
+#[derive(Debug, Clone)]
+pub struct OptionalUser(pub Option<AuthenticatedUser>);
+
+impl OptionalUser {
+    pub fn id(&self) -> Option<UserId> {
+        self.0.as_ref().map(AuthenticatedUser::id)
+    }
+}
+
+/// Find the valid login of a request.
+///
+/// An `Authorization: Bearer` API token takes precedence over the session
+/// cookie. A login whose session expired or was logged out, or whose account
+/// was deactivated, is removed from the session cookie and yields `None`.
+pub async fn authenticate(req: &HttpRequest) -> Result<Option<AuthenticatedUser>> {
+    if let Some(cached) = req.extensions().get::<AuthenticatedUser>() {
+        return Ok(Some(cached.clone()));
+    }
+
+    if let Some(token) = bearer_token(req)? {
+        return authenticate_token(req, &token).await.map(Some);
+    }
+
+    let session = req.get_session();
+    let data = match session.get::<SessionData>("user_data") {
+        Ok(Some(data)) => data,
+        Ok(None) => return Ok(None),
+        // Written by an older version, without a session id
+        Err(_) => {
+            session.remove("user_data");
+            return Ok(None);
+        }
+    };
+
+    let auth_service = req
+        .app_data::<web::Data<AuthService>>()
+        .ok_or_else(|| AppError::Internal("Authentication service not configured".into()))?;
+
+    match auth_service.validate_session(data.session_id).await {
+        Ok(user) if user.id.0 == data.user_id => {
+            let authenticated = AuthenticatedUser { user, credential: Credential::Session(data) };
+            req.extensions_mut().insert(authenticated.clone());
+            Ok(Some(authenticated))
+        }
+        Ok(_) | Err(AppError::Authentication(_)) => {
+            debug!("Dropping session {} of user {}", data.session_id, data.user_id);
+            session.remove("user_data");
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// The token of an `Authorization: Bearer` header, if the request has one
+fn bearer_token(req: &HttpRequest) -> Result<Option<String>> {
+    let Some(value) = req.headers().get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| Some(token.trim().to_string()))
+        .ok_or_else(|| AppError::Authentication("Expected a Bearer token".into()))
+}
+
+/// Check an API token. Unlike a stale cookie, a bad token fails the request:
+/// its client asked to act as someone.
+async fn authenticate_token(req: &HttpRequest, token: &str) -> Result<AuthenticatedUser> {
+    let token_service = req
+        .app_data::<web::Data<ApiTokenService>>()
+        .ok_or_else(|| AppError::Internal("API token service not configured".into()))?;
+
+    let (user, api_token) = token_service.authenticate(token).await?;
+    if !api_token.scope.allows(req.method()) {
+        return Err(AppError::Authorization("This API token is read-only".into()));
+    }
+
+    let authenticated = AuthenticatedUser {
+        user,
+        credential: Credential::ApiToken { id: api_token.id, scope: api_token.scope },
+    };
+    req.extensions_mut().insert(authenticated.clone());
+    Ok(authenticated)
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            authenticate(&req)
+                .await?
+                .ok_or_else(|| AppError::Authentication("Not authenticated".into()))
+        })
+    }
+}
+
+impl FromRequest for OptionalUser {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move { Ok(OptionalUser(authenticate(&req).await?)) })
+    }
+}
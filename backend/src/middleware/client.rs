@@ -0,0 +1,57 @@
+use crate::config::Config;
+use crate::domain::session::ClientInfo;
+use actix_web::http::header;
+use actix_web::{web, HttpRequest};
+use std::net::{IpAddr, SocketAddr};
+
+/// Where the address of a client is read from. Proxy headers can be forged
+/// by anyone reaching the backend directly, so they are only read when the
+/// backend sits behind a trusted reverse proxy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProxyPolicy {
+    pub trust_proxy: bool,
+}
+
+impl ProxyPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self { trust_proxy: config.trust_proxy }
+    }
+}
+
+/// Address of the client of a request. Proxy headers are ignored unless a
+/// trusting `ProxyPolicy` is registered as app data.
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+    let trust_proxy = req
+        .app_data::<web::Data<ProxyPolicy>>()
+        .is_some_and(|policy| policy.trust_proxy);
+
+    if trust_proxy {
+        // The address may come with a port
+        let forwarded = req
+            .connection_info()
+            .realip_remote_addr()
+            .and_then(|addr| {
+                addr.parse::<SocketAddr>()
+                    .map(|addr| addr.ip())
+                    .or_else(|_| addr.parse::<IpAddr>())
+                    .ok()
+            });
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+
+    req.peer_addr().map(|addr| addr.ip())
+}
+
+/// IP address and user agent of the client making a request
+pub fn client_info(req: &HttpRequest) -> ClientInfo {
+    let ip_address = client_ip(req).map(|ip| ip.to_string());
+    let user_agent = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(500).collect());
+
+    ClientInfo { ip_address, user_agent }
+}
@@ -0,0 +1,5 @@
+pub mod auth;
+pub mod session;
+pub mod rate_limit;
+pub mod audit;
+pub mod client;
\ No newline at end of file
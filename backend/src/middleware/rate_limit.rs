@@ -0,0 +1,48 @@
+use crate::domain::rate_limit::RouteGroup;
+use crate::domain::session::SessionData;
+use crate::middleware::client::client_ip;
+use crate::services::rate_limiter::RateLimiter;
+use actix_session::SessionExt;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::web;
+
+/// Apply the `RateLimiter` to every API request, answering 429 with a
+/// `Retry-After` header once a client's bucket is empty.
+///
+/// Must run before `validate_session`, so that a request is counted before its
+/// login is checked and guessing API tokens is throttled like guessing
+/// passwords. Must run inside the session middleware. Requests pass
+/// unchecked when no `RateLimiter` is registered.
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    if let (Some(limiter), Some(group)) = (
+        req.app_data::<web::Data<RateLimiter>>(),
+        RouteGroup::for_path(req.path()),
+    ) {
+        limiter.check(group, &client_key(&req, group)).await?;
+    }
+
+    next.call(req).await
+}
+
+/// Who a request is counted against. The session cookie is encrypted with
+/// the server key, so its user can be trusted for counting before the session
+/// itself is checked. API tokens are counted per address until checked.
+fn client_key(req: &ServiceRequest, group: RouteGroup) -> String {
+    if group != RouteGroup::Auth
+        && !req.headers().contains_key(header::AUTHORIZATION)
+        && let Ok(Some(data)) = req.get_session().get::<SessionData>("user_data")
+    {
+        return format!("user:{}", data.user_id);
+    }
+
+    match client_ip(req.request()) {
+        Some(ip) => format!("ip:{}", ip),
+        None => "ip:unknown".to_string(),
+    }
+}
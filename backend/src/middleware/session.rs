@@ -0,0 +1,27 @@
+use crate::middleware::auth::authenticate;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use tracing::debug;
+
+/// Check the session of every request against the `sessions` table.
+///
+/// The login of a session that expired, was logged out elsewhere or belongs
+/// to a deactivated account is dropped before the request reaches its
+/// handler, which then sees an anonymous request. A valid login is kept in
+/// the request extensions for the `AuthenticatedUser` extractor. Must run
+/// inside the session middleware.
+///
+/// A bad API token, or a login that cannot be checked, also leaves the
+/// request anonymous: public endpoints keep working, and the extractor of
+/// endpoints that need a login checks again and answers the error.
+pub async fn validate_session(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    if let Err(e) = authenticate(req.request()).await {
+        debug!("Handling {} without a login: {}", req.path(), e);
+    }
+
+    next.call(req).await
+}
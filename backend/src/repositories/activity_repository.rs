@@ -0,0 +1,146 @@
+use crate::domain::social::{ActivityEvent, ActivityId, Chronological, RecordActivity};
+use crate::domain::pagination::{CursorPage, PageRequest};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::user::UserId;
+use crate::domain::wishlist::WishlistId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::{PgExecutor, Row};
+use sqlx::postgres::PgRow;
+
+#[async_trait]
+pub trait ActivityRepository: Send + Sync {
+    async fn record(&self, activity: RecordActivity) -> Result<()>;
+    async fn find_feed(&self, viewer_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<ActivityEvent>>;
+    async fn find_by_user(&self, user_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<ActivityEvent>>;
+}
+
+pub struct PostgresActivityRepository {
+    pool: DbPool,
+}
+
+impl PostgresActivityRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> ActivityEvent {
+        ActivityEvent {
+            id: ActivityId(row.get("id")),
+            user_id: UserId(row.get("user_id")),
+            username: row.get("username"),
+            kind: row.get("kind"),
+            restaurant_id: row.get::<Option<i32>, _>("restaurant_id").map(RestaurantId),
+            wishlist_item_id: row.get::<Option<i32>, _>("wishlist_item_id").map(WishlistId),
+            name: row.get("name"),
+            city: row.get("city"),
+            rating: row.get("rating"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    /// Page through the events matching `filters`, where `$1` is the bound user
+    async fn find_page(
+        &self,
+        filters: &str,
+        user_id: UserId,
+        page: &PageRequest<Chronological>,
+    ) -> Result<CursorPage<ActivityEvent>> {
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM activity_events a JOIN users u ON u.id = a.user_id WHERE {filters}"
+        ))
+        .bind(user_id.0)
+        .fetch_one(&self.pool)
+        .await?;
+
+        // Keyset pagination on (created_at, id), newest first
+        let mut conditions = filters.to_string();
+        let mut param_count = 2;
+        if page.after.is_some() {
+            conditions.push_str(&format!(
+                " AND (a.created_at, a.id) {} (${}::timestamptz, ${})",
+                page.direction.after_operator(),
+                param_count,
+                param_count + 1
+            ));
+            param_count += 2;
+        }
+        let direction = page.direction.sql();
+
+        let query = format!(
+            r#"
+            SELECT a.id, a.user_id, u.username, a.kind, a.restaurant_id, a.wishlist_item_id,
+                   a.name, a.city, a.rating, a.created_at, a.created_at::text AS sort_key
+            FROM activity_events a
+            JOIN users u ON u.id = a.user_id
+            WHERE {conditions}
+            ORDER BY a.created_at {direction}, a.id {direction}
+            LIMIT ${param_count}
+            "#
+        );
+
+        let mut sqlx_query = sqlx::query(&query).bind(user_id.0);
+        if let Some(cursor) = &page.after {
+            sqlx_query = sqlx_query.bind(&cursor.key).bind(cursor.id);
+        }
+
+        // Fetch one extra row to know whether another page follows
+        let mut rows = sqlx_query
+            .bind(page.limit + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_more = rows.len() as i64 > page.limit;
+        rows.truncate(page.limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(row) if has_more => Some(page.cursor_after(row.get("sort_key"), row.get("id")).encode()),
+            _ => None,
+        };
+
+        let items = rows.into_iter().map(Self::map_row).collect();
+
+        Ok(CursorPage { items, next_cursor, total })
+    }
+}
+
+#[async_trait]
+impl ActivityRepository for PostgresActivityRepository {
+    async fn record(&self, activity: RecordActivity) -> Result<()> {
+        insert_activity(&self.pool, &activity).await
+    }
+
+    async fn find_feed(&self, viewer_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<ActivityEvent>> {
+        // Users who went private since being followed drop out of the feed
+        let filters = r#"a.user_id IN (SELECT followee_id FROM follows WHERE follower_id = $1)
+            AND u.is_active = true AND u.is_private = false"#;
+        self.find_page(filters, viewer_id, page).await
+    }
+
+    async fn find_by_user(&self, user_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<ActivityEvent>> {
+        self.find_page("a.user_id = $1", user_id, page).await
+    }
+}
+
+/// Record an activity event as part of a larger write, such as the
+/// transaction that changed the rating it reports on
+pub async fn insert_activity<'e>(executor: impl PgExecutor<'e>, activity: &RecordActivity) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO activity_events (user_id, kind, restaurant_id, wishlist_item_id, name, city, rating, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+        "#
+    )
+    .bind(activity.user_id.0)
+    .bind(activity.kind)
+    .bind(activity.restaurant_id.map(|id| id.0))
+    .bind(activity.wishlist_item_id.map(|id| id.0))
+    .bind(&activity.name)
+    .bind(&activity.city)
+    .bind(activity.rating)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use crate::domain::api_token::{ApiToken, ApiTokenId, CreateApiToken};
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    async fn create(&self, token: CreateApiToken) -> Result<ApiToken>;
+    async fn find_by_user(&self, user_id: UserId) -> Result<Vec<ApiToken>>;
+    /// Unexpired token with this hash
+    async fn find_valid_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>>;
+    async fn touch(&self, id: ApiTokenId) -> Result<()>;
+    async fn delete(&self, id: ApiTokenId, user_id: UserId) -> Result<bool>;
+}
+
+pub struct PostgresApiTokenRepository {
+    pool: DbPool,
+}
+
+const TOKEN_COLUMNS: &str =
+    "id, user_id, name, token_prefix, scope, created_at, expires_at, last_used_at";
+
+impl PostgresApiTokenRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> ApiToken {
+        ApiToken {
+            id: ApiTokenId(row.get("id")),
+            user_id: UserId(row.get("user_id")),
+            name: row.get("name"),
+            token_prefix: row.get("token_prefix"),
+            scope: row.get("scope"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            last_used_at: row.get("last_used_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for PostgresApiTokenRepository {
+    async fn create(&self, token: CreateApiToken) -> Result<ApiToken> {
+        let query = format!(
+            r#"
+            INSERT INTO api_tokens (user_id, name, token_hash, token_prefix, scope, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            TOKEN_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(token.user_id.0)
+            .bind(&token.name)
+            .bind(&token.token_hash)
+            .bind(&token.token_prefix)
+            .bind(token.scope)
+            .bind(token.expires_at)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(Self::map_row(row))
+    }
+
+    async fn find_by_user(&self, user_id: UserId) -> Result<Vec<ApiToken>> {
+        let query = format!(
+            "SELECT {} FROM api_tokens WHERE user_id = $1 ORDER BY created_at DESC",
+            TOKEN_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(user_id.0)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn find_valid_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>> {
+        let query = format!(
+            r#"
+            SELECT {} FROM api_tokens
+            WHERE token_hash = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+            TOKEN_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn touch(&self, id: ApiTokenId) -> Result<()> {
+        // At most one write a minute for a busy token
+        sqlx::query(
+            r#"
+            UPDATE api_tokens SET last_used_at = NOW()
+            WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')
+            "#
+        )
+        .bind(id.0)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: ApiTokenId, user_id: UserId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM api_tokens WHERE id = $1 AND user_id = $2")
+            .bind(id.0)
+            .bind(user_id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::domain::audit::{AuditEvent, AuditEventId, RecordAuditEvent};
+use crate::domain::pagination::{CursorPage, PageRequest};
+use crate::domain::social::Chronological;
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn record(&self, event: RecordAuditEvent) -> Result<()>;
+    async fn find_by_user(&self, user_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<AuditEvent>>;
+    /// Delete events older than the given number of days
+    async fn delete_older_than(&self, days: u32) -> Result<u64>;
+}
+
+pub struct PostgresAuditRepository {
+    pool: DbPool,
+}
+
+impl PostgresAuditRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> AuditEvent {
+        AuditEvent {
+            id: AuditEventId(row.get("id")),
+            user_id: row.get::<Option<i32>, _>("user_id").map(UserId),
+            event_type: row.get("event_type"),
+            details: row.get("details"),
+            ip_address: row.get("ip_address"),
+            user_agent: row.get("user_agent"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for PostgresAuditRepository {
+    async fn record(&self, event: RecordAuditEvent) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (user_id, event_type, details, ip_address, user_agent, created_at)
+            VALUES ($1, $2, $3, $4::inet, $5, NOW())
+            "#
+        )
+        .bind(event.user_id.map(|id| id.0))
+        .bind(event.event_type)
+        .bind(&event.details)
+        .bind(&event.client.ip_address)
+        .bind(&event.client.user_agent)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<AuditEvent>> {
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM audit_log WHERE user_id = $1")
+            .bind(user_id.0)
+            .fetch_one(&self.pool)
+            .await?;
+
+        // Keyset pagination on (created_at, id), newest first
+        let mut conditions = "user_id = $1".to_string();
+        let mut param_count = 2;
+        if page.after.is_some() {
+            conditions.push_str(&format!(
+                " AND (created_at, id) {} (${}::timestamptz, ${})",
+                page.direction.after_operator(),
+                param_count,
+                param_count + 1
+            ));
+            param_count += 2;
+        }
+        let direction = page.direction.sql();
+
+        let query = format!(
+            r#"
+            SELECT id, user_id, event_type, details, host(ip_address) AS ip_address, user_agent,
+                   created_at, created_at::text AS sort_key
+            FROM audit_log
+            WHERE {conditions}
+            ORDER BY created_at {direction}, id {direction}
+            LIMIT ${param_count}
+            "#
+        );
+
+        let mut sqlx_query = sqlx::query(&query).bind(user_id.0);
+        if let Some(cursor) = &page.after {
+            sqlx_query = sqlx_query.bind(&cursor.key).bind(cursor.id);
+        }
+
+        // Fetch one extra row to know whether another page follows
+        let mut rows = sqlx_query
+            .bind(page.limit + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_more = rows.len() as i64 > page.limit;
+        rows.truncate(page.limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(row) if has_more => Some(page.cursor_after(row.get("sort_key"), row.get("id")).encode()),
+            _ => None,
+        };
+
+        let items = rows.into_iter().map(Self::map_row).collect();
+
+        Ok(CursorPage { items, next_cursor, total })
+    }
+
+    async fn delete_older_than(&self, days: u32) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM audit_log WHERE created_at < NOW() - make_interval(days => $1)")
+            .bind(days as i32)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,134 @@
+use crate::domain::import::{ImportEntry, ImportOutcome};
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::{Postgres, Row, Transaction};
+
+#[async_trait]
+pub trait ImportRepository: Send + Sync {
+    /// Save the entries in one transaction, skipping duplicates. A dry run
+    /// rolls the transaction back, so its outcomes match a real import.
+    async fn import(&self, owner_id: UserId, entries: &[ImportEntry], dry_run: bool) -> Result<Vec<ImportOutcome>>;
+}
+
+pub struct PostgresImportRepository {
+    pool: DbPool,
+}
+
+/// Normalized form of a name or city: unaccented, lowercase, single-spaced
+const NORMALIZE: &str = "lower(unaccent(regexp_replace(btrim({}), '\\s+', ' ', 'g')))";
+
+fn normalized(expr: &str) -> String {
+    NORMALIZE.replace("{}", expr)
+}
+
+impl PostgresImportRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Find an entry of the table with the same normalized name and city.
+    /// Rows inserted earlier in the transaction are visible, so duplicates
+    /// within the file are caught as well.
+    async fn find_duplicate(
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        owner_id: UserId,
+        name: &str,
+        city: &str,
+    ) -> Result<Option<i32>> {
+        let query = format!(
+            "SELECT id FROM {} WHERE owner_id = $1 AND {} = {} AND {} = {} ORDER BY id LIMIT 1",
+            table,
+            normalized("name"),
+            normalized("$2"),
+            normalized("city"),
+            normalized("$3"),
+        );
+
+        let row = sqlx::query(&query)
+            .bind(owner_id.0)
+            .bind(name)
+            .bind(city)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        Ok(row.map(|row| row.get("id")))
+    }
+}
+
+#[async_trait]
+impl ImportRepository for PostgresImportRepository {
+    async fn import(&self, owner_id: UserId, entries: &[ImportEntry], dry_run: bool) -> Result<Vec<ImportOutcome>> {
+        let mut tx = self.pool.begin().await?;
+        let mut outcomes = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let outcome = match entry {
+                ImportEntry::Restaurant(restaurant) => {
+                    match Self::find_duplicate(&mut tx, "restaurants", owner_id, &restaurant.name, &restaurant.city).await? {
+                        Some(id) => ImportOutcome::Duplicate(id),
+                        None => {
+                            let id: i32 = sqlx::query_scalar(
+                                r#"
+                                INSERT INTO restaurants (owner_id, name, city, rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at)
+                                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW(), NOW())
+                                RETURNING id
+                                "#
+                            )
+                            .bind(owner_id.0)
+                            .bind(&restaurant.name)
+                            .bind(&restaurant.city)
+                            .bind(restaurant.rating)
+                            .bind(&restaurant.description)
+                            .bind(restaurant.is_favorite)
+                            .bind(&restaurant.address)
+                            .bind(restaurant.latitude)
+                            .bind(restaurant.longitude)
+                            .bind(&restaurant.source_id)
+                            .fetch_one(&mut *tx)
+                            .await?;
+                            ImportOutcome::Created(id)
+                        }
+                    }
+                }
+                ImportEntry::Wishlist(item) => {
+                    match Self::find_duplicate(&mut tx, "wishlist_items", owner_id, &item.name, &item.city).await? {
+                        Some(id) => ImportOutcome::Duplicate(id),
+                        None => {
+                            let id: i32 = sqlx::query_scalar(
+                                r#"
+                                INSERT INTO wishlist_items (owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at)
+                                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW())
+                                RETURNING id
+                                "#
+                            )
+                            .bind(owner_id.0)
+                            .bind(&item.name)
+                            .bind(&item.city)
+                            .bind(&item.notes)
+                            .bind(item.priority)
+                            .bind(&item.address)
+                            .bind(item.latitude)
+                            .bind(item.longitude)
+                            .bind(&item.source_id)
+                            .fetch_one(&mut *tx)
+                            .await?;
+                            ImportOutcome::Created(id)
+                        }
+                    }
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+
+        Ok(outcomes)
+    }
+}
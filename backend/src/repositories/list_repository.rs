@@ -0,0 +1,491 @@
+use crate::domain::list::{
+    List, ListId, ListEntry, ListEntryId, ListEntryKind, CreateList, UpdateList, AddListEntry,
+    UpdateListEntry,
+};
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{Postgres, Row, Transaction};
+
+#[async_trait]
+pub trait ListRepository: Send + Sync {
+    async fn create(&self, list: CreateList) -> Result<List>;
+    async fn find_by_id(&self, id: ListId, owner_id: UserId) -> Result<Option<List>>;
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<List>>;
+    async fn find_by_share_token(&self, token: &str) -> Result<Option<List>>;
+    async fn update(&self, list: UpdateList) -> Result<Option<List>>;
+    async fn delete(&self, id: ListId, owner_id: UserId) -> Result<bool>;
+    async fn set_share_token(&self, id: ListId, owner_id: UserId, token: Option<&str>) -> Result<Option<List>>;
+    async fn find_entries(&self, list_id: ListId) -> Result<Vec<ListEntry>>;
+    async fn find_entry(&self, id: ListEntryId, list_id: ListId) -> Result<Option<ListEntry>>;
+    async fn add_entry(&self, entry: AddListEntry) -> Result<Option<ListEntryId>>;
+    async fn update_entry(&self, entry: UpdateListEntry) -> Result<bool>;
+    async fn remove_entry(&self, id: ListEntryId, list_id: ListId, owner_id: UserId) -> Result<bool>;
+    async fn reorder_entries(&self, list_id: ListId, owner_id: UserId, entry_ids: &[ListEntryId]) -> Result<bool>;
+}
+
+pub struct PostgresListRepository {
+    pool: DbPool,
+}
+
+/// Columns of a list, including its entry count
+const LIST_COLUMNS: &str = "id, owner_id, name, description, share_token, created_at, updated_at, \
+     (SELECT COUNT(*) FROM list_entries WHERE list_entries.list_id = lists.id) AS entry_count";
+
+/// Entries joined with their restaurant or wishlist item. Positions are
+/// renumbered from 0 so gaps left by deleted items never show.
+const ENTRY_QUERY: &str = r#"
+    SELECT e.id, e.list_id, e.restaurant_id, e.wishlist_item_id, e.note, e.created_at,
+           (ROW_NUMBER() OVER (ORDER BY e.position, e.id) - 1)::integer AS position,
+           COALESCE(r.name, w.name) AS name,
+           COALESCE(r.city, w.city) AS city,
+           COALESCE(ROUND(r.visit_rating)::smallint, r.rating) AS rating,
+           COALESCE(r.address, w.address) AS address,
+           COALESCE(r.latitude, w.latitude) AS latitude,
+           COALESCE(r.longitude, w.longitude) AS longitude
+    FROM list_entries e
+    LEFT JOIN restaurants r ON r.id = e.restaurant_id
+    LEFT JOIN wishlist_items w ON w.id = e.wishlist_item_id
+    WHERE e.list_id = $1
+"#;
+
+impl PostgresListRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> List {
+        List {
+            id: ListId(row.get("id")),
+            owner_id: UserId(row.get("owner_id")),
+            name: row.get("name"),
+            description: row.get("description"),
+            share_token: row.get("share_token"),
+            entry_count: row.get("entry_count"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    fn map_entry(row: PgRow) -> ListEntry {
+        let restaurant_id: Option<i32> = row.get("restaurant_id");
+        let wishlist_item_id: Option<i32> = row.get("wishlist_item_id");
+        let (kind, item_id) = match (restaurant_id, wishlist_item_id) {
+            (Some(id), _) => (ListEntryKind::Restaurant, id),
+            (None, id) => (ListEntryKind::Wishlist, id.unwrap_or_default()),
+        };
+
+        ListEntry {
+            id: ListEntryId(row.get("id")),
+            list_id: ListId(row.get("list_id")),
+            kind,
+            item_id,
+            position: row.get("position"),
+            note: row.get("note"),
+            name: row.get("name"),
+            city: row.get("city"),
+            rating: row.get("rating"),
+            address: row.get("address"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    /// Lock the list for the rest of the transaction, checking ownership
+    async fn lock_list(
+        tx: &mut Transaction<'_, Postgres>,
+        list_id: ListId,
+        owner_id: UserId,
+    ) -> Result<bool> {
+        let row = sqlx::query("SELECT id FROM lists WHERE id = $1 AND owner_id = $2 FOR UPDATE")
+            .bind(list_id.0)
+            .bind(owner_id.0)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        Ok(row.is_some())
+    }
+
+    /// Renumber positions 0..n, closing gaps left by deleted restaurants and
+    /// wishlist items. Returns the number of entries.
+    async fn compact_positions(tx: &mut Transaction<'_, Postgres>, list_id: ListId) -> Result<i32> {
+        sqlx::query(
+            r#"
+            UPDATE list_entries e
+            SET position = n.position
+            FROM (
+                SELECT id, (ROW_NUMBER() OVER (ORDER BY position, id) - 1)::integer AS position
+                FROM list_entries
+                WHERE list_id = $1
+            ) n
+            WHERE e.id = n.id AND e.position <> n.position
+            "#
+        )
+        .bind(list_id.0)
+        .execute(&mut **tx)
+        .await?;
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM list_entries WHERE list_id = $1")
+            .bind(list_id.0)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        Ok(count as i32)
+    }
+
+    async fn touch(tx: &mut Transaction<'_, Postgres>, list_id: ListId) -> Result<()> {
+        sqlx::query("UPDATE lists SET updated_at = NOW() WHERE id = $1")
+            .bind(list_id.0)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ListRepository for PostgresListRepository {
+    async fn create(&self, list: CreateList) -> Result<List> {
+        let query = format!(
+            r#"
+            INSERT INTO lists (owner_id, name, description, created_at, updated_at)
+            VALUES ($1, $2, $3, NOW(), NOW())
+            RETURNING {}
+            "#,
+            LIST_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(list.owner_id.0)
+            .bind(&list.name)
+            .bind(&list.description)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(Self::map_row(row))
+    }
+
+    async fn find_by_id(&self, id: ListId, owner_id: UserId) -> Result<Option<List>> {
+        let query = format!("SELECT {} FROM lists WHERE id = $1 AND owner_id = $2", LIST_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.0)
+            .bind(owner_id.0)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<List>> {
+        let query = format!(
+            "SELECT {} FROM lists WHERE owner_id = $1 ORDER BY updated_at DESC, id DESC",
+            LIST_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(owner_id.0)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn find_by_share_token(&self, token: &str) -> Result<Option<List>> {
+        let query = format!("SELECT {} FROM lists WHERE share_token = $1", LIST_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(token)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn update(&self, list: UpdateList) -> Result<Option<List>> {
+        let mut query_parts = Vec::new();
+        let mut param_count = 1;
+
+        if list.name.is_some() {
+            query_parts.push(format!("name = ${}", param_count));
+            param_count += 1;
+        }
+
+        if list.description.is_some() {
+            query_parts.push(format!("description = ${}", param_count));
+            param_count += 1;
+        }
+
+        if query_parts.is_empty() {
+            return self.find_by_id(list.id, list.owner_id).await;
+        }
+
+        query_parts.push("updated_at = NOW()".to_string());
+
+        let query = format!(
+            r#"
+            UPDATE lists
+            SET {}
+            WHERE id = ${} AND owner_id = ${}
+            RETURNING {}
+            "#,
+            query_parts.join(", "),
+            param_count,
+            param_count + 1,
+            LIST_COLUMNS
+        );
+
+        let mut sqlx_query = sqlx::query(&query);
+
+        if let Some(name) = &list.name {
+            sqlx_query = sqlx_query.bind(name);
+        }
+        if let Some(description) = &list.description {
+            sqlx_query = sqlx_query.bind(description);
+        }
+
+        let row = sqlx_query
+            .bind(list.id.0)
+            .bind(list.owner_id.0)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn delete(&self, id: ListId, owner_id: UserId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM lists WHERE id = $1 AND owner_id = $2")
+            .bind(id.0)
+            .bind(owner_id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_share_token(&self, id: ListId, owner_id: UserId, token: Option<&str>) -> Result<Option<List>> {
+        let query = format!(
+            r#"
+            UPDATE lists
+            SET share_token = $1, updated_at = NOW()
+            WHERE id = $2 AND owner_id = $3
+            RETURNING {}
+            "#,
+            LIST_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(token)
+            .bind(id.0)
+            .bind(owner_id.0)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn find_entries(&self, list_id: ListId) -> Result<Vec<ListEntry>> {
+        let query = format!("{} ORDER BY e.position, e.id", ENTRY_QUERY);
+
+        let rows = sqlx::query(&query)
+            .bind(list_id.0)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Self::map_entry).collect())
+    }
+
+    async fn find_entry(&self, id: ListEntryId, list_id: ListId) -> Result<Option<ListEntry>> {
+        // Filter after numbering so the position stays relative to the whole list
+        let query = format!("SELECT * FROM ({}) entries WHERE id = $2", ENTRY_QUERY);
+
+        let row = sqlx::query(&query)
+            .bind(list_id.0)
+            .bind(id.0)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_entry))
+    }
+
+    async fn add_entry(&self, entry: AddListEntry) -> Result<Option<ListEntryId>> {
+        let mut tx = self.pool.begin().await?;
+
+        if !Self::lock_list(&mut tx, entry.list_id, entry.owner_id).await? {
+            return Ok(None);
+        }
+
+        let count = Self::compact_positions(&mut tx, entry.list_id).await?;
+        let position = entry.position.unwrap_or(count).min(count);
+
+        sqlx::query("UPDATE list_entries SET position = position + 1 WHERE list_id = $1 AND position >= $2")
+            .bind(entry.list_id.0)
+            .bind(position)
+            .execute(&mut *tx)
+            .await?;
+
+        // Only insert when the restaurant or wishlist item belongs to the caller
+        let (table, target_column, item_id) = match (entry.restaurant_id, entry.wishlist_item_id) {
+            (Some(id), _) => ("restaurants", "restaurant_id", id.0),
+            (None, Some(id)) => ("wishlist_items", "wishlist_item_id", id.0),
+            (None, None) => return Ok(None),
+        };
+        let query = format!(
+            r#"
+            INSERT INTO list_entries (list_id, {target_column}, position, note, created_at)
+            SELECT $1, id, $3, $4, NOW()
+            FROM {table}
+            WHERE id = $2 AND owner_id = $5
+            RETURNING id
+            "#
+        );
+
+        let id: Option<i32> = sqlx::query_scalar(&query)
+            .bind(entry.list_id.0)
+            .bind(item_id)
+            .bind(position)
+            .bind(&entry.note)
+            .bind(entry.owner_id.0)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let Some(id) = id else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+
+        Self::touch(&mut tx, entry.list_id).await?;
+        tx.commit().await?;
+
+        Ok(Some(ListEntryId(id)))
+    }
+
+    async fn update_entry(&self, entry: UpdateListEntry) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        if !Self::lock_list(&mut tx, entry.list_id, entry.owner_id).await? {
+            return Ok(false);
+        }
+
+        let count = Self::compact_positions(&mut tx, entry.list_id).await?;
+
+        let current: Option<i32> = sqlx::query_scalar(
+            "SELECT position FROM list_entries WHERE id = $1 AND list_id = $2"
+        )
+        .bind(entry.id.0)
+        .bind(entry.list_id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(current) = current else {
+            tx.rollback().await?;
+            return Ok(false);
+        };
+
+        if let Some(note) = &entry.note {
+            sqlx::query("UPDATE list_entries SET note = $1 WHERE id = $2")
+                .bind(note)
+                .bind(entry.id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        if let Some(position) = entry.position {
+            let position = position.min(count - 1);
+
+            // Shift the entries between the old and new positions by one
+            if position < current {
+                sqlx::query(
+                    "UPDATE list_entries SET position = position + 1 WHERE list_id = $1 AND position >= $2 AND position < $3"
+                )
+                .bind(entry.list_id.0)
+                .bind(position)
+                .bind(current)
+                .execute(&mut *tx)
+                .await?;
+            } else if position > current {
+                sqlx::query(
+                    "UPDATE list_entries SET position = position - 1 WHERE list_id = $1 AND position > $2 AND position <= $3"
+                )
+                .bind(entry.list_id.0)
+                .bind(current)
+                .bind(position)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            sqlx::query("UPDATE list_entries SET position = $1 WHERE id = $2")
+                .bind(position)
+                .bind(entry.id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        Self::touch(&mut tx, entry.list_id).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn remove_entry(&self, id: ListEntryId, list_id: ListId, owner_id: UserId) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        if !Self::lock_list(&mut tx, list_id, owner_id).await? {
+            return Ok(false);
+        }
+
+        let result = sqlx::query("DELETE FROM list_entries WHERE id = $1 AND list_id = $2")
+            .bind(id.0)
+            .bind(list_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        Self::compact_positions(&mut tx, list_id).await?;
+        Self::touch(&mut tx, list_id).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn reorder_entries(&self, list_id: ListId, owner_id: UserId, entry_ids: &[ListEntryId]) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        if !Self::lock_list(&mut tx, list_id, owner_id).await? {
+            return Ok(false);
+        }
+
+        let ids: Vec<i32> = entry_ids.iter().map(|id| id.0).collect();
+        let result = sqlx::query(
+            r#"
+            UPDATE list_entries e
+            SET position = (o.ordinality - 1)::integer
+            FROM unnest($2::integer[]) WITH ORDINALITY AS o(id, ordinality)
+            WHERE e.id = o.id AND e.list_id = $1
+            "#
+        )
+        .bind(list_id.0)
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await?;
+
+        // Every entry of the list must be given exactly once
+        let count = Self::compact_positions(&mut tx, list_id).await?;
+        if result.rows_affected() != ids.len() as u64 || count as usize != ids.len() {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        Self::touch(&mut tx, list_id).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::domain::geo::{BoundingBox, EARTH_RADIUS_KM};
+
+/// SQL expression for the great-circle (haversine) distance in kilometers
+/// between the row's coordinates and the point bound at `$lat`/`$lon`
+pub fn distance_sql(lat: usize, lon: usize) -> String {
+    format!(
+        "2 * {radius} * asin(least(1, sqrt(\
+            power(sin(radians(latitude - ${lat}) / 2), 2) + \
+            cos(radians(${lat})) * cos(radians(latitude)) * \
+            power(sin(radians(longitude - ${lon}) / 2), 2))))",
+        radius = EARTH_RADIUS_KM,
+    )
+}
+
+/// SQL condition keeping rows inside a bounding box whose edges are bound
+/// as `min_lat, max_lat, min_lon, max_lon` starting at `$first`
+pub fn within_sql(bounds: &BoundingBox, first: usize) -> String {
+    let longitude = if bounds.crosses_antimeridian() {
+        format!("(longitude >= ${} OR longitude <= ${})", first + 2, first + 3)
+    } else {
+        format!("longitude BETWEEN ${} AND ${}", first + 2, first + 3)
+    };
+
+    format!(
+        "latitude IS NOT NULL AND latitude BETWEEN ${} AND ${} AND {}",
+        first,
+        first + 1,
+        longitude
+    )
+}
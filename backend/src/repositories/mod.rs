@@ -1,4 +1,15 @@
 pub mod user_repository;
 pub mod restaurant_repository;
 pub mod wishlist_repository;
-pub mod session_repository;
\ No newline at end of file
+pub mod session_repository;
+pub mod visit_repository;
+pub mod tag_repository;
+pub mod import_repository;
+pub mod location;
+pub mod list_repository;
+pub mod activity_repository;
+pub mod place_repository;
+pub mod api_token_repository;
+pub mod user_token_repository;
+pub mod two_factor_repository;
+pub mod audit_repository;
\ No newline at end of file
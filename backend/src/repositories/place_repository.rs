@@ -0,0 +1,108 @@
+use crate::domain::autocomplete::{ImportPlace, Place};
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[async_trait]
+pub trait PlaceRepository: Send + Sync {
+    async fn search(&self, term: &str, limit: i64) -> Result<Vec<Place>>;
+    async fn upsert_many(&self, source: &str, places: &[ImportPlace]) -> Result<u64>;
+}
+
+pub struct PostgresPlaceRepository {
+    pool: DbPool,
+}
+
+impl PostgresPlaceRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Escape the LIKE wildcards of user input
+pub fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[async_trait]
+impl PlaceRepository for PostgresPlaceRepository {
+    async fn search(&self, term: &str, limit: i64) -> Result<Vec<Place>> {
+        // Names containing the term come first, starting with those that
+        // begin with it; close spellings are caught by trigram similarity
+        let rows = sqlx::query(
+            r#"
+            SELECT source, source_id, name, city, cuisine, latitude, longitude,
+                   address, postcode, opening_hours, website
+            FROM places
+            WHERE lower(name) LIKE '%' || $2 || '%'
+               OR lower($1) <% lower(name)
+            ORDER BY lower(name) LIKE $2 || '%' DESC,
+                     lower(name) LIKE '%' || $2 || '%' DESC,
+                     word_similarity(lower($1), lower(name)) DESC,
+                     name
+            LIMIT $3
+            "#
+        )
+        .bind(term)
+        .bind(escape_like(&term.to_lowercase()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| Place {
+            source: row.get("source"),
+            source_id: row.get("source_id"),
+            name: row.get("name"),
+            city: row.get("city"),
+            cuisine: row.get("cuisine"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+            address: row.get("address"),
+            postcode: row.get("postcode"),
+            opening_hours: row.get("opening_hours"),
+            website: row.get("website"),
+        }).collect())
+    }
+
+    async fn upsert_many(&self, source: &str, places: &[ImportPlace]) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO places (source, source_id, name, city, cuisine, latitude, longitude,
+                                address, postcode, opening_hours, website, imported_at)
+            SELECT $1, source_id, name, city, cuisine, latitude, longitude,
+                   address, postcode, opening_hours, website, NOW()
+            FROM UNNEST($2::text[], $3::text[], $4::text[], $5::text[], $6::float8[], $7::float8[],
+                        $8::text[], $9::text[], $10::text[], $11::text[])
+                AS p(source_id, name, city, cuisine, latitude, longitude,
+                     address, postcode, opening_hours, website)
+            ON CONFLICT (source, source_id) DO UPDATE SET
+                name = EXCLUDED.name,
+                city = EXCLUDED.city,
+                cuisine = EXCLUDED.cuisine,
+                latitude = EXCLUDED.latitude,
+                longitude = EXCLUDED.longitude,
+                address = EXCLUDED.address,
+                postcode = EXCLUDED.postcode,
+                opening_hours = EXCLUDED.opening_hours,
+                website = EXCLUDED.website,
+                imported_at = EXCLUDED.imported_at
+            "#
+        )
+        .bind(source)
+        .bind(places.iter().map(|p| p.source_id.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.name.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.city.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.cuisine.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.latitude).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.longitude).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.address.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.postcode.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.opening_hours.clone()).collect::<Vec<_>>())
+        .bind(places.iter().map(|p| p.website.clone()).collect::<Vec<_>>())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,19 +1,33 @@
-use crate::domain::restaurant::{Restaurant, RestaurantId, CreateRestaurant, UpdateRestaurant, RestaurantStatsResponse};
+use crate::domain::restaurant::{
+    Restaurant, RestaurantId, CreateRestaurant, UpdateRestaurant, RestaurantStatsResponse,
+    RatingBucket, CityCount, MonthCount, WishlistConversionStats, TOP_CITIES, STATS_MONTHS,
+    RestaurantSearch, RestaurantSearchHit, RestaurantSort, HIGHLIGHT_START, HIGHLIGHT_STOP,
+};
+use crate::domain::geo::{BoundingBox, NearbySearch};
+use crate::domain::pagination::{CursorPage, PageRequest};
+use crate::domain::tag::TagId;
 use crate::domain::user::UserId;
 use crate::error::Result;
 use crate::database::DbPool;
+use crate::repositories::location::{distance_sql, within_sql};
+use crate::repositories::tag_repository::resolve_new_row_tags;
+use crate::repositories::place_repository::escape_like;
 use async_trait::async_trait;
-use bigdecimal::BigDecimal;
 use sqlx::Row;
+use sqlx::postgres::PgRow;
 
 #[async_trait]
 pub trait RestaurantRepository: Send + Sync {
     async fn create(&self, restaurant: CreateRestaurant) -> Result<Restaurant>;
     async fn find_by_id(&self, id: RestaurantId) -> Result<Option<Restaurant>>;
-    async fn find_by_owner(&self, owner_id: UserId, favorites_only: bool, limit: Option<i64>) -> Result<Vec<Restaurant>>;
+    async fn find_by_owner(&self, owner_id: UserId, favorites_only: bool, tag_ids: &[TagId], page: &PageRequest<RestaurantSort>) -> Result<CursorPage<Restaurant>>;
     async fn update(&self, restaurant: UpdateRestaurant) -> Result<Option<Restaurant>>;
     async fn delete(&self, id: RestaurantId, owner_id: UserId) -> Result<bool>;
     async fn get_stats(&self, owner_id: UserId) -> Result<RestaurantStatsResponse>;
+    async fn search(&self, search: &RestaurantSearch) -> Result<(Vec<RestaurantSearchHit>, i64)>;
+    async fn find_nearby(&self, owner_id: UserId, search: &NearbySearch) -> Result<Vec<(Restaurant, f64)>>;
+    async fn find_within(&self, owner_id: UserId, bounds: &BoundingBox, limit: i64) -> Result<Vec<Restaurant>>;
+    async fn suggest(&self, owner_id: UserId, term: &str, source_ids: &[String], limit: i64) -> Result<Vec<Restaurant>>;
 }
 
 pub struct PostgresRestaurantRepository {
@@ -24,16 +38,56 @@ impl PostgresRestaurantRepository {
     pub fn new(pool: DbPool) -> Self {
         Self { pool }
     }
+
+    fn map_restaurant(row: &PgRow) -> Restaurant {
+        Restaurant {
+            id: RestaurantId(row.get("id")),
+            owner_id: UserId(row.get("owner_id")),
+            name: row.get("name"),
+            city: row.get("city"),
+            rating: row.get("rating"),
+            visit_rating: row.get("visit_rating"),
+            description: row.get("description"),
+            is_favorite: row.get("is_favorite"),
+            address: row.get("address"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+            source_id: row.get("source_id"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    /// SQL expression a sort field orders by, and the type its cursor key casts back to
+    fn sort_key(sort: RestaurantSort) -> (&'static str, &'static str) {
+        match sort {
+            RestaurantSort::Name => ("lower(name)", "text"),
+            RestaurantSort::City => ("lower(city)", "text"),
+            RestaurantSort::Rating => ("COALESCE(visit_rating, rating, 0)", "numeric"),
+            RestaurantSort::CreatedAt => ("created_at", "timestamptz"),
+            RestaurantSort::UpdatedAt => ("updated_at", "timestamptz"),
+        }
+    }
+}
+
+/// Share of `part` in `whole`, rounded to three decimals (0 when `whole` is empty)
+fn ratio(part: i64, whole: i64) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    (part as f64 / whole as f64 * 1000.0).round() / 1000.0
 }
 
 #[async_trait]
 impl RestaurantRepository for PostgresRestaurantRepository {
     async fn create(&self, restaurant: CreateRestaurant) -> Result<Restaurant> {
+        let mut tx = self.pool.begin().await?;
+
         let row = sqlx::query(
             r#"
-            INSERT INTO restaurants (owner_id, name, city, rating, description, is_favorite, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
-            RETURNING id, owner_id, name, city, rating, description, is_favorite, created_at, updated_at
+            INSERT INTO restaurants (owner_id, name, city, rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW(), NOW())
+            RETURNING id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at
             "#
         )
         .bind(restaurant.owner_id.0)
@@ -42,26 +96,31 @@ impl RestaurantRepository for PostgresRestaurantRepository {
         .bind(restaurant.rating)
         .bind(&restaurant.description)
         .bind(restaurant.is_favorite)
-        .fetch_one(&self.pool)
+        .bind(&restaurant.address)
+        .bind(restaurant.latitude)
+        .bind(restaurant.longitude)
+        .bind(&restaurant.source_id)
+        .fetch_one(&mut *tx)
         .await?;
 
-        Ok(Restaurant {
-            id: RestaurantId(row.get("id")),
-            owner_id: UserId(row.get("owner_id")),
-            name: row.get("name"),
-            city: row.get("city"),
-            rating: row.get("rating"),
-            description: row.get("description"),
-            is_favorite: row.get("is_favorite"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        let tag_ids = resolve_new_row_tags(&mut tx, restaurant.owner_id, &restaurant.tag_ids, &restaurant.cuisines).await?;
+        if !tag_ids.is_empty() {
+            sqlx::query("INSERT INTO restaurant_tags (restaurant_id, tag_id) SELECT $1, UNNEST($2::int[])")
+                .bind(row.get::<i32, _>("id"))
+                .bind(&tag_ids)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Self::map_restaurant(&row))
     }
 
     async fn find_by_id(&self, id: RestaurantId) -> Result<Option<Restaurant>> {
         let row = sqlx::query(
             r#"
-            SELECT id, owner_id, name, city, rating, description, is_favorite, created_at, updated_at
+            SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at
             FROM restaurants 
             WHERE id = $1
             "#
@@ -70,62 +129,95 @@ impl RestaurantRepository for PostgresRestaurantRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        if let Some(row) = row {
-            Ok(Some(Restaurant {
-                id: RestaurantId(row.get("id")),
-                owner_id: UserId(row.get("owner_id")),
-                name: row.get("name"),
-                city: row.get("city"),
-                rating: row.get("rating"),
-                description: row.get("description"),
-                is_favorite: row.get("is_favorite"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(row.as_ref().map(Self::map_restaurant))
     }
 
-    async fn find_by_owner(&self, owner_id: UserId, favorites_only: bool, limit: Option<i64>) -> Result<Vec<Restaurant>> {
-        let mut query = String::from(
+    async fn find_by_owner(&self, owner_id: UserId, favorites_only: bool, tag_ids: &[TagId], page: &PageRequest<RestaurantSort>) -> Result<CursorPage<Restaurant>> {
+        let mut filters = String::from("owner_id = $1");
+        let mut param_count = 2;
+
+        if favorites_only {
+            filters.push_str(" AND is_favorite = true");
+        }
+
+        // Restaurants must carry every requested tag
+        if !tag_ids.is_empty() {
+            filters.push_str(&format!(
+                r#" AND id IN (
+                    SELECT restaurant_id FROM restaurant_tags
+                    WHERE tag_id = ANY(${})
+                    GROUP BY restaurant_id
+                    HAVING COUNT(*) = ${}
+                )"#,
+                param_count,
+                param_count + 1
+            ));
+            param_count += 2;
+        }
+
+        let ids: Vec<i32> = tag_ids.iter().map(|id| id.0).collect();
+
+        let count_query = format!("SELECT COUNT(*) FROM restaurants WHERE {}", filters);
+        let mut sqlx_count = sqlx::query_scalar::<_, i64>(&count_query).bind(owner_id.0);
+        if !tag_ids.is_empty() {
+            sqlx_count = sqlx_count.bind(ids.clone()).bind(tag_ids.len() as i64);
+        }
+        let total = sqlx_count.fetch_one(&self.pool).await?;
+
+        // Keyset pagination: resume strictly after the (sort key, id) of the cursor
+        let (key, key_type) = Self::sort_key(page.sort);
+        let direction = page.direction.sql();
+        let mut conditions = filters;
+        if page.after.is_some() {
+            conditions.push_str(&format!(
+                " AND ({}, id) {} (${}::{}, ${})",
+                key,
+                page.direction.after_operator(),
+                param_count,
+                key_type,
+                param_count + 1
+            ));
+            param_count += 2;
+        }
+
+        let query = format!(
             r#"
-            SELECT id, owner_id, name, city, rating, description, is_favorite, created_at, updated_at
-            FROM restaurants 
-            WHERE owner_id = $1
+            SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at,
+                   ({key})::text AS sort_key
+            FROM restaurants
+            WHERE {conditions}
+            ORDER BY {key} {direction}, id {direction}
+            LIMIT ${param_count}
             "#
         );
 
-        if favorites_only {
-            query.push_str(" AND is_favorite = true");
-        }
+        let mut sqlx_query = sqlx::query(&query).bind(owner_id.0);
 
-        query.push_str(" ORDER BY created_at DESC");
+        if !tag_ids.is_empty() {
+            sqlx_query = sqlx_query.bind(ids).bind(tag_ids.len() as i64);
+        }
 
-        if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
+        if let Some(cursor) = &page.after {
+            sqlx_query = sqlx_query.bind(&cursor.key).bind(cursor.id);
         }
 
-        let rows = sqlx::query(&query)
-            .bind(owner_id.0)
+        // Fetch one extra row to know whether another page follows
+        let mut rows = sqlx_query
+            .bind(page.limit + 1)
             .fetch_all(&self.pool)
             .await?;
 
-        let restaurants = rows.into_iter().map(|row| {
-            Restaurant {
-                id: RestaurantId(row.get("id")),
-                owner_id: UserId(row.get("owner_id")),
-                name: row.get("name"),
-                city: row.get("city"),
-                rating: row.get("rating"),
-                description: row.get("description"),
-                is_favorite: row.get("is_favorite"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            }
-        }).collect();
+        let has_more = rows.len() as i64 > page.limit;
+        rows.truncate(page.limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(row) if has_more => Some(page.cursor_after(row.get("sort_key"), row.get("id")).encode()),
+            _ => None,
+        };
 
-        Ok(restaurants)
+        let items = rows.iter().map(Self::map_restaurant).collect();
+
+        Ok(CursorPage { items, next_cursor, total })
     }
 
     async fn update(&self, restaurant: UpdateRestaurant) -> Result<Option<Restaurant>> {
@@ -133,31 +225,42 @@ impl RestaurantRepository for PostgresRestaurantRepository {
         let mut query_parts = Vec::new();
         let mut param_count = 1;
 
-        if let Some(name) = &restaurant.name {
+        if restaurant.name.is_some() {
             query_parts.push(format!("name = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(city) = &restaurant.city {
+        if restaurant.city.is_some() {
             query_parts.push(format!("city = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(rating) = &restaurant.rating {
+        if restaurant.rating.is_some() {
             query_parts.push(format!("rating = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(description) = &restaurant.description {
+        if restaurant.description.is_some() {
             query_parts.push(format!("description = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(is_favorite) = restaurant.is_favorite {
+        if restaurant.is_favorite.is_some() {
             query_parts.push(format!("is_favorite = ${}", param_count));
             param_count += 1;
         }
 
+        if restaurant.address.is_some() {
+            query_parts.push(format!("address = ${}", param_count));
+            param_count += 1;
+        }
+
+        if restaurant.coordinates.is_some() {
+            query_parts.push(format!("latitude = ${}", param_count));
+            query_parts.push(format!("longitude = ${}", param_count + 1));
+            param_count += 2;
+        }
+
         if query_parts.is_empty() {
             // No updates requested, just return the current restaurant
             return self.find_by_id(restaurant.id).await;
@@ -170,7 +273,7 @@ impl RestaurantRepository for PostgresRestaurantRepository {
             UPDATE restaurants 
             SET {}
             WHERE id = ${} AND owner_id = ${}
-            RETURNING id, owner_id, name, city, rating, description, is_favorite, created_at, updated_at
+            RETURNING id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at
             "#,
             query_parts.join(", "),
             param_count,
@@ -194,6 +297,14 @@ impl RestaurantRepository for PostgresRestaurantRepository {
         if let Some(is_favorite) = restaurant.is_favorite {
             sqlx_query = sqlx_query.bind(is_favorite);
         }
+        if let Some(address) = &restaurant.address {
+            sqlx_query = sqlx_query.bind(address);
+        }
+        if let Some(coordinates) = restaurant.coordinates {
+            sqlx_query = sqlx_query
+                .bind(coordinates.map(|(lat, _)| lat))
+                .bind(coordinates.map(|(_, lon)| lon));
+        }
 
         sqlx_query = sqlx_query.bind(restaurant.id.0).bind(restaurant.owner_id.0);
 
@@ -201,21 +312,7 @@ impl RestaurantRepository for PostgresRestaurantRepository {
             .fetch_optional(&self.pool)
             .await?;
 
-        if let Some(row) = row {
-            Ok(Some(Restaurant {
-                id: RestaurantId(row.get("id")),
-                owner_id: UserId(row.get("owner_id")),
-                name: row.get("name"),
-                city: row.get("city"),
-                rating: row.get("rating"),
-                description: row.get("description"),
-                is_favorite: row.get("is_favorite"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(row.as_ref().map(Self::map_restaurant))
     }
 
     async fn delete(&self, id: RestaurantId, owner_id: UserId) -> Result<bool> {
@@ -234,7 +331,11 @@ impl RestaurantRepository for PostgresRestaurantRepository {
             SELECT 
                 COUNT(*) as total_restaurants,
                 COUNT(*) FILTER (WHERE is_favorite = true) as total_favorites,
-                COALESCE(ROUND(AVG(rating::numeric), 1), 0) as average_rating
+                COUNT(*) FILTER (WHERE visit_rating IS NULL AND rating IS NULL) as unrated_restaurants,
+                COUNT(*) FILTER (WHERE promoted_from_wishlist = true) as promoted,
+                (SELECT COUNT(*) FROM visits WHERE owner_id = $1) as total_visits,
+                (SELECT COUNT(*) FROM wishlist_items WHERE owner_id = $1) as pending,
+                ROUND(AVG(COALESCE(visit_rating, rating)), 1)::float8 as average_rating
             FROM restaurants 
             WHERE owner_id = $1
             "#
@@ -243,12 +344,239 @@ impl RestaurantRepository for PostgresRestaurantRepository {
         .fetch_one(&self.pool)
         .await?;
 
-        let average_rating: BigDecimal = row.get("average_rating");
-        
+        // Every rating from 1 to 5, including those nobody was given. The
+        // mean of the visits counts as its nearest whole rating.
+        let rating_histogram = sqlx::query(
+            r#"
+            SELECT r.rating::smallint as rating, COUNT(restaurants.id) as count
+            FROM generate_series(1, 5) AS r(rating)
+            LEFT JOIN restaurants
+                ON ROUND(COALESCE(restaurants.visit_rating, restaurants.rating)) = r.rating
+                AND restaurants.owner_id = $1
+            GROUP BY r.rating
+            ORDER BY r.rating
+            "#
+        )
+        .bind(owner_id.0)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| RatingBucket {
+            rating: row.get("rating"),
+            count: row.get("count"),
+        })
+        .collect();
+
+        // Cities are grouped regardless of case and surrounding spaces
+        let restaurants_per_city: Vec<CityCount> = sqlx::query(
+            r#"
+            SELECT MIN(trim(city)) as city, COUNT(*) as count
+            FROM restaurants
+            WHERE owner_id = $1
+            GROUP BY lower(trim(city))
+            ORDER BY count DESC, lower(trim(city))
+            "#
+        )
+        .bind(owner_id.0)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| CityCount {
+            city: row.get("city"),
+            count: row.get("count"),
+        })
+        .collect();
+
+        // Months without any addition are reported with a zero count
+        let added_per_month = sqlx::query(
+            r#"
+            SELECT to_char(m.month, 'YYYY-MM') as month, COUNT(restaurants.id) as count
+            FROM generate_series(
+                date_trunc('month', NOW()) - make_interval(months => $2 - 1),
+                date_trunc('month', NOW()),
+                interval '1 month'
+            ) AS m(month)
+            LEFT JOIN restaurants
+                ON restaurants.owner_id = $1
+                AND date_trunc('month', restaurants.created_at) = m.month
+            GROUP BY m.month
+            ORDER BY m.month
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(STATS_MONTHS)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| MonthCount {
+            month: row.get("month"),
+            count: row.get("count"),
+        })
+        .collect();
+
+        let total_restaurants: i64 = row.get("total_restaurants");
+        let total_favorites: i64 = row.get("total_favorites");
+        let promoted: i64 = row.get("promoted");
+        let pending: i64 = row.get("pending");
+
         Ok(RestaurantStatsResponse {
-            total_restaurants: row.get::<i64, _>("total_restaurants"),
-            total_favorites: row.get::<i64, _>("total_favorites"),
-            average_rating: average_rating.to_string(),
+            total_restaurants,
+            total_favorites,
+            favorite_ratio: ratio(total_favorites, total_restaurants),
+            total_visits: row.get("total_visits"),
+            average_rating: row.get("average_rating"),
+            rating_histogram,
+            unrated_restaurants: row.get("unrated_restaurants"),
+            top_cities: restaurants_per_city.iter().take(TOP_CITIES).cloned().collect(),
+            restaurants_per_city,
+            added_per_month,
+            wishlist_conversion: WishlistConversionStats {
+                promoted,
+                pending,
+                conversion_rate: ratio(promoted, promoted + pending),
+            },
         })
     }
-}
\ No newline at end of file
+
+    async fn search(&self, search: &RestaurantSearch) -> Result<(Vec<RestaurantSearchHit>, i64)> {
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM restaurants
+            WHERE owner_id = $1
+              AND search_vector @@ websearch_to_tsquery('french_unaccent', $2)
+            "#
+        )
+        .bind(search.owner_id.0)
+        .bind(&search.query)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if total == 0 {
+            return Ok((Vec::new(), 0));
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at,
+                   ts_rank_cd(search_vector, query) AS rank,
+                   ts_headline('french_unaccent', name, query, $5 || ', HighlightAll=true') AS name_highlight,
+                   ts_headline('french_unaccent', city, query, $5 || ', HighlightAll=true') AS city_highlight,
+                   CASE WHEN description IS NULL THEN NULL
+                        ELSE ts_headline('french_unaccent', description, query, $5 || ', MaxWords=35, MinWords=15')
+                   END AS description_highlight
+            FROM restaurants, websearch_to_tsquery('french_unaccent', $2) AS query
+            WHERE owner_id = $1
+              AND search_vector @@ query
+            ORDER BY rank DESC, updated_at DESC, id DESC
+            LIMIT $3 OFFSET $4
+            "#
+        )
+        .bind(search.owner_id.0)
+        .bind(&search.query)
+        .bind(search.page_size)
+        .bind(search.offset())
+        .bind(format!("StartSel={}, StopSel={}", HIGHLIGHT_START, HIGHLIGHT_STOP))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let hits = rows.into_iter().map(|row| {
+            RestaurantSearchHit {
+                restaurant: Self::map_restaurant(&row),
+                rank: row.get("rank"),
+                name_highlight: row.get("name_highlight"),
+                city_highlight: row.get("city_highlight"),
+                description_highlight: row.get("description_highlight"),
+            }
+        }).collect();
+
+        Ok((hits, total))
+    }
+
+    async fn find_nearby(&self, owner_id: UserId, search: &NearbySearch) -> Result<Vec<(Restaurant, f64)>> {
+        // The bounding box lets the location index discard far rows before
+        // computing exact distances
+        let query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at,
+                       {} AS distance_km
+                FROM restaurants
+                WHERE owner_id = $1 AND {}
+            ) nearby
+            WHERE distance_km <= $8
+            ORDER BY distance_km, id
+            LIMIT $9
+            "#,
+            distance_sql(2, 3),
+            within_sql(&search.bounds, 4),
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(owner_id.0)
+            .bind(search.latitude)
+            .bind(search.longitude)
+            .bind(search.bounds.min_lat)
+            .bind(search.bounds.max_lat)
+            .bind(search.bounds.min_lon)
+            .bind(search.bounds.max_lon)
+            .bind(search.radius_km)
+            .bind(search.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| (Self::map_restaurant(row), row.get("distance_km"))).collect())
+    }
+
+    async fn find_within(&self, owner_id: UserId, bounds: &BoundingBox, limit: i64) -> Result<Vec<Restaurant>> {
+        let query = format!(
+            r#"
+            SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at
+            FROM restaurants
+            WHERE owner_id = $1 AND {}
+            ORDER BY is_favorite DESC, COALESCE(visit_rating, rating, 0) DESC, id
+            LIMIT $6
+            "#,
+            within_sql(bounds, 2),
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(owner_id.0)
+            .bind(bounds.min_lat)
+            .bind(bounds.max_lat)
+            .bind(bounds.min_lon)
+            .bind(bounds.max_lon)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(Self::map_restaurant).collect())
+    }
+
+    async fn suggest(&self, owner_id: UserId, term: &str, source_ids: &[String], limit: i64) -> Result<Vec<Restaurant>> {
+        // Entries linked to one of the given upstream records, then an
+        // accent-insensitive substring match on name or city, names
+        // starting with the term first
+        let rows = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, city, rating, visit_rating::float8 AS visit_rating, description, is_favorite, address, latitude, longitude, source_id, created_at, updated_at
+            FROM restaurants
+            WHERE owner_id = $1
+              AND (lower(unaccent(name)) LIKE '%' || lower(unaccent($2)) || '%'
+                OR lower(unaccent(city)) LIKE '%' || lower(unaccent($2)) || '%'
+                OR source_id = ANY($4))
+            ORDER BY source_id = ANY($4) DESC NULLS LAST,
+                     lower(unaccent(name)) LIKE lower(unaccent($2)) || '%' DESC, updated_at DESC, id
+            LIMIT $3
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(escape_like(term.trim()))
+        .bind(limit)
+        .bind(source_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::map_restaurant).collect())
+    }
+}
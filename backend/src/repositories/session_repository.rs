@@ -34,8 +34,8 @@ impl SessionRepository for PostgresSessionRepository {
         let row = sqlx::query(
             r#"
             INSERT INTO sessions (id, user_id, ip_address, user_agent, created_at, expires_at, is_active)
-            VALUES ($1, $2, $3, $4, NOW(), $5, true)
-            RETURNING id, user_id, ip_address, user_agent, created_at, expires_at, is_active
+            VALUES ($1, $2, $3::inet, $4, NOW(), $5, true)
+            RETURNING id, user_id, host(ip_address) AS ip_address, user_agent, created_at, expires_at, is_active
             "#
         )
         .bind(id)
@@ -60,7 +60,7 @@ impl SessionRepository for PostgresSessionRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Session>> {
         let row = sqlx::query(
             r#"
-            SELECT id, user_id, ip_address, user_agent, created_at, expires_at, is_active
+            SELECT id, user_id, host(ip_address) AS ip_address, user_agent, created_at, expires_at, is_active
             FROM sessions 
             WHERE id = $1 AND is_active = true AND expires_at > NOW()
             "#
@@ -87,7 +87,7 @@ impl SessionRepository for PostgresSessionRepository {
     async fn find_active_by_user(&self, user_id: UserId) -> Result<Vec<Session>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, user_id, ip_address, user_agent, created_at, expires_at, is_active
+            SELECT id, user_id, host(ip_address) AS ip_address, user_agent, created_at, expires_at, is_active
             FROM sessions 
             WHERE user_id = $1 AND is_active = true AND expires_at > NOW()
             ORDER BY created_at DESC
@@ -122,10 +122,15 @@ impl SessionRepository for PostgresSessionRepository {
     }
 
     async fn deactivate_all_for_user(&self, user_id: UserId) -> Result<u64> {
-        let result = sqlx::query("UPDATE sessions SET is_active = false WHERE user_id = $1")
-            .bind(user_id.0)
-            .execute(&self.pool)
-            .await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE sessions SET is_active = false
+            WHERE user_id = $1 AND is_active = true AND expires_at > NOW()
+            "#
+        )
+        .bind(user_id.0)
+        .execute(&self.pool)
+        .await?;
 
         Ok(result.rows_affected())
     }
@@ -0,0 +1,299 @@
+use crate::domain::tag::{Tag, TagId, TagKind, CreateTag, UpdateTag};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::wishlist::WishlistId;
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{Postgres, Row, Transaction};
+
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn create(&self, tag: CreateTag) -> Result<Tag>;
+    async fn find_by_id(&self, id: TagId) -> Result<Option<Tag>>;
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<Tag>>;
+    async fn find_by_name(&self, owner_id: UserId, name: &str) -> Result<Option<Tag>>;
+    async fn find_by_ids(&self, owner_id: UserId, ids: &[TagId]) -> Result<Vec<Tag>>;
+    async fn update(&self, tag: UpdateTag) -> Result<Option<Tag>>;
+    async fn delete(&self, id: TagId, owner_id: UserId) -> Result<bool>;
+    async fn find_by_restaurant(&self, restaurant_id: RestaurantId) -> Result<Vec<Tag>>;
+    async fn set_restaurant_tags(&self, restaurant_id: RestaurantId, owner_id: UserId, tag_ids: &[TagId]) -> Result<bool>;
+    async fn find_by_wishlist_item(&self, item_id: WishlistId) -> Result<Vec<Tag>>;
+    async fn set_wishlist_item_tags(&self, item_id: WishlistId, owner_id: UserId, tag_ids: &[TagId]) -> Result<bool>;
+}
+
+/// Tags to attach to a new restaurant or wishlist item, inside the
+/// transaction creating it: the given tags the user owns, plus a cuisine tag
+/// found or created for each cuisine name
+pub async fn resolve_new_row_tags(
+    tx: &mut Transaction<'_, Postgres>,
+    owner_id: UserId,
+    tag_ids: &[TagId],
+    cuisines: &[String],
+) -> Result<Vec<i32>> {
+    let mut resolved: Vec<i32> = sqlx::query_scalar("SELECT id FROM tags WHERE owner_id = $1 AND id = ANY($2)")
+        .bind(owner_id.0)
+        .bind(PostgresTagRepository::raw_ids(tag_ids))
+        .fetch_all(&mut **tx)
+        .await?;
+
+    for name in cuisines {
+        // The no-op update makes RETURNING yield the existing row on conflict
+        let id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO tags (owner_id, name, kind, created_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (owner_id, (lower(name))) DO UPDATE SET name = tags.name
+            RETURNING id
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(name)
+        .bind(TagKind::Cuisine)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if !resolved.contains(&id) {
+            resolved.push(id);
+        }
+    }
+
+    Ok(resolved)
+}
+
+pub struct PostgresTagRepository {
+    pool: DbPool,
+}
+
+impl PostgresTagRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> Tag {
+        Tag {
+            id: TagId(row.get("id")),
+            owner_id: UserId(row.get("owner_id")),
+            name: row.get("name"),
+            kind: row.get("kind"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    fn raw_ids(tag_ids: &[TagId]) -> Vec<i32> {
+        tag_ids.iter().map(|id| id.0).collect()
+    }
+}
+
+#[async_trait]
+impl TagRepository for PostgresTagRepository {
+    async fn create(&self, tag: CreateTag) -> Result<Tag> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO tags (owner_id, name, kind, created_at)
+            VALUES ($1, $2, $3, NOW())
+            RETURNING id, owner_id, name, kind, created_at
+            "#
+        )
+        .bind(tag.owner_id.0)
+        .bind(&tag.name)
+        .bind(tag.kind)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::map_row(row))
+    }
+
+    async fn find_by_id(&self, id: TagId) -> Result<Option<Tag>> {
+        let row = sqlx::query("SELECT id, owner_id, name, kind, created_at FROM tags WHERE id = $1")
+            .bind(id.0)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<Tag>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, kind, created_at
+            FROM tags
+            WHERE owner_id = $1
+            ORDER BY kind, lower(name)
+            "#
+        )
+        .bind(owner_id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn find_by_name(&self, owner_id: UserId, name: &str) -> Result<Option<Tag>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, kind, created_at
+            FROM tags
+            WHERE owner_id = $1 AND lower(name) = lower($2)
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn find_by_ids(&self, owner_id: UserId, ids: &[TagId]) -> Result<Vec<Tag>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, kind, created_at
+            FROM tags
+            WHERE owner_id = $1 AND id = ANY($2)
+            ORDER BY kind, lower(name)
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(Self::raw_ids(ids))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn update(&self, tag: UpdateTag) -> Result<Option<Tag>> {
+        let row = sqlx::query(
+            r#"
+            UPDATE tags
+            SET name = COALESCE($1, name), kind = COALESCE($2, kind)
+            WHERE id = $3 AND owner_id = $4
+            RETURNING id, owner_id, name, kind, created_at
+            "#
+        )
+        .bind(&tag.name)
+        .bind(tag.kind)
+        .bind(tag.id.0)
+        .bind(tag.owner_id.0)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn delete(&self, id: TagId, owner_id: UserId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = $1 AND owner_id = $2")
+            .bind(id.0)
+            .bind(owner_id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_by_restaurant(&self, restaurant_id: RestaurantId) -> Result<Vec<Tag>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.id, t.owner_id, t.name, t.kind, t.created_at
+            FROM tags t
+            JOIN restaurant_tags rt ON rt.tag_id = t.id
+            WHERE rt.restaurant_id = $1
+            ORDER BY t.kind, lower(t.name)
+            "#
+        )
+        .bind(restaurant_id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn set_restaurant_tags(&self, restaurant_id: RestaurantId, owner_id: UserId, tag_ids: &[TagId]) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let restaurant = sqlx::query("SELECT id FROM restaurants WHERE id = $1 AND owner_id = $2 FOR UPDATE")
+            .bind(restaurant_id.0)
+            .bind(owner_id.0)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if restaurant.is_none() {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM restaurant_tags WHERE restaurant_id = $1")
+            .bind(restaurant_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO restaurant_tags (restaurant_id, tag_id)
+            SELECT $1, id FROM tags WHERE owner_id = $2 AND id = ANY($3)
+            "#
+        )
+        .bind(restaurant_id.0)
+        .bind(owner_id.0)
+        .bind(Self::raw_ids(tag_ids))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn find_by_wishlist_item(&self, item_id: WishlistId) -> Result<Vec<Tag>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.id, t.owner_id, t.name, t.kind, t.created_at
+            FROM tags t
+            JOIN wishlist_item_tags wt ON wt.tag_id = t.id
+            WHERE wt.wishlist_item_id = $1
+            ORDER BY t.kind, lower(t.name)
+            "#
+        )
+        .bind(item_id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn set_wishlist_item_tags(&self, item_id: WishlistId, owner_id: UserId, tag_ids: &[TagId]) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let item = sqlx::query("SELECT id FROM wishlist_items WHERE id = $1 AND owner_id = $2 FOR UPDATE")
+            .bind(item_id.0)
+            .bind(owner_id.0)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if item.is_none() {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM wishlist_item_tags WHERE wishlist_item_id = $1")
+            .bind(item_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wishlist_item_tags (wishlist_item_id, tag_id)
+            SELECT $1, id FROM tags WHERE owner_id = $2 AND id = ANY($3)
+            "#
+        )
+        .bind(item_id.0)
+        .bind(owner_id.0)
+        .bind(Self::raw_ids(tag_ids))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+}
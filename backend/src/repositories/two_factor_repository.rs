@@ -0,0 +1,178 @@
+use crate::domain::two_factor::TwoFactor;
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::{Postgres, Row, Transaction};
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    async fn find(&self, user_id: UserId) -> Result<Option<TwoFactor>>;
+    /// Store the secret of a new setup, replacing a setup never confirmed.
+    /// Returns false when two-factor authentication is already enabled.
+    async fn start_setup(&self, user_id: UserId, secret: &str) -> Result<bool>;
+    /// Enable a pending setup, its first code having been used at `step`
+    async fn enable(&self, user_id: UserId, step: i64, code_hashes: &[String]) -> Result<bool>;
+    /// Record the step of an accepted code. Returns false when a code of this
+    /// step or a later one was already used.
+    async fn use_step(&self, user_id: UserId, step: i64) -> Result<bool>;
+    async fn replace_recovery_codes(&self, user_id: UserId, code_hashes: &[String]) -> Result<()>;
+    /// Delete a recovery code, returning whether it existed
+    async fn use_recovery_code(&self, user_id: UserId, code_hash: &str) -> Result<bool>;
+    async fn count_recovery_codes(&self, user_id: UserId) -> Result<i64>;
+    /// Remove the secret and recovery codes
+    async fn disable(&self, user_id: UserId) -> Result<bool>;
+}
+
+pub struct PostgresTwoFactorRepository {
+    pool: DbPool,
+}
+
+impl PostgresTwoFactorRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    async fn insert_recovery_codes(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: UserId,
+        code_hashes: &[String],
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+            .bind(user_id.0)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO recovery_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::text[]) AS code_hash
+            "#
+        )
+        .bind(user_id.0)
+        .bind(code_hashes)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for PostgresTwoFactorRepository {
+    async fn find(&self, user_id: UserId) -> Result<Option<TwoFactor>> {
+        let row = sqlx::query(
+            "SELECT user_id, secret, enabled_at, last_used_step FROM user_totp WHERE user_id = $1"
+        )
+        .bind(user_id.0)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| TwoFactor {
+            user_id: UserId(row.get("user_id")),
+            secret: row.get("secret"),
+            enabled_at: row.get("enabled_at"),
+            last_used_step: row.get("last_used_step"),
+        }))
+    }
+
+    async fn start_setup(&self, user_id: UserId, secret: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_totp (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, last_used_step = NULL, created_at = NOW()
+            WHERE user_totp.enabled_at IS NULL
+            "#
+        )
+        .bind(user_id.0)
+        .bind(secret)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn enable(&self, user_id: UserId, step: i64, code_hashes: &[String]) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE user_totp SET enabled_at = NOW(), last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NULL
+            "#
+        )
+        .bind(user_id.0)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        Self::insert_recovery_codes(&mut tx, user_id, code_hashes).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn use_step(&self, user_id: UserId, step: i64) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_totp SET last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < $2)
+            "#
+        )
+        .bind(user_id.0)
+        .bind(step)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn replace_recovery_codes(&self, user_id: UserId, code_hashes: &[String]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_recovery_codes(&mut tx, user_id, code_hashes).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn use_recovery_code(&self, user_id: UserId, code_hash: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1 AND code_hash = $2")
+            .bind(user_id.0)
+            .bind(code_hash)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_recovery_codes(&self, user_id: UserId) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM recovery_codes WHERE user_id = $1")
+            .bind(user_id.0)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    async fn disable(&self, user_id: UserId) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM recovery_codes WHERE user_id = $1")
+            .bind(user_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM user_totp WHERE user_id = $1")
+            .bind(user_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,4 +1,6 @@
 use crate::domain::user::{User, UserId, CreateUser};
+use crate::domain::social::{Chronological, Follow, UserProfile};
+use crate::domain::pagination::{CursorPage, PageRequest};
 use crate::error::Result;
 use crate::database::DbPool;
 use async_trait::async_trait;
@@ -14,6 +16,21 @@ pub trait UserRepository: Send + Sync {
     async fn increment_failed_attempts(&self, id: UserId) -> Result<()>;
     async fn reset_failed_attempts(&self, id: UserId) -> Result<()>;
     async fn lock_account(&self, id: UserId, until: chrono::DateTime<chrono::Utc>) -> Result<()>;
+    async fn unlock_expired_accounts(&self) -> Result<u64>;
+    async fn mark_email_verified(&self, id: UserId) -> Result<()>;
+    async fn update_password(&self, id: UserId, password_hash: &str) -> Result<()>;
+    async fn update_username(&self, id: UserId, username: &str) -> Result<()>;
+    /// Change the email address, which then needs verifying again
+    async fn update_email(&self, id: UserId, email: &str) -> Result<()>;
+    /// Delete a user, their data going with them through `ON DELETE CASCADE`
+    async fn delete(&self, id: UserId) -> Result<bool>;
+    async fn set_private(&self, id: UserId, is_private: bool) -> Result<()>;
+    async fn find_profile(&self, username: &str) -> Result<Option<UserProfile>>;
+    async fn follow(&self, follower_id: UserId, followee_id: UserId) -> Result<bool>;
+    async fn unfollow(&self, follower_id: UserId, followee_id: UserId) -> Result<bool>;
+    async fn is_following(&self, follower_id: UserId, followee_id: UserId) -> Result<bool>;
+    async fn find_followers(&self, id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<Follow>>;
+    async fn find_following(&self, id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<Follow>>;
 }
 
 pub struct PostgresUserRepository {
@@ -24,6 +41,78 @@ impl PostgresUserRepository {
     pub fn new(pool: DbPool) -> Self {
         Self { pool }
     }
+
+    /// Page through one side of the follows table. `own_column` holds the
+    /// listed user, `other_column` the users returned.
+    async fn find_follows(
+        &self,
+        id: UserId,
+        own_column: &str,
+        other_column: &str,
+        page: &PageRequest<Chronological>,
+    ) -> Result<CursorPage<Follow>> {
+        let filters = format!("f.{own_column} = $1 AND u.is_active = true");
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM follows f JOIN users u ON u.id = f.{other_column} WHERE {filters}"
+        ))
+        .bind(id.0)
+        .fetch_one(&self.pool)
+        .await?;
+
+        // Keyset pagination on (followed at, user id), newest first
+        let mut conditions = filters;
+        let mut param_count = 2;
+        if page.after.is_some() {
+            conditions.push_str(&format!(
+                " AND (f.created_at, u.id) {} (${}::timestamptz, ${})",
+                page.direction.after_operator(),
+                param_count,
+                param_count + 1
+            ));
+            param_count += 2;
+        }
+        let direction = page.direction.sql();
+
+        let query = format!(
+            r#"
+            SELECT u.id, u.username, f.created_at, f.created_at::text AS sort_key
+            FROM follows f
+            JOIN users u ON u.id = f.{other_column}
+            WHERE {conditions}
+            ORDER BY f.created_at {direction}, u.id {direction}
+            LIMIT ${param_count}
+            "#
+        );
+
+        let mut sqlx_query = sqlx::query(&query).bind(id.0);
+        if let Some(cursor) = &page.after {
+            sqlx_query = sqlx_query.bind(&cursor.key).bind(cursor.id);
+        }
+
+        let mut rows = sqlx_query
+            .bind(page.limit + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_more = rows.len() as i64 > page.limit;
+        rows.truncate(page.limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(row) if has_more => Some(page.cursor_after(row.get("sort_key"), row.get("id")).encode()),
+            _ => None,
+        };
+
+        let items = rows.into_iter().map(|row| {
+            Follow {
+                user_id: UserId(row.get("id")),
+                username: row.get("username"),
+                followed_at: row.get("created_at"),
+            }
+        }).collect();
+
+        Ok(CursorPage { items, next_cursor, total })
+    }
 }
 
 #[async_trait]
@@ -34,7 +123,8 @@ impl UserRepository for PostgresUserRepository {
             INSERT INTO users (username, email, password_hash, created_at, updated_at, is_active, failed_login_attempts)
             VALUES ($1, $2, $3, NOW(), NOW(), true, 0)
             RETURNING id, username, email, password_hash, created_at, updated_at, is_active, 
-                     failed_login_attempts, last_login, account_locked_until
+                     failed_login_attempts, last_login, account_locked_until, is_private,
+                   email_verified_at
             "#
         )
         .bind(&user.username)
@@ -54,6 +144,8 @@ impl UserRepository for PostgresUserRepository {
             failed_login_attempts: row.get("failed_login_attempts"),
             last_login: row.get("last_login"),
             account_locked_until: row.get("account_locked_until"),
+            is_private: row.get("is_private"),
+            email_verified_at: row.get("email_verified_at"),
         })
     }
 
@@ -61,7 +153,8 @@ impl UserRepository for PostgresUserRepository {
         let row = sqlx::query(
             r#"
             SELECT id, username, email, password_hash, created_at, updated_at, 
-                   is_active, failed_login_attempts, last_login, account_locked_until
+                   is_active, failed_login_attempts, last_login, account_locked_until, is_private,
+                   email_verified_at
             FROM users 
             WHERE id = $1
             "#
@@ -82,6 +175,8 @@ impl UserRepository for PostgresUserRepository {
                 failed_login_attempts: row.get("failed_login_attempts"),
                 last_login: row.get("last_login"),
                 account_locked_until: row.get("account_locked_until"),
+                is_private: row.get("is_private"),
+                email_verified_at: row.get("email_verified_at"),
             }))
         } else {
             Ok(None)
@@ -92,7 +187,8 @@ impl UserRepository for PostgresUserRepository {
         let row = sqlx::query(
             r#"
             SELECT id, username, email, password_hash, created_at, updated_at, 
-                   is_active, failed_login_attempts, last_login, account_locked_until
+                   is_active, failed_login_attempts, last_login, account_locked_until, is_private,
+                   email_verified_at
             FROM users 
             WHERE username = $1
             "#
@@ -113,6 +209,8 @@ impl UserRepository for PostgresUserRepository {
                 failed_login_attempts: row.get("failed_login_attempts"),
                 last_login: row.get("last_login"),
                 account_locked_until: row.get("account_locked_until"),
+                is_private: row.get("is_private"),
+                email_verified_at: row.get("email_verified_at"),
             }))
         } else {
             Ok(None)
@@ -123,7 +221,8 @@ impl UserRepository for PostgresUserRepository {
         let row = sqlx::query(
             r#"
             SELECT id, username, email, password_hash, created_at, updated_at, 
-                   is_active, failed_login_attempts, last_login, account_locked_until
+                   is_active, failed_login_attempts, last_login, account_locked_until, is_private,
+                   email_verified_at
             FROM users 
             WHERE email = $1
             "#
@@ -144,6 +243,8 @@ impl UserRepository for PostgresUserRepository {
                 failed_login_attempts: row.get("failed_login_attempts"),
                 last_login: row.get("last_login"),
                 account_locked_until: row.get("account_locked_until"),
+                is_private: row.get("is_private"),
+                email_verified_at: row.get("email_verified_at"),
             }))
         } else {
             Ok(None)
@@ -186,4 +287,159 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(())
     }
+
+    async fn unlock_expired_accounts(&self) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET account_locked_until = NULL, failed_login_attempts = 0, updated_at = NOW()
+            WHERE account_locked_until <= NOW()
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn mark_email_verified(&self, id: UserId) -> Result<()> {
+        sqlx::query(
+            "UPDATE users SET email_verified_at = COALESCE(email_verified_at, NOW()), updated_at = NOW() WHERE id = $1"
+        )
+        .bind(id.0)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_password(&self, id: UserId, password_hash: &str) -> Result<()> {
+        // A new password also lifts a lock caused by failed logins
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = $1, failed_login_attempts = 0, account_locked_until = NULL, updated_at = NOW()
+            WHERE id = $2
+            "#
+        )
+        .bind(password_hash)
+        .bind(id.0)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_username(&self, id: UserId, username: &str) -> Result<()> {
+        sqlx::query("UPDATE users SET username = $1, updated_at = NOW() WHERE id = $2")
+            .bind(username)
+            .bind(id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_email(&self, id: UserId, email: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE users SET email = $1, email_verified_at = NULL, updated_at = NOW() WHERE id = $2"
+        )
+        .bind(email)
+        .bind(id.0)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: UserId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_private(&self, id: UserId, is_private: bool) -> Result<()> {
+        sqlx::query("UPDATE users SET is_private = $1, updated_at = NOW() WHERE id = $2")
+            .bind(is_private)
+            .bind(id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_profile(&self, username: &str) -> Result<Option<UserProfile>> {
+        let row = sqlx::query(
+            r#"
+            SELECT u.id, u.username, u.is_private, u.created_at,
+                   (SELECT COUNT(*) FROM follows f JOIN users o ON o.id = f.follower_id
+                    WHERE f.followee_id = u.id AND o.is_active = true) AS follower_count,
+                   (SELECT COUNT(*) FROM follows f JOIN users o ON o.id = f.followee_id
+                    WHERE f.follower_id = u.id AND o.is_active = true) AS following_count
+            FROM users u
+            WHERE u.username = $1 AND u.is_active = true
+            "#
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| UserProfile {
+            user_id: UserId(row.get("id")),
+            username: row.get("username"),
+            is_private: row.get("is_private"),
+            follower_count: row.get("follower_count"),
+            following_count: row.get("following_count"),
+            created_at: row.get("created_at"),
+        }))
+    }
+
+    async fn follow(&self, follower_id: UserId, followee_id: UserId) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO follows (follower_id, followee_id, created_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (follower_id, followee_id) DO NOTHING
+            "#
+        )
+        .bind(follower_id.0)
+        .bind(followee_id.0)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn unfollow(&self, follower_id: UserId, followee_id: UserId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2")
+            .bind(follower_id.0)
+            .bind(followee_id.0)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn is_following(&self, follower_id: UserId, followee_id: UserId) -> Result<bool> {
+        let following = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM follows WHERE follower_id = $1 AND followee_id = $2)"
+        )
+        .bind(follower_id.0)
+        .bind(followee_id.0)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(following)
+    }
+
+    async fn find_followers(&self, id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<Follow>> {
+        self.find_follows(id, "followee_id", "follower_id", page).await
+    }
+
+    async fn find_following(&self, id: UserId, page: &PageRequest<Chronological>) -> Result<CursorPage<Follow>> {
+        self.find_follows(id, "follower_id", "followee_id", page).await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,95 @@
+use crate::domain::user::UserId;
+use crate::domain::user_token::{CreateUserToken, UserTokenPurpose};
+use crate::error::Result;
+use crate::database::DbPool;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[async_trait]
+pub trait UserTokenRepository: Send + Sync {
+    /// Store a token, dropping the user's earlier tokens for the same purpose
+    async fn replace(&self, token: CreateUserToken) -> Result<()>;
+    /// User of a valid token, leaving the token usable
+    async fn find_valid(&self, token_hash: &str, purpose: UserTokenPurpose) -> Result<Option<UserId>>;
+    /// Use up a valid token, returning its user
+    async fn consume(&self, token_hash: &str, purpose: UserTokenPurpose) -> Result<Option<UserId>>;
+    async fn cleanup_expired(&self) -> Result<u64>;
+}
+
+pub struct PostgresUserTokenRepository {
+    pool: DbPool,
+}
+
+impl PostgresUserTokenRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserTokenRepository for PostgresUserTokenRepository {
+    async fn replace(&self, token: CreateUserToken) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM user_tokens WHERE user_id = $1 AND purpose = $2")
+            .bind(token.user_id.0)
+            .bind(token.purpose)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_tokens (user_id, purpose, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#
+        )
+        .bind(token.user_id.0)
+        .bind(token.purpose)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_valid(&self, token_hash: &str, purpose: UserTokenPurpose) -> Result<Option<UserId>> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_id FROM user_tokens
+            WHERE token_hash = $1 AND purpose = $2 AND expires_at > NOW()
+            "#
+        )
+        .bind(token_hash)
+        .bind(purpose)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| UserId(row.get("user_id"))))
+    }
+
+    async fn consume(&self, token_hash: &str, purpose: UserTokenPurpose) -> Result<Option<UserId>> {
+        let row = sqlx::query(
+            r#"
+            DELETE FROM user_tokens
+            WHERE token_hash = $1 AND purpose = $2 AND expires_at > NOW()
+            RETURNING user_id
+            "#
+        )
+        .bind(token_hash)
+        .bind(purpose)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| UserId(row.get("user_id"))))
+    }
+
+    async fn cleanup_expired(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM user_tokens WHERE expires_at < NOW()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
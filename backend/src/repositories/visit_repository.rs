@@ -0,0 +1,279 @@
+use crate::domain::visit::{Visit, VisitId, CreateVisit, UpdateVisit};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::social::{ActivityKind, RecordActivity};
+use crate::domain::user::UserId;
+use crate::error::Result;
+use crate::database::DbPool;
+use crate::repositories::activity_repository::insert_activity;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{Postgres, Row, Transaction};
+
+#[async_trait]
+pub trait VisitRepository: Send + Sync {
+    async fn create(&self, visit: CreateVisit) -> Result<Option<Visit>>;
+    async fn find_by_id(&self, id: VisitId, owner_id: UserId) -> Result<Option<Visit>>;
+    async fn find_by_restaurant(&self, restaurant_id: RestaurantId, owner_id: UserId) -> Result<Vec<Visit>>;
+    async fn update(&self, visit: UpdateVisit) -> Result<Option<Visit>>;
+    async fn delete(&self, id: VisitId, restaurant_id: RestaurantId, owner_id: UserId) -> Result<bool>;
+}
+
+pub struct PostgresVisitRepository {
+    pool: DbPool,
+}
+
+impl PostgresVisitRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> Visit {
+        Visit {
+            id: VisitId(row.get("id")),
+            restaurant_id: RestaurantId(row.get("restaurant_id")),
+            owner_id: UserId(row.get("owner_id")),
+            visited_on: row.get("visited_on"),
+            rating: row.get("rating"),
+            party_size: row.get("party_size"),
+            spend: row.get("spend"),
+            notes: row.get("notes"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    /// Recompute the mean rating of the restaurant's visits, cleared once no
+    /// rated visit remains. The rating given by hand is left alone. When the
+    /// displayed rating changes, followers are told about it.
+    async fn refresh_restaurant_rating(
+        tx: &mut Transaction<'_, Postgres>,
+        restaurant_id: RestaurantId,
+    ) -> Result<()> {
+        let previous: Option<f64> = sqlx::query_scalar(
+            "SELECT COALESCE(visit_rating, rating)::float8 FROM restaurants WHERE id = $1 FOR UPDATE"
+        )
+        .bind(restaurant_id.0)
+        .fetch_optional(&mut **tx)
+        .await?
+        .flatten();
+
+        let row = sqlx::query(
+            r#"
+            UPDATE restaurants
+            SET visit_rating = (
+                    SELECT ROUND(AVG(rating), 2)
+                    FROM visits
+                    WHERE restaurant_id = $1 AND rating IS NOT NULL
+                ),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING owner_id, name, city, COALESCE(visit_rating, rating)::float8 AS displayed_rating
+            "#
+        )
+        .bind(restaurant_id.0)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let rating: Option<f64> = row.get("displayed_rating");
+        if let Some(rating) = rating
+            && previous != Some(rating)
+        {
+            let activity = RecordActivity {
+                user_id: UserId(row.get("owner_id")),
+                kind: ActivityKind::RestaurantRated,
+                restaurant_id: Some(restaurant_id),
+                wishlist_item_id: None,
+                name: row.get("name"),
+                city: row.get("city"),
+                // Feeds show whole stars
+                rating: Some(rating.round() as i16),
+            };
+            insert_activity(&mut **tx, &activity).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VisitRepository for PostgresVisitRepository {
+    async fn create(&self, visit: CreateVisit) -> Result<Option<Visit>> {
+        let mut tx = self.pool.begin().await?;
+
+        // Only insert when the restaurant belongs to the caller
+        let row = sqlx::query(
+            r#"
+            INSERT INTO visits (restaurant_id, owner_id, visited_on, rating, party_size, spend, notes, created_at, updated_at)
+            SELECT id, owner_id, $3, $4, $5, $6, $7, NOW(), NOW()
+            FROM restaurants
+            WHERE id = $1 AND owner_id = $2
+            RETURNING id, restaurant_id, owner_id, visited_on, rating, party_size, spend::float8 AS spend,
+                      notes, created_at, updated_at
+            "#
+        )
+        .bind(visit.restaurant_id.0)
+        .bind(visit.owner_id.0)
+        .bind(visit.visited_on)
+        .bind(visit.rating)
+        .bind(visit.party_size)
+        .bind(visit.spend)
+        .bind(&visit.notes)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        match row {
+            Some(row) => {
+                Self::refresh_restaurant_rating(&mut tx, visit.restaurant_id).await?;
+                tx.commit().await?;
+                Ok(Some(Self::map_row(row)))
+            }
+            None => {
+                tx.rollback().await?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn find_by_id(&self, id: VisitId, owner_id: UserId) -> Result<Option<Visit>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, restaurant_id, owner_id, visited_on, rating, party_size, spend::float8 AS spend,
+                   notes, created_at, updated_at
+            FROM visits
+            WHERE id = $1 AND owner_id = $2
+            "#
+        )
+        .bind(id.0)
+        .bind(owner_id.0)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn find_by_restaurant(&self, restaurant_id: RestaurantId, owner_id: UserId) -> Result<Vec<Visit>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, restaurant_id, owner_id, visited_on, rating, party_size, spend::float8 AS spend,
+                   notes, created_at, updated_at
+            FROM visits
+            WHERE restaurant_id = $1 AND owner_id = $2
+            ORDER BY visited_on DESC, created_at DESC
+            "#
+        )
+        .bind(restaurant_id.0)
+        .bind(owner_id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn update(&self, visit: UpdateVisit) -> Result<Option<Visit>> {
+        let mut query_parts = Vec::new();
+        let mut param_count = 1;
+
+        if visit.visited_on.is_some() {
+            query_parts.push(format!("visited_on = ${}", param_count));
+            param_count += 1;
+        }
+
+        if visit.rating.is_some() {
+            query_parts.push(format!("rating = ${}", param_count));
+            param_count += 1;
+        }
+
+        if visit.party_size.is_some() {
+            query_parts.push(format!("party_size = ${}", param_count));
+            param_count += 1;
+        }
+
+        if visit.spend.is_some() {
+            query_parts.push(format!("spend = ${}", param_count));
+            param_count += 1;
+        }
+
+        if visit.notes.is_some() {
+            query_parts.push(format!("notes = ${}", param_count));
+            param_count += 1;
+        }
+
+        if query_parts.is_empty() {
+            return self.find_by_id(visit.id, visit.owner_id).await;
+        }
+
+        query_parts.push("updated_at = NOW()".to_string());
+
+        let query = format!(
+            r#"
+            UPDATE visits
+            SET {}
+            WHERE id = ${} AND restaurant_id = ${} AND owner_id = ${}
+            RETURNING id, restaurant_id, owner_id, visited_on, rating, party_size, spend::float8 AS spend,
+                      notes, created_at, updated_at
+            "#,
+            query_parts.join(", "),
+            param_count,
+            param_count + 1,
+            param_count + 2
+        );
+
+        let mut sqlx_query = sqlx::query(&query);
+
+        if let Some(visited_on) = visit.visited_on {
+            sqlx_query = sqlx_query.bind(visited_on);
+        }
+        if let Some(rating) = visit.rating {
+            sqlx_query = sqlx_query.bind(rating);
+        }
+        if let Some(party_size) = visit.party_size {
+            sqlx_query = sqlx_query.bind(party_size);
+        }
+        if let Some(spend) = visit.spend {
+            sqlx_query = sqlx_query.bind(spend);
+        }
+        if let Some(notes) = &visit.notes {
+            sqlx_query = sqlx_query.bind(notes);
+        }
+
+        sqlx_query = sqlx_query
+            .bind(visit.id.0)
+            .bind(visit.restaurant_id.0)
+            .bind(visit.owner_id.0);
+
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx_query.fetch_optional(&mut *tx).await?;
+
+        match row {
+            Some(row) => {
+                Self::refresh_restaurant_rating(&mut tx, visit.restaurant_id).await?;
+                tx.commit().await?;
+                Ok(Some(Self::map_row(row)))
+            }
+            None => {
+                tx.rollback().await?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn delete(&self, id: VisitId, restaurant_id: RestaurantId, owner_id: UserId) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM visits WHERE id = $1 AND restaurant_id = $2 AND owner_id = $3")
+            .bind(id.0)
+            .bind(restaurant_id.0)
+            .bind(owner_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        Self::refresh_restaurant_rating(&mut tx, restaurant_id).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -1,7 +1,14 @@
-use crate::domain::wishlist::{WishlistItem, WishlistId, CreateWishlistItem, UpdateWishlistItem, WishlistPriority, WishlistCountResponse};
+use crate::domain::wishlist::{WishlistItem, WishlistId, CreateWishlistItem, UpdateWishlistItem, WishlistPriority, WishlistCountResponse, WishlistSort};
+use crate::domain::geo::{BoundingBox, NearbySearch};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::pagination::{CursorPage, PageRequest};
+use crate::domain::tag::TagId;
 use crate::domain::user::UserId;
 use crate::error::Result;
 use crate::database::DbPool;
+use crate::repositories::location::{distance_sql, within_sql};
+use crate::repositories::tag_repository::resolve_new_row_tags;
+use crate::repositories::place_repository::escape_like;
 use async_trait::async_trait;
 use sqlx::Row;
 
@@ -9,11 +16,14 @@ use sqlx::Row;
 pub trait WishlistRepository: Send + Sync {
     async fn create(&self, item: CreateWishlistItem) -> Result<WishlistItem>;
     async fn find_by_id(&self, id: WishlistId) -> Result<Option<WishlistItem>>;
-    async fn find_by_owner(&self, owner_id: UserId, priority: Option<WishlistPriority>, limit: Option<i64>) -> Result<Vec<WishlistItem>>;
+    async fn find_by_owner(&self, owner_id: UserId, priority: Option<WishlistPriority>, tag_ids: &[TagId], page: &PageRequest<WishlistSort>) -> Result<CursorPage<WishlistItem>>;
     async fn update(&self, item: UpdateWishlistItem) -> Result<Option<WishlistItem>>;
     async fn delete(&self, id: WishlistId, owner_id: UserId) -> Result<bool>;
     async fn get_count(&self, owner_id: UserId) -> Result<WishlistCountResponse>;
-    async fn promote_to_restaurant(&self, id: WishlistId, owner_id: UserId) -> Result<bool>;
+    async fn promote_to_restaurant(&self, id: WishlistId, owner_id: UserId) -> Result<Option<RestaurantId>>;
+    async fn find_nearby(&self, owner_id: UserId, search: &NearbySearch) -> Result<Vec<(WishlistItem, f64)>>;
+    async fn find_within(&self, owner_id: UserId, bounds: &BoundingBox, limit: i64) -> Result<Vec<WishlistItem>>;
+    async fn suggest(&self, owner_id: UserId, term: &str, source_ids: &[String], limit: i64) -> Result<Vec<WishlistItem>>;
 }
 
 pub struct PostgresWishlistRepository {
@@ -24,39 +34,53 @@ impl PostgresWishlistRepository {
     pub fn new(pool: DbPool) -> Self {
         Self { pool }
     }
+
+    /// SQL expression a sort field orders by, and the type its cursor key casts back to
+    fn sort_key(sort: WishlistSort) -> (&'static str, &'static str) {
+        match sort {
+            WishlistSort::Name => ("lower(name)", "text"),
+            WishlistSort::City => ("lower(city)", "text"),
+            WishlistSort::Priority => ("priority", "wishlist_priority"),
+            WishlistSort::CreatedAt => ("created_at", "timestamptz"),
+            WishlistSort::UpdatedAt => ("updated_at", "timestamptz"),
+        }
+    }
 }
 
 #[async_trait]
 impl WishlistRepository for PostgresWishlistRepository {
     async fn create(&self, item: CreateWishlistItem) -> Result<WishlistItem> {
-        let priority_str = match item.priority {
-            WishlistPriority::Low => "low",
-            WishlistPriority::Medium => "medium",
-            WishlistPriority::High => "high",
-        };
+        let mut tx = self.pool.begin().await?;
 
         let row = sqlx::query(
             r#"
-            INSERT INTO wishlist_items (owner_id, name, city, notes, priority, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
-            RETURNING id, owner_id, name, city, notes, priority, created_at, updated_at
+            INSERT INTO wishlist_items (owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW())
+            RETURNING id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at
             "#
         )
         .bind(item.owner_id.0)
         .bind(&item.name)
         .bind(&item.city)
         .bind(&item.notes)
-        .bind(priority_str)
-        .fetch_one(&self.pool)
+        .bind(item.priority)
+        .bind(&item.address)
+        .bind(item.latitude)
+        .bind(item.longitude)
+        .bind(&item.source_id)
+        .fetch_one(&mut *tx)
         .await?;
 
-        let priority_str: String = row.get("priority");
-        let priority = match priority_str.as_str() {
-            "low" => WishlistPriority::Low,
-            "medium" => WishlistPriority::Medium,
-            "high" => WishlistPriority::High,
-            _ => WishlistPriority::Medium,
-        };
+        let tag_ids = resolve_new_row_tags(&mut tx, item.owner_id, &item.tag_ids, &item.cuisines).await?;
+        if !tag_ids.is_empty() {
+            sqlx::query("INSERT INTO wishlist_item_tags (wishlist_item_id, tag_id) SELECT $1, UNNEST($2::int[])")
+                .bind(row.get::<i32, _>("id"))
+                .bind(&tag_ids)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
 
         Ok(WishlistItem {
             id: WishlistId(row.get("id")),
@@ -64,7 +88,11 @@ impl WishlistRepository for PostgresWishlistRepository {
             name: row.get("name"),
             city: row.get("city"),
             notes: row.get("notes"),
-            priority,
+            priority: row.get("priority"),
+            address: row.get("address"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+            source_id: row.get("source_id"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -73,7 +101,7 @@ impl WishlistRepository for PostgresWishlistRepository {
     async fn find_by_id(&self, id: WishlistId) -> Result<Option<WishlistItem>> {
         let row = sqlx::query(
             r#"
-            SELECT id, owner_id, name, city, notes, priority, created_at, updated_at
+            SELECT id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at
             FROM wishlist_items 
             WHERE id = $1
             "#
@@ -83,13 +111,6 @@ impl WishlistRepository for PostgresWishlistRepository {
         .await?;
 
         if let Some(row) = row {
-            let priority_str: String = row.get("priority");
-            let priority = match priority_str.as_str() {
-                "low" => WishlistPriority::Low,
-                "medium" => WishlistPriority::Medium,
-                "high" => WishlistPriority::High,
-                _ => WishlistPriority::Medium,
-            };
 
             Ok(Some(WishlistItem {
                 id: WishlistId(row.get("id")),
@@ -97,7 +118,11 @@ impl WishlistRepository for PostgresWishlistRepository {
                 name: row.get("name"),
                 city: row.get("city"),
                 notes: row.get("notes"),
-                priority,
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
             }))
@@ -106,86 +131,152 @@ impl WishlistRepository for PostgresWishlistRepository {
         }
     }
 
-    async fn find_by_owner(&self, owner_id: UserId, priority: Option<WishlistPriority>, limit: Option<i64>) -> Result<Vec<WishlistItem>> {
-        let mut query = String::from(
-            r#"
-            SELECT id, owner_id, name, city, notes, priority, created_at, updated_at
-            FROM wishlist_items 
-            WHERE owner_id = $1
-            "#
-        );
+    async fn find_by_owner(&self, owner_id: UserId, priority: Option<WishlistPriority>, tag_ids: &[TagId], page: &PageRequest<WishlistSort>) -> Result<CursorPage<WishlistItem>> {
+        let mut filters = String::from("owner_id = $1");
+        let mut param_count = 2;
 
         if priority.is_some() {
-            query.push_str(" AND priority = $2");
+            filters.push_str(" AND priority = $2");
+            param_count += 1;
+        }
+
+        // Items must carry every requested tag
+        if !tag_ids.is_empty() {
+            filters.push_str(&format!(
+                r#" AND id IN (
+                    SELECT wishlist_item_id FROM wishlist_item_tags
+                    WHERE tag_id = ANY(${})
+                    GROUP BY wishlist_item_id
+                    HAVING COUNT(*) = ${}
+                )"#,
+                param_count,
+                param_count + 1
+            ));
+            param_count += 2;
         }
 
-        query.push_str(" ORDER BY priority DESC, created_at DESC");
+        let ids: Vec<i32> = tag_ids.iter().map(|id| id.0).collect();
 
-        if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
+        let count_query = format!("SELECT COUNT(*) FROM wishlist_items WHERE {}", filters);
+        let mut sqlx_count = sqlx::query_scalar::<_, i64>(&count_query).bind(owner_id.0);
+        if let Some(priority) = priority {
+            sqlx_count = sqlx_count.bind(priority);
+        }
+        if !tag_ids.is_empty() {
+            sqlx_count = sqlx_count.bind(ids.clone()).bind(tag_ids.len() as i64);
+        }
+        let total = sqlx_count.fetch_one(&self.pool).await?;
+
+        // Keyset pagination: resume strictly after the (sort key, id) of the cursor
+        let (key, key_type) = Self::sort_key(page.sort);
+        let direction = page.direction.sql();
+        let mut conditions = filters;
+        if page.after.is_some() {
+            conditions.push_str(&format!(
+                " AND ({}, id) {} (${}::{}, ${})",
+                key,
+                page.direction.after_operator(),
+                param_count,
+                key_type,
+                param_count + 1
+            ));
+            param_count += 2;
         }
 
+        let query = format!(
+            r#"
+            SELECT id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at,
+                   ({key})::text AS sort_key
+            FROM wishlist_items
+            WHERE {conditions}
+            ORDER BY {key} {direction}, id {direction}
+            LIMIT ${param_count}
+            "#
+        );
+
         let mut sqlx_query = sqlx::query(&query).bind(owner_id.0);
 
         if let Some(priority) = priority {
-            let priority_str = match priority {
-                WishlistPriority::Low => "low",
-                WishlistPriority::Medium => "medium",
-                WishlistPriority::High => "high",
-            };
-            sqlx_query = sqlx_query.bind(priority_str);
+            sqlx_query = sqlx_query.bind(priority);
         }
 
-        let rows = sqlx_query.fetch_all(&self.pool).await?;
+        if !tag_ids.is_empty() {
+            sqlx_query = sqlx_query.bind(ids).bind(tag_ids.len() as i64);
+        }
 
-        let items = rows.into_iter().map(|row| {
-            let priority_str: String = row.get("priority");
-            let priority = match priority_str.as_str() {
-                "low" => WishlistPriority::Low,
-                "medium" => WishlistPriority::Medium,
-                "high" => WishlistPriority::High,
-                _ => WishlistPriority::Medium,
-            };
+        if let Some(cursor) = &page.after {
+            sqlx_query = sqlx_query.bind(&cursor.key).bind(cursor.id);
+        }
+
+        // Fetch one extra row to know whether another page follows
+        let mut rows = sqlx_query
+            .bind(page.limit + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_more = rows.len() as i64 > page.limit;
+        rows.truncate(page.limit as usize);
 
+        let next_cursor = match rows.last() {
+            Some(row) if has_more => Some(page.cursor_after(row.get("sort_key"), row.get("id")).encode()),
+            _ => None,
+        };
+
+        let items = rows.into_iter().map(|row| {
             WishlistItem {
                 id: WishlistId(row.get("id")),
                 owner_id: UserId(row.get("owner_id")),
                 name: row.get("name"),
                 city: row.get("city"),
                 notes: row.get("notes"),
-                priority,
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
             }
         }).collect();
 
-        Ok(items)
+        Ok(CursorPage { items, next_cursor, total })
     }
 
     async fn update(&self, item: UpdateWishlistItem) -> Result<Option<WishlistItem>> {
         let mut query_parts = Vec::new();
         let mut param_count = 1;
 
-        if let Some(name) = &item.name {
+        if item.name.is_some() {
             query_parts.push(format!("name = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(city) = &item.city {
+        if item.city.is_some() {
             query_parts.push(format!("city = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(notes) = &item.notes {
+        if item.notes.is_some() {
             query_parts.push(format!("notes = ${}", param_count));
             param_count += 1;
         }
 
-        if let Some(priority) = item.priority {
+        if item.priority.is_some() {
             query_parts.push(format!("priority = ${}", param_count));
             param_count += 1;
         }
 
+        if item.address.is_some() {
+            query_parts.push(format!("address = ${}", param_count));
+            param_count += 1;
+        }
+
+        if item.coordinates.is_some() {
+            query_parts.push(format!("latitude = ${}", param_count));
+            query_parts.push(format!("longitude = ${}", param_count + 1));
+            param_count += 2;
+        }
+
         if query_parts.is_empty() {
             return self.find_by_id(item.id).await;
         }
@@ -197,7 +288,7 @@ impl WishlistRepository for PostgresWishlistRepository {
             UPDATE wishlist_items 
             SET {}
             WHERE id = ${} AND owner_id = ${}
-            RETURNING id, owner_id, name, city, notes, priority, created_at, updated_at
+            RETURNING id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at
             "#,
             query_parts.join(", "),
             param_count,
@@ -216,12 +307,15 @@ impl WishlistRepository for PostgresWishlistRepository {
             sqlx_query = sqlx_query.bind(notes);
         }
         if let Some(priority) = item.priority {
-            let priority_str = match priority {
-                WishlistPriority::Low => "low",
-                WishlistPriority::Medium => "medium",
-                WishlistPriority::High => "high",
-            };
-            sqlx_query = sqlx_query.bind(priority_str);
+            sqlx_query = sqlx_query.bind(priority);
+        }
+        if let Some(address) = &item.address {
+            sqlx_query = sqlx_query.bind(address);
+        }
+        if let Some(coordinates) = item.coordinates {
+            sqlx_query = sqlx_query
+                .bind(coordinates.map(|(lat, _)| lat))
+                .bind(coordinates.map(|(_, lon)| lon));
         }
 
         sqlx_query = sqlx_query.bind(item.id.0).bind(item.owner_id.0);
@@ -229,13 +323,6 @@ impl WishlistRepository for PostgresWishlistRepository {
         let row = sqlx_query.fetch_optional(&self.pool).await?;
 
         if let Some(row) = row {
-            let priority_str: String = row.get("priority");
-            let priority = match priority_str.as_str() {
-                "low" => WishlistPriority::Low,
-                "medium" => WishlistPriority::Medium,
-                "high" => WishlistPriority::High,
-                _ => WishlistPriority::Medium,
-            };
 
             Ok(Some(WishlistItem {
                 id: WishlistId(row.get("id")),
@@ -243,7 +330,11 @@ impl WishlistRepository for PostgresWishlistRepository {
                 name: row.get("name"),
                 city: row.get("city"),
                 notes: row.get("notes"),
-                priority,
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
             }))
@@ -273,11 +364,11 @@ impl WishlistRepository for PostgresWishlistRepository {
         })
     }
 
-    async fn promote_to_restaurant(&self, id: WishlistId, owner_id: UserId) -> Result<bool> {
+    async fn promote_to_restaurant(&self, id: WishlistId, owner_id: UserId) -> Result<Option<RestaurantId>> {
         let mut tx = self.pool.begin().await?;
 
         // Get the wishlist item
-        let wishlist_item = sqlx::query("SELECT name, city, notes FROM wishlist_items WHERE id = $1 AND owner_id = $2")
+        let wishlist_item = sqlx::query("SELECT name, city, notes, address, latitude, longitude, source_id FROM wishlist_items WHERE id = $1 AND owner_id = $2")
             .bind(id.0)
             .bind(owner_id.0)
             .fetch_optional(&mut *tx)
@@ -287,18 +378,52 @@ impl WishlistRepository for PostgresWishlistRepository {
             let name: String = item.get("name");
             let city: String = item.get("city");
             let notes: Option<String> = item.get("notes");
+            let address: Option<String> = item.get("address");
+            let latitude: Option<f64> = item.get("latitude");
+            let longitude: Option<f64> = item.get("longitude");
+            let source_id: Option<String> = item.get("source_id");
 
             // Create restaurant from wishlist item
-            sqlx::query(
+            let restaurant_id: i32 = sqlx::query_scalar(
                 r#"
-                INSERT INTO restaurants (owner_id, name, city, description, is_favorite, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, false, NOW(), NOW())
+                INSERT INTO restaurants (owner_id, name, city, description, is_favorite, address, latitude, longitude, source_id, promoted_from_wishlist, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, false, $5, $6, $7, $8, true, NOW(), NOW())
+                RETURNING id
                 "#
             )
             .bind(owner_id.0)
             .bind(&name)
             .bind(&city)
             .bind(&notes)
+            .bind(&address)
+            .bind(latitude)
+            .bind(longitude)
+            .bind(&source_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            // Carry the item's tags over to the new restaurant
+            sqlx::query(
+                r#"
+                INSERT INTO restaurant_tags (restaurant_id, tag_id)
+                SELECT $1, tag_id FROM wishlist_item_tags WHERE wishlist_item_id = $2
+                "#
+            )
+            .bind(restaurant_id)
+            .bind(id.0)
+            .execute(&mut *tx)
+            .await?;
+
+            // Keep the item's place in the user's lists
+            sqlx::query(
+                r#"
+                UPDATE list_entries
+                SET restaurant_id = $1, wishlist_item_id = NULL
+                WHERE wishlist_item_id = $2
+                "#
+            )
+            .bind(restaurant_id)
+            .bind(id.0)
             .execute(&mut *tx)
             .await?;
 
@@ -310,10 +435,141 @@ impl WishlistRepository for PostgresWishlistRepository {
                 .await?;
 
             tx.commit().await?;
-            Ok(true)
+            Ok(Some(RestaurantId(restaurant_id)))
         } else {
             tx.rollback().await?;
-            Ok(false)
+            Ok(None)
         }
     }
-}
\ No newline at end of file
+
+    async fn find_nearby(&self, owner_id: UserId, search: &NearbySearch) -> Result<Vec<(WishlistItem, f64)>> {
+        let query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at,
+                       {} AS distance_km
+                FROM wishlist_items
+                WHERE owner_id = $1 AND {}
+            ) nearby
+            WHERE distance_km <= $8
+            ORDER BY distance_km, id
+            LIMIT $9
+            "#,
+            distance_sql(2, 3),
+            within_sql(&search.bounds, 4),
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(owner_id.0)
+            .bind(search.latitude)
+            .bind(search.longitude)
+            .bind(search.bounds.min_lat)
+            .bind(search.bounds.max_lat)
+            .bind(search.bounds.min_lon)
+            .bind(search.bounds.max_lon)
+            .bind(search.radius_km)
+            .bind(search.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(|row| {
+            let item = WishlistItem {
+                id: WishlistId(row.get("id")),
+                owner_id: UserId(row.get("owner_id")),
+                name: row.get("name"),
+                city: row.get("city"),
+                notes: row.get("notes"),
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            };
+            (item, row.get("distance_km"))
+        }).collect())
+    }
+
+    async fn find_within(&self, owner_id: UserId, bounds: &BoundingBox, limit: i64) -> Result<Vec<WishlistItem>> {
+        let query = format!(
+            r#"
+            SELECT id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at
+            FROM wishlist_items
+            WHERE owner_id = $1 AND {}
+            ORDER BY priority DESC, id
+            LIMIT $6
+            "#,
+            within_sql(bounds, 2),
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(owner_id.0)
+            .bind(bounds.min_lat)
+            .bind(bounds.max_lat)
+            .bind(bounds.min_lon)
+            .bind(bounds.max_lon)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(|row| {
+            WishlistItem {
+                id: WishlistId(row.get("id")),
+                owner_id: UserId(row.get("owner_id")),
+                name: row.get("name"),
+                city: row.get("city"),
+                notes: row.get("notes"),
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            }
+        }).collect())
+    }
+
+    async fn suggest(&self, owner_id: UserId, term: &str, source_ids: &[String], limit: i64) -> Result<Vec<WishlistItem>> {
+        // Entries linked to one of the given upstream records, then an
+        // accent-insensitive substring match on name or city, names
+        // starting with the term first
+        let rows = sqlx::query(
+            r#"
+            SELECT id, owner_id, name, city, notes, priority, address, latitude, longitude, source_id, created_at, updated_at
+            FROM wishlist_items
+            WHERE owner_id = $1
+              AND (lower(unaccent(name)) LIKE '%' || lower(unaccent($2)) || '%'
+                OR lower(unaccent(city)) LIKE '%' || lower(unaccent($2)) || '%'
+                OR source_id = ANY($4))
+            ORDER BY source_id = ANY($4) DESC NULLS LAST,
+                     lower(unaccent(name)) LIKE lower(unaccent($2)) || '%' DESC, priority DESC, id
+            LIMIT $3
+            "#
+        )
+        .bind(owner_id.0)
+        .bind(escape_like(term.trim()))
+        .bind(limit)
+        .bind(source_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| {
+            WishlistItem {
+                id: WishlistId(row.get("id")),
+                owner_id: UserId(row.get("owner_id")),
+                name: row.get("name"),
+                city: row.get("city"),
+                notes: row.get("notes"),
+                priority: row.get("priority"),
+                address: row.get("address"),
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+                source_id: row.get("source_id"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            }
+        }).collect())
+    }
+}
@@ -1,21 +1,23 @@
 use crate::handlers::{
     auth_handler::AuthHandler,
+    api_token_handler::ApiTokenHandler,
+    two_factor_handler::TwoFactorHandler,
+    audit_handler::AuditHandler,
+    user_handler::UserHandler,
     restaurant_handler::RestaurantHandler,
     wishlist_handler::WishlistHandler,
     autocomplete_handler::AutocompleteHandler,
+    visit_handler::VisitHandler,
+    tag_handler::TagHandler,
+    list_handler::ListHandler,
+    social_handler::SocialHandler,
+    export_handler::ExportHandler,
+    import_handler::ImportHandler,
     health_handler,
 };
-use crate::domain::user::{RegisterUserCommand, LoginCommand, RegisterResponse, UserResponse};
-use crate::domain::restaurant::{
-    CreateRestaurantRequest, UpdateRestaurantRequest, RestaurantResponse, RestaurantStatsResponse
-};
-use crate::domain::wishlist::{
-    CreateWishlistRequest, UpdateWishlistRequest, WishlistItemResponse, WishlistCountResponse
-};
-use crate::domain::autocomplete::{AutocompleteRequest, AutocompleteResponse};
-use crate::error::AppError;
+use crate::domain::autocomplete::AutocompleteRequest;
+use crate::middleware::auth::{AuthenticatedUser, OptionalUser};
 use actix_web::web;
-use utoipa::path;
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg
@@ -27,8 +29,14 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         .service(
             web::scope("/api/v1")
                 .configure(auth_routes)
+                .configure(account_routes)
                 .configure(restaurant_routes)
                 .configure(wishlist_routes)
+                .configure(tag_routes)
+                .configure(list_routes)
+                .configure(public_routes)
+                .configure(social_routes)
+                .configure(data_routes)
                 .configure(autocomplete_routes)
         );
 }
@@ -38,9 +46,36 @@ fn auth_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/auth")
             .route("/register", web::post().to(register))
             .route("/login", web::post().to(login))
+            .route("/login/2fa", web::post().to(login_two_factor))
             .route("/logout", web::post().to(logout))
             .route("/me", web::get().to(get_user_info))
             .route("/check", web::get().to(check_auth))
+            .route("/verify-email", web::post().to(verify_email))
+            .route("/verify-email/resend", web::post().to(resend_verification))
+            .route("/password-reset", web::post().to(request_password_reset))
+            .route("/password-reset/confirm", web::post().to(confirm_password_reset))
+            .route("/sessions", web::get().to(list_sessions))
+            .route("/sessions", web::delete().to(revoke_other_sessions))
+            .route("/sessions/{id}", web::delete().to(revoke_session))
+            .route("/tokens", web::get().to(get_api_tokens))
+            .route("/tokens", web::post().to(create_api_token))
+            .route("/tokens/{id}", web::delete().to(revoke_api_token))
+            .route("/2fa", web::get().to(get_two_factor_status))
+            .route("/2fa/setup", web::post().to(setup_two_factor))
+            .route("/2fa/enable", web::post().to(enable_two_factor))
+            .route("/2fa/disable", web::post().to(disable_two_factor))
+            .route("/2fa/recovery-codes", web::post().to(regenerate_recovery_codes))
+            .route("/security-events", web::get().to(get_security_events))
+    );
+}
+
+fn account_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/account")
+            .route("", web::delete().to(delete_account))
+            .route("/password", web::put().to(change_password))
+            .route("/username", web::put().to(change_username))
+            .route("/email", web::put().to(change_email))
     );
 }
 
@@ -51,9 +86,18 @@ fn restaurant_routes(cfg: &mut web::ServiceConfig) {
             .route("", web::post().to(create_restaurant))
             .route("/stats", web::get().to(get_restaurant_stats))
             .route("/search", web::post().to(search_restaurants))
+            .route("/nearby", web::get().to(get_restaurants_nearby))
+            .route("/within", web::get().to(get_restaurants_within))
             .route("/{id}", web::get().to(get_restaurant))
             .route("/{id}", web::put().to(update_restaurant))
             .route("/{id}", web::delete().to(delete_restaurant))
+            .route("/{id}/visits", web::get().to(get_visits))
+            .route("/{id}/visits", web::post().to(create_visit))
+            .route("/{id}/visits/{visit_id}", web::get().to(get_visit))
+            .route("/{id}/visits/{visit_id}", web::put().to(update_visit))
+            .route("/{id}/visits/{visit_id}", web::delete().to(delete_visit))
+            .route("/{id}/tags", web::get().to(get_restaurant_tags))
+            .route("/{id}/tags", web::put().to(set_restaurant_tags))
     );
 }
 
@@ -63,14 +107,73 @@ fn wishlist_routes(cfg: &mut web::ServiceConfig) {
             .route("", web::get().to(get_wishlist_items))
             .route("", web::post().to(create_wishlist_item))
             .route("/count", web::get().to(get_wishlist_count))
+            .route("/nearby", web::get().to(get_wishlist_items_nearby))
+            .route("/within", web::get().to(get_wishlist_items_within))
             .route("/priority/{priority}", web::get().to(get_items_by_priority))
             .route("/{id}", web::get().to(get_wishlist_item))
             .route("/{id}", web::put().to(update_wishlist_item))
             .route("/{id}", web::delete().to(delete_wishlist_item))
             .route("/{id}/promote", web::post().to(promote_wishlist_item))
+            .route("/{id}/tags", web::get().to(get_wishlist_item_tags))
+            .route("/{id}/tags", web::put().to(set_wishlist_item_tags))
+    );
+}
+
+fn tag_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/tags")
+            .route("", web::get().to(get_tags))
+            .route("", web::post().to(create_tag))
+            .route("/{id}", web::put().to(update_tag))
+            .route("/{id}", web::delete().to(delete_tag))
+    );
+}
+
+fn list_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/lists")
+            .route("", web::get().to(get_lists))
+            .route("", web::post().to(create_list))
+            .route("/{id}", web::get().to(get_list))
+            .route("/{id}", web::put().to(update_list))
+            .route("/{id}", web::delete().to(delete_list))
+            .route("/{id}/share", web::post().to(share_list))
+            .route("/{id}/share", web::delete().to(unshare_list))
+            .route("/{id}/order", web::put().to(reorder_list))
+            .route("/{id}/entries", web::post().to(add_list_entry))
+            .route("/{id}/entries/{entry_id}", web::put().to(update_list_entry))
+            .route("/{id}/entries/{entry_id}", web::delete().to(remove_list_entry))
+    );
+}
+
+/// Endpoints readable without logging in
+fn public_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/public")
+            .route("/lists/{token}", web::get().to(get_public_list))
     );
 }
 
+fn social_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/feed", web::get().to(get_feed))
+        .route("/settings/privacy", web::get().to(get_privacy_settings))
+        .route("/settings/privacy", web::put().to(update_privacy_settings))
+        .service(
+            web::scope("/users/{username}")
+                .route("", web::get().to(get_user_profile))
+                .route("/follow", web::post().to(follow_user))
+                .route("/follow", web::delete().to(unfollow_user))
+                .route("/followers", web::get().to(get_user_followers))
+                .route("/following", web::get().to(get_user_following))
+                .route("/activity", web::get().to(get_user_activity))
+        );
+}
+
+fn data_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/export", web::get().to(export_collection))
+        .route("/import", web::post().to(import_collection));
+}
+
 // Auth handlers
 #[utoipa::path(
     post,
@@ -96,16 +199,36 @@ async fn register(
     tag = "Authentication",
     request_body = LoginCommand,
     responses(
-        (status = 200, description = "Login successful"),
-        (status = 401, description = "Invalid credentials", body = AppError)
+        (status = 200, description = "Login successful, or a two-factor challenge when the account uses two-factor authentication", body = LoginChallengeResponse),
+        (status = 401, description = "Invalid credentials or account locked", body = AppError)
     )
 )]
 async fn login(
     auth_handler: web::Data<AuthHandler>,
     req: web::Json<crate::domain::user::LoginCommand>,
+    http_req: actix_web::HttpRequest,
+    session: actix_session::Session,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    auth_handler.login(req, http_req, session).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login/2fa",
+    tag = "Authentication",
+    request_body = LoginTwoFactorRequest,
+    responses(
+        (status = 200, description = "Login successful"),
+        (status = 401, description = "Invalid or expired challenge, invalid code, or account locked", body = AppError)
+    )
+)]
+async fn login_two_factor(
+    auth_handler: web::Data<AuthHandler>,
+    req: web::Json<crate::domain::two_factor::LoginTwoFactorRequest>,
+    http_req: actix_web::HttpRequest,
     session: actix_session::Session,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    auth_handler.login(req, session).await
+    auth_handler.login_two_factor(req, http_req, session).await
 }
 
 #[utoipa::path(
@@ -119,9 +242,10 @@ async fn login(
 )]
 async fn logout(
     auth_handler: web::Data<AuthHandler>,
+    http_req: actix_web::HttpRequest,
     session: actix_session::Session,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    auth_handler.logout(session).await
+    auth_handler.logout(http_req, session).await
 }
 
 #[utoipa::path(
@@ -135,9 +259,9 @@ async fn logout(
 )]
 async fn get_user_info(
     auth_handler: web::Data<AuthHandler>,
-    session: actix_session::Session,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    auth_handler.get_user_info(session).await
+    auth_handler.get_user_info(user).await
 }
 
 #[utoipa::path(
@@ -151,332 +275,1638 @@ async fn get_user_info(
 )]
 async fn check_auth(
     auth_handler: web::Data<AuthHandler>,
-    session: actix_session::Session,
+    user: OptionalUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    auth_handler.check_auth(session).await
+    auth_handler.check_auth(user).await
 }
 
-// Restaurant handlers
 #[utoipa::path(
     post,
-    path = "/api/v1/restaurants",
-    tag = "Restaurants",
-    request_body = CreateRestaurantRequest,
+    path = "/api/v1/auth/verify-email",
+    tag = "Authentication",
+    request_body = VerifyEmailRequest,
     responses(
-        (status = 201, description = "Restaurant created successfully", body = RestaurantResponse),
-        (status = 400, description = "Validation error", body = AppError),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 200, description = "Email verified"),
+        (status = 400, description = "Invalid or expired token", body = AppError)
     )
 )]
-async fn create_restaurant(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    req: web::Json<crate::domain::restaurant::CreateRestaurantRequest>,
-    session: actix_session::Session,
+async fn verify_email(
+    auth_handler: web::Data<AuthHandler>,
+    req: web::Json<crate::domain::user_token::VerifyEmailRequest>,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.create(req, session).await
+    auth_handler.verify_email(req).await
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/restaurants",
-    tag = "Restaurants",
-    params(
-        ("favorites" = Option<bool>, Query, description = "Filter by favorite restaurants only"),
-        ("limit" = Option<i64>, Query, description = "Maximum number of results")
-    ),
+    post,
+    path = "/api/v1/auth/verify-email/resend",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "List of restaurants", body = Vec<RestaurantResponse>),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 200, description = "Verification email sent"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 409, description = "Email already verified", body = AppError)
     )
 )]
-async fn get_restaurants(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    query: web::Query<crate::domain::restaurant::RestaurantQuery>,
-    session: actix_session::Session,
+async fn resend_verification(
+    auth_handler: web::Data<AuthHandler>,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.get_restaurants(query, session).await
+    auth_handler.resend_verification(user).await
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/restaurants/{id}",
-    tag = "Restaurants",
-    params(
-        ("id" = i32, Path, description = "Restaurant ID")
-    ),
+    post,
+    path = "/api/v1/auth/password-reset",
+    tag = "Authentication",
+    request_body = PasswordResetRequest,
     responses(
-        (status = 200, description = "Restaurant details", body = RestaurantResponse),
-        (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Restaurant not found", body = AppError)
+        (status = 200, description = "Reset link sent if the email has an account"),
+        (status = 400, description = "Invalid email format", body = AppError)
     )
 )]
-async fn get_restaurant(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    path: web::Path<i32>,
-    session: actix_session::Session,
+async fn request_password_reset(
+    auth_handler: web::Data<AuthHandler>,
+    req: web::Json<crate::domain::user_token::PasswordResetRequest>,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.get_restaurant(path, session).await
+    auth_handler.request_password_reset(req).await
 }
 
 #[utoipa::path(
-    put,
-    path = "/api/v1/restaurants/{id}",
-    tag = "Restaurants",
-    params(
-        ("id" = i32, Path, description = "Restaurant ID")
-    ),
-    request_body = UpdateRestaurantRequest,
+    post,
+    path = "/api/v1/auth/password-reset/confirm",
+    tag = "Authentication",
+    request_body = PasswordResetConfirmRequest,
     responses(
-        (status = 200, description = "Restaurant updated successfully", body = RestaurantResponse),
-        (status = 400, description = "Validation error", body = AppError),
-        (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Restaurant not found", body = AppError)
+        (status = 200, description = "Password changed, every session logged out"),
+        (status = 400, description = "Invalid or expired token, or invalid password", body = AppError)
     )
 )]
-async fn update_restaurant(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    path: web::Path<i32>,
-    req: web::Json<crate::domain::restaurant::UpdateRestaurantRequest>,
-    session: actix_session::Session,
+async fn confirm_password_reset(
+    auth_handler: web::Data<AuthHandler>,
+    req: web::Json<crate::domain::user_token::PasswordResetConfirmRequest>,
+    http_req: actix_web::HttpRequest,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.update(path, req, session).await
+    auth_handler.confirm_password_reset(req, http_req).await
 }
 
 #[utoipa::path(
-    delete,
-    path = "/api/v1/restaurants/{id}",
-    tag = "Restaurants",
-    params(
-        ("id" = i32, Path, description = "Restaurant ID")
-    ),
+    get,
+    path = "/api/v1/auth/sessions",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "Restaurant deleted successfully"),
-        (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Restaurant not found", body = AppError)
+        (status = 200, description = "Active sessions, newest first", body = [SessionResponse]),
+        (status = 401, description = "Not authenticated", body = AppError)
     )
 )]
-async fn delete_restaurant(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    path: web::Path<i32>,
-    session: actix_session::Session,
+async fn list_sessions(
+    auth_handler: web::Data<AuthHandler>,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.delete(path, session).await
+    auth_handler.list_sessions(user).await
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/restaurants/stats",
-    tag = "Restaurants",
+    delete,
+    path = "/api/v1/auth/sessions",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "Restaurant statistics", body = RestaurantStatsResponse),
+        (status = 200, description = "Every other session logged out", body = RevokeSessionsResponse),
         (status = 401, description = "Not authenticated", body = AppError)
     )
 )]
-async fn get_restaurant_stats(
-    restaurant_handler: web::Data<RestaurantHandler>,
+async fn revoke_other_sessions(
+    auth_handler: web::Data<AuthHandler>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
     session: actix_session::Session,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.get_stats(session).await
+    auth_handler.revoke_other_sessions(http_req, user, session).await
 }
 
 #[utoipa::path(
-    post,
-    path = "/api/v1/restaurants/search",
-    tag = "Restaurants",
+    delete,
+    path = "/api/v1/auth/sessions/{id}",
+    tag = "Authentication",
+    params(
+        ("id" = Uuid, Path, description = "Session ID")
+    ),
     responses(
-        (status = 200, description = "Search results", body = Vec<RestaurantResponse>),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 200, description = "Session logged out"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Session not found", body = AppError)
     )
 )]
-async fn search_restaurants(
-    restaurant_handler: web::Data<RestaurantHandler>,
-    query: web::Query<serde_json::Value>,
+async fn revoke_session(
+    auth_handler: web::Data<AuthHandler>,
+    path: web::Path<uuid::Uuid>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
     session: actix_session::Session,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    restaurant_handler.search(query, session).await
+    auth_handler.revoke_session(path, http_req, user, session).await
 }
 
-// Wishlist handlers
 #[utoipa::path(
     post,
-    path = "/api/v1/wishlist",
-    tag = "Wishlist",
-    request_body = CreateWishlistRequest,
+    path = "/api/v1/auth/tokens",
+    tag = "Authentication",
+    request_body = CreateApiTokenRequest,
     responses(
-        (status = 201, description = "Wishlist item created successfully", body = WishlistItemResponse),
+        (status = 201, description = "API token created, shown this once", body = CreatedApiTokenResponse),
         (status = 400, description = "Validation error", body = AppError),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Called with an API token", body = AppError)
     )
 )]
-async fn create_wishlist_item(
-    wishlist_handler: web::Data<WishlistHandler>,
-    req: web::Json<crate::domain::wishlist::CreateWishlistRequest>,
-    session: actix_session::Session,
+async fn create_api_token(
+    api_token_handler: web::Data<ApiTokenHandler>,
+    req: web::Json<crate::domain::api_token::CreateApiTokenRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.create(req, session).await
+    api_token_handler.create(req, http_req, user).await
 }
 
 #[utoipa::path(
     get,
-    path = "/api/v1/wishlist",
-    tag = "Wishlist",
-    params(
-        ("priority" = Option<String>, Query, description = "Filter by priority (low, medium, high)"),
-        ("limit" = Option<i64>, Query, description = "Maximum number of results")
-    ),
+    path = "/api/v1/auth/tokens",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "List of wishlist items", body = Vec<WishlistItemResponse>),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 200, description = "API tokens, newest first", body = [ApiTokenResponse]),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Called with an API token", body = AppError)
     )
 )]
-async fn get_wishlist_items(
-    wishlist_handler: web::Data<WishlistHandler>,
-    query: web::Query<crate::domain::wishlist::WishlistQuery>,
-    session: actix_session::Session,
+async fn get_api_tokens(
+    api_token_handler: web::Data<ApiTokenHandler>,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.get_items(query, session).await
+    api_token_handler.get_tokens(user).await
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/wishlist/{id}",
-    tag = "Wishlist",
+    delete,
+    path = "/api/v1/auth/tokens/{id}",
+    tag = "Authentication",
     params(
-        ("id" = i32, Path, description = "Wishlist item ID")
+        ("id" = i32, Path, description = "API token ID")
     ),
     responses(
-        (status = 200, description = "Wishlist item details", body = WishlistItemResponse),
+        (status = 200, description = "API token revoked"),
         (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Wishlist item not found", body = AppError)
+        (status = 403, description = "Called with an API token", body = AppError),
+        (status = 404, description = "API token not found", body = AppError)
     )
 )]
-async fn get_wishlist_item(
-    wishlist_handler: web::Data<WishlistHandler>,
+async fn revoke_api_token(
+    api_token_handler: web::Data<ApiTokenHandler>,
     path: web::Path<i32>,
-    session: actix_session::Session,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.get_item(path, session).await
+    api_token_handler.revoke(path, http_req, user).await
 }
 
+// Two-factor handlers
 #[utoipa::path(
-    put,
-    path = "/api/v1/wishlist/{id}",
-    tag = "Wishlist",
-    params(
-        ("id" = i32, Path, description = "Wishlist item ID")
-    ),
-    request_body = UpdateWishlistRequest,
+    get,
+    path = "/api/v1/auth/2fa",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "Wishlist item updated successfully", body = WishlistItemResponse),
-        (status = 400, description = "Validation error", body = AppError),
+        (status = 200, description = "Two-factor status", body = TwoFactorStatusResponse),
         (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Wishlist item not found", body = AppError)
+        (status = 403, description = "Called with an API token", body = AppError)
     )
 )]
-async fn update_wishlist_item(
-    wishlist_handler: web::Data<WishlistHandler>,
-    path: web::Path<i32>,
-    req: web::Json<crate::domain::wishlist::UpdateWishlistRequest>,
-    session: actix_session::Session,
+async fn get_two_factor_status(
+    two_factor_handler: web::Data<TwoFactorHandler>,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.update(path, req, session).await
+    two_factor_handler.status(user).await
 }
 
 #[utoipa::path(
-    delete,
-    path = "/api/v1/wishlist/{id}",
-    tag = "Wishlist",
-    params(
-        ("id" = i32, Path, description = "Wishlist item ID")
-    ),
+    post,
+    path = "/api/v1/auth/2fa/setup",
+    tag = "Authentication",
     responses(
-        (status = 200, description = "Wishlist item deleted successfully"),
+        (status = 200, description = "New secret to add to an authenticator app", body = TwoFactorSetupResponse),
         (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Wishlist item not found", body = AppError)
+        (status = 403, description = "Called with an API token", body = AppError),
+        (status = 409, description = "Two-factor authentication already enabled", body = AppError)
     )
 )]
-async fn delete_wishlist_item(
-    wishlist_handler: web::Data<WishlistHandler>,
-    path: web::Path<i32>,
-    session: actix_session::Session,
+async fn setup_two_factor(
+    two_factor_handler: web::Data<TwoFactorHandler>,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.delete(path, session).await
+    two_factor_handler.setup(user).await
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/wishlist/count",
-    tag = "Wishlist",
+    post,
+    path = "/api/v1/auth/2fa/enable",
+    tag = "Authentication",
+    request_body = TwoFactorCodeRequest,
     responses(
-        (status = 200, description = "Wishlist item count", body = WishlistCountResponse),
-        (status = 401, description = "Not authenticated", body = AppError)
+        (status = 200, description = "Two-factor authentication enabled, with recovery codes shown only once", body = RecoveryCodesResponse),
+        (status = 400, description = "No setup started, or invalid code", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Called with an API token", body = AppError),
+        (status = 409, description = "Two-factor authentication already enabled", body = AppError)
     )
 )]
-async fn get_wishlist_count(
-    wishlist_handler: web::Data<WishlistHandler>,
-    session: actix_session::Session,
+async fn enable_two_factor(
+    two_factor_handler: web::Data<TwoFactorHandler>,
+    req: web::Json<crate::domain::two_factor::TwoFactorCodeRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.get_count(session).await
+    two_factor_handler.enable(req, http_req, user).await
 }
 
 #[utoipa::path(
     post,
-    path = "/api/v1/wishlist/{id}/promote",
-    tag = "Wishlist",
-    params(
-        ("id" = i32, Path, description = "Wishlist item ID")
-    ),
+    path = "/api/v1/auth/2fa/disable",
+    tag = "Authentication",
+    request_body = TwoFactorCodeRequest,
     responses(
-        (status = 200, description = "Wishlist item promoted to restaurant successfully", body = RestaurantResponse),
+        (status = 200, description = "Two-factor authentication disabled"),
+        (status = 400, description = "Two-factor authentication not enabled", body = AppError),
         (status = 401, description = "Not authenticated", body = AppError),
-        (status = 404, description = "Wishlist item not found", body = AppError)
+        (status = 403, description = "Invalid code, account locked after too many, or called with an API token", body = AppError)
     )
 )]
-async fn promote_wishlist_item(
-    wishlist_handler: web::Data<WishlistHandler>,
-    path: web::Path<i32>,
-    session: actix_session::Session,
-) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.promote(path, session).await
-}
-
-async fn get_items_by_priority(
-    wishlist_handler: web::Data<WishlistHandler>,
-    path: web::Path<String>,
-    session: actix_session::Session,
+async fn disable_two_factor(
+    two_factor_handler: web::Data<TwoFactorHandler>,
+    req: web::Json<crate::domain::two_factor::TwoFactorCodeRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    wishlist_handler.get_by_priority(path, session).await
-}
-
-fn autocomplete_routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(
-        web::scope("/autocomplete")
-            .route("/restaurants", web::post().to(search_restaurants_autocomplete))
-            .route("/wishlist", web::post().to(search_wishlist_autocomplete))
-    );
+    two_factor_handler.disable(req, http_req, user).await
 }
 
 #[utoipa::path(
     post,
-    path = "/api/v1/autocomplete/restaurants",
-    tag = "Autocomplete",
-    request_body = AutocompleteRequest,
+    path = "/api/v1/auth/2fa/recovery-codes",
+    tag = "Authentication",
+    request_body = TwoFactorCodeRequest,
     responses(
-        (status = 200, description = "Restaurant suggestions", body = AutocompleteResponse),
-        (status = 400, description = "Validation error", body = AppError)
+        (status = 200, description = "New recovery codes replacing the old ones", body = RecoveryCodesResponse),
+        (status = 400, description = "Two-factor authentication not enabled", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Invalid code, account locked after too many, or called with an API token", body = AppError)
     )
 )]
-async fn search_restaurants_autocomplete(
-    autocomplete_handler: web::Data<AutocompleteHandler>,
-    req: web::Json<AutocompleteRequest>,
+async fn regenerate_recovery_codes(
+    two_factor_handler: web::Data<TwoFactorHandler>,
+    req: web::Json<crate::domain::two_factor::TwoFactorCodeRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    autocomplete_handler.search_restaurants(req).await
+    two_factor_handler.regenerate_recovery_codes(req, http_req, user).await
 }
 
 #[utoipa::path(
-    post,
-    path = "/api/v1/autocomplete/wishlist",
-    tag = "Autocomplete",
+    get,
+    path = "/api/v1/auth/security-events",
+    tag = "Authentication",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (defaults to 20, at most 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor returned as next_cursor by the previous page")
+    ),
+    responses(
+        (status = 200, description = "Logins, failed logins, password changes, deletes and other security events of the current user, newest first", body = SecurityEventPage),
+        (status = 400, description = "Invalid limit or cursor", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Called with an API token", body = AppError)
+    )
+)]
+async fn get_security_events(
+    audit_handler: web::Data<AuditHandler>,
+    query: web::Query<crate::domain::audit::SecurityEventQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    audit_handler.list_events(query, user).await
+}
+
+// Account handlers
+#[utoipa::path(
+    put,
+    path = "/api/v1/account/password",
+    tag = "Account",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed, other sessions logged out"),
+        (status = 400, description = "New password refused by the password policy", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Wrong current password, account locked after too many, or called with an API token", body = AppError)
+    )
+)]
+async fn change_password(
+    user_handler: web::Data<UserHandler>,
+    req: web::Json<crate::domain::user::ChangePasswordRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
+    session: actix_session::Session,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    user_handler.change_password(req, http_req, user, session).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/account/username",
+    tag = "Account",
+    request_body = ChangeUsernameRequest,
+    responses(
+        (status = 200, description = "Username changed", body = UserResponse),
+        (status = 400, description = "Invalid username", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Called with an API token", body = AppError),
+        (status = 409, description = "Username already exists", body = AppError)
+    )
+)]
+async fn change_username(
+    user_handler: web::Data<UserHandler>,
+    req: web::Json<crate::domain::user::ChangeUsernameRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
+    session: actix_session::Session,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    user_handler.change_username(req, http_req, user, session).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/account/email",
+    tag = "Account",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 200, description = "Email changed, a verification link was sent to it", body = UserResponse),
+        (status = 400, description = "Invalid email", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Wrong current password, account locked after too many, or called with an API token", body = AppError),
+        (status = 409, description = "Email already registered", body = AppError)
+    )
+)]
+async fn change_email(
+    user_handler: web::Data<UserHandler>,
+    req: web::Json<crate::domain::user::ChangeEmailRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    user_handler.change_email(req, http_req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/account",
+    tag = "Account",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 200, description = "Account and all its data deleted"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Wrong password, account locked after too many, or called with an API token", body = AppError)
+    )
+)]
+async fn delete_account(
+    user_handler: web::Data<UserHandler>,
+    req: web::Json<crate::domain::user::DeleteAccountRequest>,
+    http_req: actix_web::HttpRequest,
+    user: AuthenticatedUser,
+    session: actix_session::Session,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    user_handler.delete_account(req, http_req, user, session).await
+}
+
+// Restaurant handlers
+#[utoipa::path(
+    post,
+    path = "/api/v1/restaurants",
+    tag = "Restaurants",
+    request_body = CreateRestaurantRequest,
+    responses(
+        (status = 201, description = "Restaurant created successfully", body = RestaurantResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn create_restaurant(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    req: web::Json<crate::domain::restaurant::CreateRestaurantRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.create(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants",
+    tag = "Restaurants",
+    params(
+        ("favorites" = Option<bool>, Query, description = "Filter by favorite restaurants only"),
+        ("limit" = Option<i64>, Query, description = "Page size (1-100, defaults to 20)"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tag IDs, restaurants must carry all of them"),
+        ("sort" = Option<RestaurantSort>, Query, description = "Sort field: name, city, rating, created_at (default) or updated_at"),
+        ("direction" = Option<SortDirection>, Query, description = "Sort direction: asc or desc (defaults to asc for name and city, desc otherwise)"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from the previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "One page of restaurants", body = RestaurantPage),
+        (status = 400, description = "Invalid filter, sort or cursor", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_restaurants(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    query: web::Query<crate::domain::restaurant::RestaurantQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.get_restaurants(query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/{id}",
+    tag = "Restaurants",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    responses(
+        (status = 200, description = "Restaurant details", body = RestaurantResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn get_restaurant(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.get_restaurant(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/restaurants/{id}",
+    tag = "Restaurants",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    request_body = UpdateRestaurantRequest,
+    responses(
+        (status = 200, description = "Restaurant updated successfully", body = RestaurantResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn update_restaurant(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::restaurant::UpdateRestaurantRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.update(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/restaurants/{id}",
+    tag = "Restaurants",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    responses(
+        (status = 200, description = "Restaurant deleted successfully"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn delete_restaurant(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.delete(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/stats",
+    tag = "Restaurants",
+    responses(
+        (status = 200, description = "Collection statistics: totals, rating histogram, cities, monthly additions and wishlist conversion", body = RestaurantStatsResponse),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_restaurant_stats(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.get_stats(user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/restaurants/search",
+    tag = "Restaurants",
+    request_body = RestaurantSearchRequest,
+    responses(
+        (status = 200, description = "Search results ranked by relevance", body = RestaurantSearchResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn search_restaurants(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    req: web::Json<crate::domain::restaurant::RestaurantSearchRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.search(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/nearby",
+    tag = "Restaurants",
+    params(
+        ("lat" = f64, Query, description = "Latitude of the center point"),
+        ("lon" = f64, Query, description = "Longitude of the center point"),
+        ("radius_km" = Option<f64>, Query, description = "Search radius in kilometers (defaults to 5, at most 200)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, defaults to 50)")
+    ),
+    responses(
+        (status = 200, description = "Restaurants within the radius, closest first", body = Vec<NearbyRestaurantResponse>),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_restaurants_nearby(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    query: web::Query<crate::domain::geo::NearbyQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.get_nearby(query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/within",
+    tag = "Restaurants",
+    params(
+        ("min_lat" = f64, Query, description = "Southern edge of the box"),
+        ("min_lon" = f64, Query, description = "Western edge of the box (greater than max_lon when crossing the antimeridian)"),
+        ("max_lat" = f64, Query, description = "Northern edge of the box"),
+        ("max_lon" = f64, Query, description = "Eastern edge of the box"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, defaults to 50)")
+    ),
+    responses(
+        (status = 200, description = "Restaurants inside the bounding box", body = Vec<RestaurantResponse>),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_restaurants_within(
+    restaurant_handler: web::Data<RestaurantHandler>,
+    query: web::Query<crate::domain::geo::WithinQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    restaurant_handler.get_within(query, user).await
+}
+
+// Visit handlers
+#[utoipa::path(
+    post,
+    path = "/api/v1/restaurants/{id}/visits",
+    tag = "Visits",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    request_body = CreateVisitRequest,
+    responses(
+        (status = 201, description = "Visit logged successfully", body = VisitResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn create_visit(
+    visit_handler: web::Data<VisitHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::visit::CreateVisitRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    visit_handler.create(path, req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/{id}/visits",
+    tag = "Visits",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    responses(
+        (status = 200, description = "Visits to the restaurant, most recent first", body = Vec<VisitResponse>),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn get_visits(
+    visit_handler: web::Data<VisitHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    visit_handler.get_visits(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/{id}/visits/{visit_id}",
+    tag = "Visits",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID"),
+        ("visit_id" = i32, Path, description = "Visit ID")
+    ),
+    responses(
+        (status = 200, description = "Visit details", body = VisitResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Visit not found", body = AppError)
+    )
+)]
+async fn get_visit(
+    visit_handler: web::Data<VisitHandler>,
+    path: web::Path<(i32, i32)>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    visit_handler.get_visit(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/restaurants/{id}/visits/{visit_id}",
+    tag = "Visits",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID"),
+        ("visit_id" = i32, Path, description = "Visit ID")
+    ),
+    request_body = UpdateVisitRequest,
+    responses(
+        (status = 200, description = "Visit updated successfully", body = VisitResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Visit not found", body = AppError)
+    )
+)]
+async fn update_visit(
+    visit_handler: web::Data<VisitHandler>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<crate::domain::visit::UpdateVisitRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    visit_handler.update(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/restaurants/{id}/visits/{visit_id}",
+    tag = "Visits",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID"),
+        ("visit_id" = i32, Path, description = "Visit ID")
+    ),
+    responses(
+        (status = 200, description = "Visit deleted successfully"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Visit not found", body = AppError)
+    )
+)]
+async fn delete_visit(
+    visit_handler: web::Data<VisitHandler>,
+    path: web::Path<(i32, i32)>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    visit_handler.delete(path, user).await
+}
+
+// Wishlist handlers
+#[utoipa::path(
+    post,
+    path = "/api/v1/wishlist",
+    tag = "Wishlist",
+    request_body = CreateWishlistRequest,
+    responses(
+        (status = 201, description = "Wishlist item created successfully", body = WishlistItemResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn create_wishlist_item(
+    wishlist_handler: web::Data<WishlistHandler>,
+    req: web::Json<crate::domain::wishlist::CreateWishlistRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.create(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist",
+    tag = "Wishlist",
+    params(
+        ("priority" = Option<String>, Query, description = "Filter by priority (low, medium, high)"),
+        ("limit" = Option<i64>, Query, description = "Page size (1-100, defaults to 20)"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tag IDs, items must carry all of them"),
+        ("sort" = Option<WishlistSort>, Query, description = "Sort field: name, city, priority (default), created_at or updated_at"),
+        ("direction" = Option<SortDirection>, Query, description = "Sort direction: asc or desc (defaults to asc for name and city, desc otherwise)"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from the previous page's next_cursor")
+    ),
+    responses(
+        (status = 200, description = "One page of wishlist items", body = WishlistPage),
+        (status = 400, description = "Invalid filter, sort or cursor", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_wishlist_items(
+    wishlist_handler: web::Data<WishlistHandler>,
+    query: web::Query<crate::domain::wishlist::WishlistQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_items(query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist/{id}",
+    tag = "Wishlist",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    responses(
+        (status = 200, description = "Wishlist item details", body = WishlistItemResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn get_wishlist_item(
+    wishlist_handler: web::Data<WishlistHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_item(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/wishlist/{id}",
+    tag = "Wishlist",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    request_body = UpdateWishlistRequest,
+    responses(
+        (status = 200, description = "Wishlist item updated successfully", body = WishlistItemResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn update_wishlist_item(
+    wishlist_handler: web::Data<WishlistHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::wishlist::UpdateWishlistRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.update(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/wishlist/{id}",
+    tag = "Wishlist",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    responses(
+        (status = 200, description = "Wishlist item deleted successfully"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn delete_wishlist_item(
+    wishlist_handler: web::Data<WishlistHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.delete(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist/count",
+    tag = "Wishlist",
+    responses(
+        (status = 200, description = "Wishlist item count", body = WishlistCountResponse),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_wishlist_count(
+    wishlist_handler: web::Data<WishlistHandler>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_count(user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist/nearby",
+    tag = "Wishlist",
+    params(
+        ("lat" = f64, Query, description = "Latitude of the center point"),
+        ("lon" = f64, Query, description = "Longitude of the center point"),
+        ("radius_km" = Option<f64>, Query, description = "Search radius in kilometers (defaults to 5, at most 200)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, defaults to 50)")
+    ),
+    responses(
+        (status = 200, description = "Wishlist items within the radius, closest first", body = Vec<NearbyWishlistItemResponse>),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_wishlist_items_nearby(
+    wishlist_handler: web::Data<WishlistHandler>,
+    query: web::Query<crate::domain::geo::NearbyQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_nearby(query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist/within",
+    tag = "Wishlist",
+    params(
+        ("min_lat" = f64, Query, description = "Southern edge of the box"),
+        ("min_lon" = f64, Query, description = "Western edge of the box (greater than max_lon when crossing the antimeridian)"),
+        ("max_lat" = f64, Query, description = "Northern edge of the box"),
+        ("max_lon" = f64, Query, description = "Eastern edge of the box"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, defaults to 50)")
+    ),
+    responses(
+        (status = 200, description = "Wishlist items inside the bounding box", body = Vec<WishlistItemResponse>),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_wishlist_items_within(
+    wishlist_handler: web::Data<WishlistHandler>,
+    query: web::Query<crate::domain::geo::WithinQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_within(query, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/wishlist/{id}/promote",
+    tag = "Wishlist",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    responses(
+        (status = 200, description = "Wishlist item promoted to restaurant successfully", body = RestaurantResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn promote_wishlist_item(
+    wishlist_handler: web::Data<WishlistHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.promote(path, user).await
+}
+
+// Tag handlers
+#[utoipa::path(
+    post,
+    path = "/api/v1/tags",
+    tag = "Tags",
+    request_body = CreateTagRequest,
+    responses(
+        (status = 201, description = "Tag created successfully", body = TagResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 409, description = "Tag already exists", body = AppError)
+    )
+)]
+async fn create_tag(
+    tag_handler: web::Data<TagHandler>,
+    req: web::Json<crate::domain::tag::CreateTagRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.create(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "List of tags", body = Vec<TagResponse>),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_tags(
+    tag_handler: web::Data<TagHandler>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.get_tags(user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/tags/{id}",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Tag ID")
+    ),
+    request_body = UpdateTagRequest,
+    responses(
+        (status = 200, description = "Tag updated successfully", body = TagResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Tag not found", body = AppError),
+        (status = 409, description = "Tag already exists", body = AppError)
+    )
+)]
+async fn update_tag(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::tag::UpdateTagRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.update(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tags/{id}",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Tag ID")
+    ),
+    responses(
+        (status = 200, description = "Tag deleted successfully"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Tag not found", body = AppError)
+    )
+)]
+async fn delete_tag(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.delete(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/restaurants/{id}/tags",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    responses(
+        (status = 200, description = "Tags of the restaurant", body = Vec<TagResponse>),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn get_restaurant_tags(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.get_restaurant_tags(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/restaurants/{id}/tags",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Restaurant ID")
+    ),
+    request_body = SetTagsRequest,
+    responses(
+        (status = 200, description = "Tags of the restaurant replaced", body = Vec<TagResponse>),
+        (status = 400, description = "Unknown tag", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Restaurant not found", body = AppError)
+    )
+)]
+async fn set_restaurant_tags(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::tag::SetTagsRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.set_restaurant_tags(path, req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/wishlist/{id}/tags",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    responses(
+        (status = 200, description = "Tags of the wishlist item", body = Vec<TagResponse>),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn get_wishlist_item_tags(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.get_wishlist_item_tags(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/wishlist/{id}/tags",
+    tag = "Tags",
+    params(
+        ("id" = i32, Path, description = "Wishlist item ID")
+    ),
+    request_body = SetTagsRequest,
+    responses(
+        (status = 200, description = "Tags of the wishlist item replaced", body = Vec<TagResponse>),
+        (status = 400, description = "Unknown tag", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "Wishlist item not found", body = AppError)
+    )
+)]
+async fn set_wishlist_item_tags(
+    tag_handler: web::Data<TagHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::tag::SetTagsRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    tag_handler.set_wishlist_item_tags(path, req, user).await
+}
+
+async fn get_items_by_priority(
+    wishlist_handler: web::Data<WishlistHandler>,
+    path: web::Path<String>,
+    query: web::Query<crate::domain::wishlist::WishlistQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    wishlist_handler.get_by_priority(path, query, user).await
+}
+
+// List handlers
+#[utoipa::path(
+    post,
+    path = "/api/v1/lists",
+    tag = "Lists",
+    request_body = CreateListRequest,
+    responses(
+        (status = 201, description = "List created successfully", body = ListResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn create_list(
+    list_handler: web::Data<ListHandler>,
+    req: web::Json<crate::domain::list::CreateListRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.create(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/lists",
+    tag = "Lists",
+    responses(
+        (status = 200, description = "Lists of the user, most recently updated first", body = Vec<ListResponse>),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_lists(
+    list_handler: web::Data<ListHandler>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.get_lists(user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/lists/{id}",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "List with its entries in order", body = ListDetailResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError)
+    )
+)]
+async fn get_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.get_list(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/lists/{id}",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    request_body = UpdateListRequest,
+    responses(
+        (status = 200, description = "List updated successfully", body = ListResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError)
+    )
+)]
+async fn update_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::list::UpdateListRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.update(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/lists/{id}",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "List deleted successfully"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError)
+    )
+)]
+async fn delete_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.delete(path, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/lists/{id}/share",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "List published under a new share token (links shared earlier stop working)", body = ListResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError)
+    )
+)]
+async fn share_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.share(path, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/lists/{id}/share",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "List is no longer public", body = ListResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError)
+    )
+)]
+async fn unshare_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.unshare(path, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/lists/{id}/order",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    request_body = ReorderListRequest,
+    responses(
+        (status = 200, description = "Entries reordered", body = ListDetailResponse),
+        (status = 400, description = "entry_ids does not list every entry exactly once", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List not found", body = AppError),
+        (status = 409, description = "The list changed concurrently", body = AppError)
+    )
+)]
+async fn reorder_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::list::ReorderListRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.reorder(path, req, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/lists/{id}/entries",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID")
+    ),
+    request_body = AddListEntryRequest,
+    responses(
+        (status = 201, description = "Entry added", body = ListEntryResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List, restaurant or wishlist item not found", body = AppError),
+        (status = 409, description = "Already in this list", body = AppError)
+    )
+)]
+async fn add_list_entry(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<i32>,
+    req: web::Json<crate::domain::list::AddListEntryRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.add_entry(path, req, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/lists/{id}/entries/{entry_id}",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID"),
+        ("entry_id" = i32, Path, description = "Entry ID")
+    ),
+    request_body = UpdateListEntryRequest,
+    responses(
+        (status = 200, description = "Entry updated", body = ListEntryResponse),
+        (status = 400, description = "Validation error", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List or entry not found", body = AppError)
+    )
+)]
+async fn update_list_entry(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<crate::domain::list::UpdateListEntryRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.update_entry(path, req, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/lists/{id}/entries/{entry_id}",
+    tag = "Lists",
+    params(
+        ("id" = i32, Path, description = "List ID"),
+        ("entry_id" = i32, Path, description = "Entry ID")
+    ),
+    responses(
+        (status = 200, description = "Entry removed"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "List or entry not found", body = AppError)
+    )
+)]
+async fn remove_list_entry(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<(i32, i32)>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.remove_entry(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/public/lists/{token}",
+    tag = "Lists",
+    params(
+        ("token" = String, Path, description = "Share token of a published list")
+    ),
+    responses(
+        (status = 200, description = "Published list, readable without logging in", body = PublicListResponse),
+        (status = 404, description = "No list is published under this token", body = AppError)
+    ),
+    security(())
+)]
+async fn get_public_list(
+    list_handler: web::Data<ListHandler>,
+    path: web::Path<String>,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    list_handler.get_public_list(path).await
+}
+
+// Social handlers
+#[utoipa::path(
+    get,
+    path = "/api/v1/feed",
+    tag = "Social",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (defaults to 20, at most 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor returned as next_cursor by the previous page")
+    ),
+    responses(
+        (status = 200, description = "Recent activity of followed public users, newest first", body = ActivityPage),
+        (status = 400, description = "Invalid limit or cursor", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_feed(
+    social_handler: web::Data<SocialHandler>,
+    query: web::Query<crate::domain::social::SocialPageQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_feed(query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/settings/privacy",
+    tag = "Social",
+    responses(
+        (status = 200, description = "Current privacy setting", body = PrivacySettingsResponse),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn get_privacy_settings(
+    social_handler: web::Data<SocialHandler>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_privacy(user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/settings/privacy",
+    tag = "Social",
+    request_body = PrivacySettingsRequest,
+    responses(
+        (status = 200, description = "Privacy setting updated", body = PrivacySettingsResponse),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn update_privacy_settings(
+    social_handler: web::Data<SocialHandler>,
+    req: web::Json<crate::domain::social::PrivacySettingsRequest>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.set_privacy(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{username}",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User profile", body = UserProfileResponse),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "User not found", body = AppError)
+    )
+)]
+async fn get_user_profile(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_profile(path, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{username}/follow",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User followed", body = UserProfileResponse),
+        (status = 400, description = "Cannot follow yourself", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Profile is private", body = AppError),
+        (status = 404, description = "User not found", body = AppError)
+    )
+)]
+async fn follow_user(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.follow(path, user).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{username}/follow",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User unfollowed"),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 404, description = "User not found or not followed", body = AppError)
+    )
+)]
+async fn unfollow_user(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.unfollow(path, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{username}/followers",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (defaults to 20, at most 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor returned as next_cursor by the previous page")
+    ),
+    responses(
+        (status = 200, description = "Followers, most recent first", body = FollowPage),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Profile is private", body = AppError),
+        (status = 404, description = "User not found", body = AppError)
+    )
+)]
+async fn get_user_followers(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    query: web::Query<crate::domain::social::SocialPageQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_followers(path, query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{username}/following",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (defaults to 20, at most 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor returned as next_cursor by the previous page")
+    ),
+    responses(
+        (status = 200, description = "Followed users, most recent first", body = FollowPage),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Profile is private", body = AppError),
+        (status = 404, description = "User not found", body = AppError)
+    )
+)]
+async fn get_user_following(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    query: web::Query<crate::domain::social::SocialPageQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_following(path, query, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{username}/activity",
+    tag = "Social",
+    params(
+        ("username" = String, Path, description = "Username"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (defaults to 20, at most 100)"),
+        ("cursor" = Option<String>, Query, description = "Cursor returned as next_cursor by the previous page")
+    ),
+    responses(
+        (status = 200, description = "Recent activity of the user, newest first", body = ActivityPage),
+        (status = 401, description = "Not authenticated", body = AppError),
+        (status = 403, description = "Profile is private", body = AppError),
+        (status = 404, description = "User not found", body = AppError)
+    )
+)]
+async fn get_user_activity(
+    social_handler: web::Data<SocialHandler>,
+    path: web::Path<String>,
+    query: web::Query<crate::domain::social::SocialPageQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    social_handler.get_user_activity(path, query, user).await
+}
+
+// Import/export handlers
+#[utoipa::path(
+    get,
+    path = "/api/v1/export",
+    tag = "Import/Export",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "Output format: json (default) or csv")
+    ),
+    responses(
+        (status = 200, description = "Account metadata, restaurants and wishlist items, streamed as an attachment. CSV exports hold one row per account, restaurant and wishlist item", content(
+            ("application/json" = ExportDocument),
+            ("text/csv" = String)
+        )),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn export_collection(
+    export_handler: web::Data<ExportHandler>,
+    query: web::Query<crate::domain::export::ExportQuery>,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    export_handler.export(query, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/import",
+    tag = "Import/Export",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Validate and report without saving anything"),
+        ("target" = Option<ImportTarget>, Query, description = "Collection for rows that do not name one: restaurants (default) or wishlist")
+    ),
+    request_body(content = ImportUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Per-row report of created, skipped (duplicate name and city) and failed rows", body = ImportReport),
+        (status = 400, description = "Missing, oversized or unreadable file", body = AppError),
+        (status = 401, description = "Not authenticated", body = AppError)
+    )
+)]
+async fn import_collection(
+    import_handler: web::Data<ImportHandler>,
+    query: web::Query<crate::domain::import::ImportQuery>,
+    payload: actix_multipart::Multipart,
+    user: AuthenticatedUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    import_handler.import(query, payload, user).await
+}
+
+fn autocomplete_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/autocomplete")
+            .route("/restaurants", web::post().to(search_restaurants_autocomplete))
+            .route("/wishlist", web::post().to(search_wishlist_autocomplete))
+            .route("/cache", web::get().to(get_autocomplete_cache_stats))
+    );
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/autocomplete/restaurants",
+    tag = "Autocomplete",
+    request_body = AutocompleteRequest,
+    responses(
+        (status = 200, description = "Restaurant suggestions", body = AutocompleteResponse),
+        (status = 400, description = "Validation error", body = AppError)
+    )
+)]
+async fn search_restaurants_autocomplete(
+    autocomplete_handler: web::Data<AutocompleteHandler>,
+    req: web::Json<AutocompleteRequest>,
+    user: OptionalUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    autocomplete_handler.search_restaurants(req, user).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/autocomplete/wishlist",
+    tag = "Autocomplete",
     request_body = AutocompleteRequest,
     responses(
         (status = 200, description = "Wishlist suggestions", body = AutocompleteResponse),
@@ -486,6 +1916,21 @@ async fn search_restaurants_autocomplete(
 async fn search_wishlist_autocomplete(
     autocomplete_handler: web::Data<AutocompleteHandler>,
     req: web::Json<AutocompleteRequest>,
+    user: OptionalUser,
+) -> crate::error::Result<actix_web::HttpResponse> {
+    autocomplete_handler.search_wishlist(req, user).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/autocomplete/cache",
+    tag = "Autocomplete",
+    responses(
+        (status = 200, description = "Autocomplete cache counters since the server started", body = AutocompleteCacheStats)
+    )
+)]
+async fn get_autocomplete_cache_stats(
+    autocomplete_handler: web::Data<AutocompleteHandler>,
 ) -> crate::error::Result<actix_web::HttpResponse> {
-    autocomplete_handler.search_wishlist(req).await
-}
\ No newline at end of file
+    autocomplete_handler.cache_stats().await
+}
@@ -0,0 +1,93 @@
+use crate::domain::api_token::{
+    generate_api_token, ApiToken, ApiTokenId, ApiTokenResponse, CreateApiTokenRequest,
+    CreatedApiTokenResponse, MAX_API_TOKENS,
+};
+use crate::domain::token::hash_token;
+use crate::domain::user::{User, UserId};
+use crate::domain::session::ClientInfo;
+use crate::domain::audit::SecurityEventType;
+use crate::repositories::api_token_repository::ApiTokenRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::services::audit_service::AuditService;
+use crate::error::{AppError, Result};
+use serde_json::json;
+use std::sync::Arc;
+
+pub struct ApiTokenService {
+    repo: Arc<dyn ApiTokenRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    audit: Arc<AuditService>,
+}
+
+impl ApiTokenService {
+    pub fn new(
+        repo: Arc<dyn ApiTokenRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        audit: Arc<AuditService>,
+    ) -> Self {
+        Self { repo, user_repo, audit }
+    }
+
+    /// Create a token. The response is the only place the token appears.
+    pub async fn create_token(
+        &self,
+        request: CreateApiTokenRequest,
+        user_id: UserId,
+        client: &ClientInfo,
+    ) -> Result<CreatedApiTokenResponse> {
+        if self.repo.find_by_user(user_id).await?.len() >= MAX_API_TOKENS {
+            return Err(AppError::Validation(format!(
+                "A user can have at most {} API tokens",
+                MAX_API_TOKENS
+            )));
+        }
+
+        let token = generate_api_token();
+        let command = request.to_command(user_id, &token)?;
+        let api_token = self.repo.create(command).await?;
+
+        let details = json!({"token_id": api_token.id.0, "name": api_token.name});
+        self.audit.record(Some(user_id), SecurityEventType::ApiTokenCreated, client, Some(details)).await;
+
+        Ok(CreatedApiTokenResponse {
+            token,
+            api_token: ApiTokenResponse::from(api_token),
+        })
+    }
+
+    /// Get all tokens of a user, newest first
+    pub async fn get_tokens(&self, user_id: UserId) -> Result<Vec<ApiTokenResponse>> {
+        let tokens = self.repo.find_by_user(user_id).await?;
+        Ok(tokens.into_iter().map(ApiTokenResponse::from).collect())
+    }
+
+    /// Revoke a token
+    pub async fn revoke_token(&self, id: ApiTokenId, user_id: UserId, client: &ClientInfo) -> Result<()> {
+        if !self.repo.delete(id, user_id).await? {
+            return Err(AppError::NotFound("API token not found".into()));
+        }
+
+        let details = json!({"token_id": id.0});
+        self.audit.record(Some(user_id), SecurityEventType::ApiTokenRevoked, client, Some(details)).await;
+        Ok(())
+    }
+
+    /// Find the user of a bearer token. Unknown and expired tokens, and tokens
+    /// of deactivated accounts, are rejected.
+    pub async fn authenticate(&self, token: &str) -> Result<(User, ApiToken)> {
+        let api_token = self.repo
+            .find_valid_by_hash(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::Authentication("Invalid API token".into()))?;
+
+        let user = self.user_repo
+            .find_by_id(api_token.user_id)
+            .await?
+            .filter(|user| user.is_active)
+            .ok_or_else(|| AppError::Authentication("Invalid API token".into()))?;
+
+        self.repo.touch(api_token.id).await?;
+
+        Ok((user, api_token))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::domain::audit::{
+    RecordAuditEvent, SecurityEventQuery, SecurityEventResponse, SecurityEventType,
+};
+use crate::domain::pagination::CursorPage;
+use crate::domain::session::ClientInfo;
+use crate::domain::user::UserId;
+use crate::repositories::audit_repository::AuditRepository;
+use crate::error::Result;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Records security events in the `audit_log` table
+pub struct AuditService {
+    repo: Arc<dyn AuditRepository>,
+}
+
+impl AuditService {
+    pub fn new(repo: Arc<dyn AuditRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Record an event of a user. A failure is logged rather than returned,
+    /// so that auditing never breaks the action being audited.
+    pub async fn record(
+        &self,
+        user_id: Option<UserId>,
+        event_type: SecurityEventType,
+        client: &ClientInfo,
+        details: Option<serde_json::Value>,
+    ) {
+        let event = RecordAuditEvent {
+            user_id,
+            event_type,
+            details,
+            client: client.clone(),
+        };
+
+        if let Err(e) = self.repo.record(event).await {
+            warn!("Failed to record {:?} event of user {:?}: {}", event_type, user_id.map(|id| id.0), e);
+        }
+    }
+
+    /// Security history of a user, newest first
+    pub async fn list_events(&self, user_id: UserId, query: SecurityEventQuery) -> Result<CursorPage<SecurityEventResponse>> {
+        let page = query.page()?;
+        let events = self.repo.find_by_user(user_id, &page).await?;
+        Ok(events.map(SecurityEventResponse::from))
+    }
+}
@@ -1,56 +1,114 @@
-use crate::domain::user::{User, UserId, CreateUser, RegisterUserCommand, LoginCommand, RegisterResponse, UserInfoResponse};
-use crate::domain::session::{CreateSession, SessionData};
+use crate::domain::user::{
+    User, UserId, CreateUser, Email, RegisterUserCommand, LoginCommand, RegisterResponse,
+    UserInfoResponse,
+};
+use crate::domain::password::PasswordPolicy;
+use crate::domain::session::{ClientInfo, CreateSession, SessionData, SessionResponse};
+use crate::domain::user_token::{
+    CreateUserToken, UserTokenPurpose, VerifyEmailRequest, PasswordResetRequest,
+    PasswordResetConfirmRequest,
+};
+use crate::domain::two_factor::LoginTwoFactorRequest;
+use crate::domain::audit::SecurityEventType;
+use crate::domain::token::{generate_token, hash_token};
+use crate::domain::mail::MailMessage;
 use crate::repositories::user_repository::UserRepository;
 use crate::repositories::session_repository::SessionRepository;
+use crate::repositories::user_token_repository::UserTokenRepository;
+use crate::services::mailer::Mailer;
+use crate::services::password_hasher::PasswordHashing;
+use crate::services::two_factor_service::TwoFactorService;
+use crate::services::audit_service::AuditService;
 use crate::error::{AppError, Result};
 use crate::config::Config;
-use bcrypt::{hash, verify, DEFAULT_COST};
-use chrono::{DateTime, Utc, Duration};
+use chrono::{Utc, Duration};
+use serde_json::json;
 use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Result of checking a username and password
+#[derive(Debug)]
+pub enum LoginOutcome {
+    /// The login is complete and its session open
+    LoggedIn(SessionData, User),
+    /// The password was right but a two-factor code must be posted with the
+    /// challenge before a session is opened
+    TwoFactorRequired { challenge: String },
+}
 
 pub struct AuthService {
     user_repo: Arc<dyn UserRepository>,
     session_repo: Arc<dyn SessionRepository>,
+    token_repo: Arc<dyn UserTokenRepository>,
+    mailer: Arc<dyn Mailer>,
+    two_factor: Arc<TwoFactorService>,
+    audit: Arc<AuditService>,
+    password_hashing: PasswordHashing,
+    password_policy: PasswordPolicy,
     config: Config,
 }
 
 impl AuthService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repo: Arc<dyn UserRepository>,
         session_repo: Arc<dyn SessionRepository>,
+        token_repo: Arc<dyn UserTokenRepository>,
+        mailer: Arc<dyn Mailer>,
+        two_factor: Arc<TwoFactorService>,
+        audit: Arc<AuditService>,
+        password_hashing: PasswordHashing,
         config: Config,
     ) -> Self {
+        let password_policy = PasswordPolicy {
+            min_length: config.password_min_length,
+            reject_common: config.password_reject_common,
+            reject_username: config.password_reject_username,
+        };
+
         Self {
             user_repo,
             session_repo,
+            token_repo,
+            mailer,
+            two_factor,
+            audit,
+            password_hashing,
+            password_policy,
             config,
         }
     }
 
-    /// Register a new user
+    /// Register a new user and send them an email verification link
     pub async fn register(&self, command: RegisterUserCommand) -> Result<RegisterResponse> {
-        // Check if username already exists
-        if let Some(_) = self.user_repo.find_by_username(&command.username).await? {
+        let email = Email::new(command.email)?;
+
+        // Check if username or email already exists
+        if self.user_repo.find_by_username(&command.username).await?.is_some() {
             return Err(AppError::Conflict("Username already exists".into()));
         }
-
-        // Validate and hash password
-        if command.password.len() < 4 {
-            return Err(AppError::Validation("Password must be at least 4 characters long".into()));
+        if self.user_repo.find_by_email(email.as_str()).await?.is_some() {
+            return Err(AppError::Conflict("Email already registered".into()));
         }
 
-        let password_hash = hash(&command.password, self.config.bcrypt_cost)
-            .map_err(|_| AppError::Internal("Failed to hash password".into()))?;
+        // Validate and hash password
+        let password_hash = self.hash_new_password(command.password, &command.username).await?;
 
         // Create user
         let create_user = CreateUser {
             username: command.username.clone(),
-            email: format!("{}@example.com", command.username), // Simplified for now
+            email: email.into_string(),
             password_hash,
         };
 
         let user = self.user_repo.create(create_user).await?;
 
+        // The account works without a verified email, the link can be resent
+        if let Err(e) = self.send_verification(&user).await {
+            warn!("Failed to send verification email to user {}: {}", user.id.0, e);
+        }
+
         Ok(RegisterResponse {
             id: user.id.0,
             username: user.username,
@@ -58,49 +116,295 @@ impl AuthService {
         })
     }
 
-    /// Login a user
-    pub async fn login(&self, command: LoginCommand) -> Result<(SessionData, User)> {
-        // Find user by username
+    /// Email a fresh single-use link, replacing earlier ones for the same purpose
+    async fn send_token(&self, user: &User, purpose: UserTokenPurpose) -> Result<()> {
+        let token = generate_token();
+        self.token_repo
+            .replace(CreateUserToken {
+                user_id: user.id,
+                purpose,
+                token_hash: hash_token(&token),
+                expires_at: Utc::now() + purpose.lifetime(),
+            })
+            .await?;
+
+        let message = match purpose {
+            UserTokenPurpose::EmailVerification => {
+                let link = format!("{}/verify-email?token={}", self.config.app_base_url, token);
+                MailMessage::email_verification(&user.email, &user.username, &link)
+            }
+            UserTokenPurpose::PasswordReset => {
+                let link = format!("{}/reset-password?token={}", self.config.app_base_url, token);
+                MailMessage::password_reset(&user.email, &user.username, &link)
+            }
+            UserTokenPurpose::LoginChallenge => {
+                return Err(AppError::Internal("Login challenges are not emailed".into()));
+            }
+        };
+        self.mailer.send(message).await
+    }
+
+    /// Email a link to verify the user's current address
+    pub async fn send_verification(&self, user: &User) -> Result<()> {
+        self.send_token(user, UserTokenPurpose::EmailVerification).await
+    }
+
+    /// Send the email verification link again
+    pub async fn resend_verification(&self, user: &User) -> Result<()> {
+        if user.email_verified_at.is_some() {
+            return Err(AppError::Conflict("Email already verified".into()));
+        }
+        self.send_verification(user).await
+    }
+
+    /// Mark the email of a verification token's user as verified
+    pub async fn verify_email(&self, request: VerifyEmailRequest) -> Result<()> {
+        let user_id = self.token_repo
+            .consume(&hash_token(request.token.trim()), UserTokenPurpose::EmailVerification)
+            .await?
+            .ok_or_else(|| AppError::Validation("Invalid or expired verification token".into()))?;
+
+        self.user_repo.mark_email_verified(user_id).await
+    }
+
+    /// Email a password reset link. Unknown addresses are silently ignored so
+    /// the endpoint does not tell which emails have an account.
+    pub async fn request_password_reset(&self, request: PasswordResetRequest) -> Result<()> {
+        let email = Email::new(request.email)?;
         let user = self.user_repo
-            .find_by_username(&command.username)
+            .find_by_email(email.as_str())
             .await?
-            .ok_or_else(|| AppError::Authentication("Invalid credentials".into()))?;
+            .filter(|user| user.is_active);
 
-        // Check if account is locked
-        if let Some(locked_until) = user.account_locked_until {
-            if locked_until > Utc::now() {
-                return Err(AppError::Authentication("Account is temporarily locked".into()));
-            }
+        if let Some(user) = user
+            && let Err(e) = self.send_token(&user, UserTokenPurpose::PasswordReset).await
+        {
+            warn!("Failed to send password reset email to user {}: {}", user.id.0, e);
+        }
+
+        Ok(())
+    }
+
+    /// Set a new password with a reset token and log out every session
+    pub async fn confirm_password_reset(&self, request: PasswordResetConfirmRequest, client: ClientInfo) -> Result<()> {
+        let token_hash = hash_token(request.token.trim());
+        let invalid_token = || AppError::Validation("Invalid or expired reset token".into());
+
+        // Check the password before using up the token, so a refused password
+        // can be corrected with the same link
+        let user_id = self.token_repo
+            .find_valid(&token_hash, UserTokenPurpose::PasswordReset)
+            .await?
+            .ok_or_else(invalid_token)?;
+        let user = self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(invalid_token)?;
+        let password = self.password_policy.validate(request.new_password, &user.username)?;
+
+        if self.token_repo.consume(&token_hash, UserTokenPurpose::PasswordReset).await?.is_none() {
+            return Err(invalid_token());
         }
 
+        let password_hash = self.password_hashing.hash(password.as_str()).await?;
+        self.user_repo.update_password(user_id, &password_hash).await?;
+        // The link was opened from the mailbox, which proves the address
+        self.user_repo.mark_email_verified(user_id).await?;
+        self.session_repo.deactivate_all_for_user(user_id).await?;
+        self.audit.record(Some(user_id), SecurityEventType::PasswordReset, &client, None).await;
+
+        Ok(())
+    }
+
+    /// Confirm a sensitive change with the password in use. A wrong password
+    /// answers 403 rather than 401, as the login itself is still valid, and
+    /// counts towards the account lock like a failed login.
+    pub async fn check_password(&self, user: &User, password: &str, client: &ClientInfo) -> Result<()> {
+        self.ensure_change_not_locked(user, client).await?;
+
+        let check = self.password_hashing.verify(password, &user.password_hash).await?;
+        if !check.valid {
+            self.record_failed_login(user, client, "invalid_current_password").await?;
+            return Err(AppError::Authorization("Current password is incorrect".into()));
+        }
+
+        if user.failed_login_attempts > 0 {
+            self.user_repo.reset_failed_attempts(user.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Confirm a change to two-factor authentication with a code. Like the
+    /// current password, a wrong code answers 403 and counts towards the
+    /// account lock.
+    pub async fn check_two_factor_code(&self, user: &User, code: &str, client: &ClientInfo) -> Result<()> {
+        if !self.two_factor.is_enabled(user.id).await? {
+            return Err(AppError::Validation("Two-factor authentication is not enabled".into()));
+        }
+        self.ensure_change_not_locked(user, client).await?;
+
+        if !self.two_factor.verify(user.id, code).await? {
+            self.record_failed_login(user, client, "invalid_two_factor_code").await?;
+            return Err(AppError::Authorization("Invalid two-factor code".into()));
+        }
+
+        if user.failed_login_attempts > 0 {
+            self.user_repo.reset_failed_attempts(user.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Check a new password of a user against the policy and hash it
+    pub async fn hash_new_password(&self, password: String, username: &str) -> Result<String> {
+        let password = self.password_policy.validate(password, username)?;
+        self.password_hashing.hash(password.as_str()).await
+    }
+
+    /// Check a user's password. Users with two-factor authentication get a
+    /// challenge to complete with `login_two_factor`, others a session.
+    pub async fn login(&self, command: LoginCommand, client: ClientInfo) -> Result<LoginOutcome> {
+        // Find user by username
+        let Some(user) = self.user_repo.find_by_username(&command.username).await? else {
+            let details = json!({"reason": "unknown_user", "username": command.username});
+            self.audit.record(None, SecurityEventType::LoginFailed, &client, Some(details)).await;
+            return Err(AppError::Authentication("Invalid credentials".into()));
+        };
+
+        self.ensure_not_locked(&user, &client).await?;
+
         // Verify password
-        let password_valid = verify(&command.password, &user.password_hash)
-            .map_err(|_| AppError::Internal("Password verification failed".into()))?;
-
-        if !password_valid {
-            // Increment failed login attempts
-            self.user_repo.increment_failed_attempts(user.id).await?;
-            
-            // Lock account after 5 failed attempts
-            if user.failed_login_attempts >= 4 {
-                let lock_until = Utc::now() + Duration::minutes(15);
-                self.user_repo.lock_account(user.id, lock_until).await?;
-            }
-            
+        let check = self.password_hashing.verify(&command.password, &user.password_hash).await?;
+
+        if !check.valid {
+            self.record_failed_login(&user, &client, "invalid_password").await?;
             return Err(AppError::Authentication("Invalid credentials".into()));
         }
 
-        // Reset failed attempts and update last login
+        // Move the hash to the current algorithm and settings while the
+        // password is at hand
+        if check.needs_rehash {
+            match self.password_hashing.hash(&command.password).await {
+                Ok(password_hash) => self.user_repo.update_password(user.id, &password_hash).await?,
+                Err(e) => warn!("Failed to rehash password of user {}: {}", user.id.0, e),
+            }
+        }
+
+        if self.two_factor.is_enabled(user.id).await? {
+            let challenge = generate_token();
+            self.token_repo
+                .replace(CreateUserToken {
+                    user_id: user.id,
+                    purpose: UserTokenPurpose::LoginChallenge,
+                    token_hash: hash_token(&challenge),
+                    expires_at: Utc::now() + UserTokenPurpose::LoginChallenge.lifetime(),
+                })
+                .await?;
+
+            return Ok(LoginOutcome::TwoFactorRequired { challenge });
+        }
+
+        let session_data = self.complete_login(&user, client, false).await?;
+        Ok(LoginOutcome::LoggedIn(session_data, user))
+    }
+
+    /// Finish a login with the challenge it returned and a two-factor code.
+    /// Bad codes count towards the account lock like bad passwords.
+    pub async fn login_two_factor(&self, request: LoginTwoFactorRequest, client: ClientInfo) -> Result<(SessionData, User)> {
+        let token_hash = hash_token(request.challenge.trim());
+        let invalid_challenge = || AppError::Authentication("Invalid or expired login challenge".into());
+
+        let user_id = self.token_repo
+            .find_valid(&token_hash, UserTokenPurpose::LoginChallenge)
+            .await?
+            .ok_or_else(invalid_challenge)?;
+        let user = self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(invalid_challenge)?;
+
+        self.ensure_not_locked(&user, &client).await?;
+
+        if !self.two_factor.verify(user.id, &request.code).await? {
+            self.record_failed_login(&user, &client, "invalid_two_factor_code").await?;
+            return Err(AppError::Authentication("Invalid two-factor code".into()));
+        }
+
+        if self.token_repo.consume(&token_hash, UserTokenPurpose::LoginChallenge).await?.is_none() {
+            return Err(invalid_challenge());
+        }
+
+        let session_data = self.complete_login(&user, client, true).await?;
+        Ok((session_data, user))
+    }
+
+    /// Refuse logins to an account locked after failed logins
+    async fn ensure_not_locked(&self, user: &User, client: &ClientInfo) -> Result<()> {
+        if let Some(locked_until) = user.account_locked_until
+            && locked_until > Utc::now()
+        {
+            let details = json!({"reason": "account_locked"});
+            self.audit.record(Some(user.id), SecurityEventType::LoginFailed, client, Some(details)).await;
+            return Err(AppError::Authentication("Account is temporarily locked".into()));
+        }
+        Ok(())
+    }
+
+    /// Refuse sensitive changes to an account locked after failed logins,
+    /// with a 403 as the session itself is still valid
+    async fn ensure_change_not_locked(&self, user: &User, client: &ClientInfo) -> Result<()> {
+        if let Some(locked_until) = user.account_locked_until
+            && locked_until > Utc::now()
+        {
+            let details = json!({"reason": "account_locked"});
+            self.audit.record(Some(user.id), SecurityEventType::LoginFailed, client, Some(details)).await;
+            return Err(AppError::Authorization("Account is temporarily locked".into()));
+        }
+        Ok(())
+    }
+
+    /// Count a failed login, locking the account after 5 in a row
+    async fn record_failed_login(&self, user: &User, client: &ClientInfo, reason: &str) -> Result<()> {
+        self.user_repo.increment_failed_attempts(user.id).await?;
+        let details = json!({"reason": reason});
+        self.audit.record(Some(user.id), SecurityEventType::LoginFailed, client, Some(details)).await;
+
+        if user.failed_login_attempts >= 4 {
+            let lock_until = Utc::now() + Duration::minutes(15);
+            self.user_repo.lock_account(user.id, lock_until).await?;
+            let details = json!({"locked_until": lock_until});
+            self.audit.record(Some(user.id), SecurityEventType::AccountLocked, client, Some(details)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Reset the failed login count and open a session
+    async fn complete_login(&self, user: &User, client: ClientInfo, two_factor: bool) -> Result<SessionData> {
         self.user_repo.reset_failed_attempts(user.id).await?;
         self.user_repo.update_last_login(user.id).await?;
 
-        // Create session
-        let session_data = SessionData {
-            user_id: user.id.0,
-            username: user.username.clone(),
-        };
+        let details = json!({"two_factor": two_factor});
+        self.audit.record(Some(user.id), SecurityEventType::Login, &client, Some(details)).await;
 
-        Ok((session_data, user))
+        self.open_session(user.id, &user.username, client).await
+    }
+
+    /// Create a session row for a login
+    async fn open_session(&self, user_id: UserId, username: &str, client: ClientInfo) -> Result<SessionData> {
+        let session = self.session_repo
+            .create(CreateSession {
+                user_id,
+                ip_address: client.ip_address,
+                user_agent: client.user_agent,
+                expires_at: Utc::now() + Duration::hours(self.config.session_ttl_hours),
+            })
+            .await?;
+
+        Ok(SessionData {
+            user_id: user_id.0,
+            username: username.to_string(),
+            session_id: session.id,
+        })
     }
 
     /// Get user info
@@ -113,17 +417,74 @@ impl AuthService {
         Ok(UserInfoResponse::from(&user))
     }
 
-    /// Logout user (deactivate session)
-    pub async fn logout(&self, user_id: UserId) -> Result<()> {
-        self.session_repo.deactivate_all_for_user(user_id).await?;
+    /// Logout user (deactivate the current session only)
+    pub async fn logout(&self, current: &SessionData, client: &ClientInfo) -> Result<()> {
+        if self.session_repo.deactivate(current.session_id).await? {
+            self.audit.record(Some(UserId(current.user_id)), SecurityEventType::Logout, client, None).await;
+        }
+        Ok(())
+    }
+
+    /// List the user's active sessions, flagging the one making the request
+    pub async fn list_sessions(&self, current: &SessionData) -> Result<Vec<SessionResponse>> {
+        let sessions = self.session_repo
+            .find_active_by_user(UserId(current.user_id))
+            .await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionResponse::from_session(session, current.session_id))
+            .collect())
+    }
+
+    /// Log out one of the user's sessions
+    pub async fn revoke_session(&self, current: &SessionData, session_id: Uuid, client: &ClientInfo) -> Result<()> {
+        // Sessions of other users are reported as missing
+        let owned = self.session_repo
+            .find_by_id(session_id)
+            .await?
+            .is_some_and(|session| session.user_id.0 == current.user_id);
+
+        if !owned || !self.session_repo.deactivate(session_id).await? {
+            return Err(AppError::NotFound("Session not found".into()));
+        }
+
+        let details = json!({"session_id": session_id, "current": session_id == current.session_id});
+        self.audit.record(Some(UserId(current.user_id)), SecurityEventType::SessionRevoked, client, Some(details)).await;
+
         Ok(())
     }
 
-    /// Validate session and get user
-    pub async fn validate_session(&self, user_id: UserId) -> Result<User> {
+    /// Log out every session of the user but the current one.
+    ///
+    /// All sessions are deactivated at once and the current login moves to a
+    /// fresh session, so a session opened concurrently cannot slip through.
+    /// Returns the new session data and the number of other sessions revoked.
+    pub async fn revoke_other_sessions(&self, current: &SessionData, client: ClientInfo) -> Result<(SessionData, u64)> {
+        let user_id = UserId(current.user_id);
+        let deactivated = self.session_repo.deactivate_all_for_user(user_id).await?;
+        let revoked = deactivated.saturating_sub(1);
+
+        let details = json!({"other_sessions": revoked});
+        self.audit.record(Some(user_id), SecurityEventType::SessionRevoked, &client, Some(details)).await;
+
+        let session_data = self.open_session(user_id, &current.username, client).await?;
+
+        Ok((session_data, revoked))
+    }
+
+    /// Validate session and get user. Expired and deactivated sessions, and
+    /// sessions of deactivated accounts, are rejected.
+    pub async fn validate_session(&self, session_id: Uuid) -> Result<User> {
+        let session = self.session_repo
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| AppError::Authentication("Session expired".into()))?;
+
         self.user_repo
-            .find_by_id(user_id)
+            .find_by_id(session.user_id)
             .await?
+            .filter(|user| user.is_active)
             .ok_or_else(|| AppError::Authentication("Invalid session".into()))
     }
 }
\ No newline at end of file
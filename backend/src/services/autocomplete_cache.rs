@@ -0,0 +1,300 @@
+use crate::config::Config;
+use crate::domain::autocomplete::{AutocompleteCacheStats, AutocompleteSuggestion};
+use crate::error::{AppError, Result};
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+type Suggestions = Arc<Vec<AutocompleteSuggestion>>;
+
+/// Outcome of a provider call, shared between coalesced callers
+type SharedFetch = Shared<BoxFuture<'static, std::result::Result<Suggestions, String>>>;
+
+/// Normalize a search term so that "  Pizza  Mario" and "pizza mario" share
+/// a cache entry
+pub fn normalize_term(term: &str) -> String {
+    term.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+struct Entry {
+    suggestions: Suggestions,
+    /// Number of results the provider was asked for
+    limit: usize,
+    /// Whether the provider returned fewer results than asked for, i.e.
+    /// every match of the term. Only complete entries can serve longer terms.
+    complete: bool,
+    fetched_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    /// Entries by last use, oldest first
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    inflight: HashMap<String, SharedFetch>,
+}
+
+impl Entry {
+    /// Whether the entry holds every result a search with `limit` would get
+    fn covers(&self, limit: usize) -> bool {
+        self.complete || limit <= self.limit
+    }
+
+    fn first(&self, limit: usize) -> Vec<AutocompleteSuggestion> {
+        self.suggestions.iter().take(limit).cloned().collect()
+    }
+}
+
+impl State {
+    fn touch(&mut self, term: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(term) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = tick;
+            self.recency.insert(tick, term.to_string());
+        }
+    }
+
+    fn remove(&mut self, term: &str) {
+        if let Some(entry) = self.entries.remove(term) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    hits: AtomicU64,
+    prefix_hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+    stale_served: AtomicU64,
+    evictions: AtomicU64,
+}
+
+/// In-process TTL + LRU cache of autocomplete results.
+///
+/// Results stay fresh for `ttl`. Expired results are kept until `stale` so
+/// they can stand in when the provider fails, and the least recently used
+/// entries are dropped beyond `capacity`. Concurrent searches for the same
+/// term share a single provider call.
+pub struct AutocompleteCache {
+    state: Mutex<State>,
+    metrics: Metrics,
+    capacity: usize,
+    ttl: Duration,
+    stale: Duration,
+}
+
+impl AutocompleteCache {
+    pub fn new(capacity: usize, ttl: Duration, stale: Duration) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            metrics: Metrics::default(),
+            capacity,
+            ttl,
+            stale: stale.max(ttl),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.autocomplete_cache_capacity,
+            Duration::from_secs(config.autocomplete_cache_ttl_secs),
+            Duration::from_secs(config.autocomplete_cache_stale_secs),
+        )
+    }
+
+    /// Cached suggestions for `term`, or else the result of `fetch`, which
+    /// must return at most `limit` suggestions
+    pub async fn get_or_fetch<F, Fut>(
+        self: &Arc<Self>,
+        term: &str,
+        limit: usize,
+        fetch: F,
+    ) -> Result<Vec<AutocompleteSuggestion>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<AutocompleteSuggestion>>> + Send + 'static,
+    {
+        let key = normalize_term(term);
+        if self.capacity == 0 {
+            return fetch().await;
+        }
+
+        let pending = {
+            let mut state = self.lock();
+
+            if let Some(suggestions) = self.lookup_fresh(&mut state, &key, limit) {
+                return Ok(suggestions);
+            }
+
+            match state.inflight.get(&key) {
+                Some(pending) => {
+                    self.metrics.coalesced.fetch_add(1, Ordering::Relaxed);
+                    pending.clone()
+                }
+                None => {
+                    self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                    let pending = self.start_fetch(key.clone(), limit, fetch());
+                    state.inflight.insert(key.clone(), pending.clone());
+                    pending
+                }
+            }
+        };
+
+        match pending.await {
+            Ok(suggestions) => Ok(suggestions.as_ref().clone()),
+            Err(error) => match self.lookup_stale(&key, limit) {
+                Some(suggestions) => {
+                    self.metrics.stale_served.fetch_add(1, Ordering::Relaxed);
+                    warn!("Autocomplete provider failed for '{}', serving stale results: {}", key, error);
+                    Ok(suggestions)
+                }
+                None => Err(AppError::Internal(error)),
+            },
+        }
+    }
+
+    /// Current counters
+    pub fn stats(&self) -> AutocompleteCacheStats {
+        let entries = self.lock().entries.len();
+        AutocompleteCacheStats {
+            hits: self.metrics.hits.load(Ordering::Relaxed),
+            prefix_hits: self.metrics.prefix_hits.load(Ordering::Relaxed),
+            misses: self.metrics.misses.load(Ordering::Relaxed),
+            coalesced: self.metrics.coalesced.load(Ordering::Relaxed),
+            stale_served: self.metrics.stale_served.load(Ordering::Relaxed),
+            evictions: self.metrics.evictions.load(Ordering::Relaxed),
+            entries,
+            capacity: self.capacity,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // The state stays consistent between statements, so a panic while
+        // holding the lock does not corrupt it
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run the provider call once and store its result, whoever polls it
+    fn start_fetch(
+        self: &Arc<Self>,
+        key: String,
+        limit: usize,
+        fetch: impl Future<Output = Result<Vec<AutocompleteSuggestion>>> + Send + 'static,
+    ) -> SharedFetch {
+        let cache = Arc::clone(self);
+        async move {
+            let result = fetch.await;
+            let mut state = cache.lock();
+            state.inflight.remove(&key);
+            match result {
+                Ok(suggestions) => {
+                    let suggestions = Arc::new(suggestions);
+                    cache.store(&mut state, key, Arc::clone(&suggestions), limit);
+                    Ok(suggestions)
+                }
+                Err(AppError::Internal(message)) => Err(message),
+                Err(error) => Err(error.to_string()),
+            }
+        }
+        .boxed()
+        .shared()
+    }
+
+    fn store(&self, state: &mut State, key: String, suggestions: Suggestions, limit: usize) {
+        state.remove(&key);
+        state.tick += 1;
+        let tick = state.tick;
+        state.recency.insert(tick, key.clone());
+        state.entries.insert(key, Entry {
+            limit,
+            complete: suggestions.len() < limit,
+            suggestions,
+            fetched_at: Instant::now(),
+            last_used: tick,
+        });
+
+        while state.entries.len() > self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else { break };
+            state.entries.remove(&oldest);
+            self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// A fresh entry for the term fetched with at least `limit` results, or
+    /// else a fresh complete entry for one of its prefixes, filtered down to
+    /// the term
+    fn lookup_fresh(&self, state: &mut State, key: &str, limit: usize) -> Option<Vec<AutocompleteSuggestion>> {
+        if let Some(entry) = state.entries.get(key)
+            && entry.fetched_at.elapsed() < self.ttl
+            && entry.covers(limit)
+        {
+            let suggestions = entry.first(limit);
+            state.touch(key);
+            self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+            debug!("Autocomplete cache hit for '{}'", key);
+            return Some(suggestions);
+        }
+
+        let (prefix, suggestions) = self.lookup_prefix(state, key, limit, self.ttl)?;
+        state.touch(&prefix);
+        self.metrics.prefix_hits.fetch_add(1, Ordering::Relaxed);
+        debug!("Autocomplete cache hit for '{}' from '{}'", key, prefix);
+        Some(suggestions)
+    }
+
+    /// Any entry younger than the stale limit, for the term or a prefix.
+    /// Fewer results than asked for beat none when the provider is down.
+    fn lookup_stale(&self, key: &str, limit: usize) -> Option<Vec<AutocompleteSuggestion>> {
+        let state = self.lock();
+        if let Some(entry) = state.entries.get(key)
+            && entry.fetched_at.elapsed() < self.stale
+        {
+            return Some(entry.first(limit));
+        }
+        self.lookup_prefix(&state, key, limit, self.stale)
+            .map(|(_, suggestions)| suggestions)
+    }
+
+    fn lookup_prefix(
+        &self,
+        state: &State,
+        key: &str,
+        limit: usize,
+        max_age: Duration,
+    ) -> Option<(String, Vec<AutocompleteSuggestion>)> {
+        // Longest prefix first, down to the two characters a search needs
+        let boundaries: Vec<usize> = key.char_indices().map(|(i, _)| i).skip(2).collect();
+        boundaries.into_iter().rev().find_map(|end| {
+            let prefix = &key[..end];
+            let entry = state.entries.get(prefix)?;
+            if !entry.complete || entry.fetched_at.elapsed() >= max_age {
+                return None;
+            }
+            let suggestions = entry
+                .suggestions
+                .iter()
+                .filter(|s| {
+                    normalize_term(&s.name).contains(key)
+                        || normalize_term(&s.city).contains(key)
+                        || s.cuisines.iter().any(|c| c.contains(key))
+                })
+                .take(limit)
+                .cloned()
+                .collect();
+            Some((prefix.to_string(), suggestions))
+        })
+    }
+}
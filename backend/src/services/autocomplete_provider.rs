@@ -0,0 +1,214 @@
+use crate::config::Config;
+use crate::domain::autocomplete::{
+    source_id, AutocompleteProviderKind, AutocompleteSuggestion, OpenDataSoftResponse,
+    SuggestionOrigin, UNKNOWN_CITY,
+};
+use crate::repositories::place_repository::PlaceRepository;
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Source of autocomplete suggestions
+#[async_trait]
+pub trait AutocompleteProvider: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Restaurants matching a search term, best matches first
+    async fn search(&self, term: &str, limit: usize) -> Result<Vec<AutocompleteSuggestion>>;
+}
+
+/// Build the provider selected in the configuration
+pub fn from_config(
+    config: &Config,
+    places: Arc<dyn PlaceRepository>,
+) -> Arc<dyn AutocompleteProvider> {
+    build(config.autocomplete_provider, config.opendatasoft_url.clone(), places)
+}
+
+/// Build a provider of the given kind
+pub fn build(
+    kind: AutocompleteProviderKind,
+    opendatasoft_url: String,
+    places: Arc<dyn PlaceRepository>,
+) -> Arc<dyn AutocompleteProvider> {
+    match kind {
+        AutocompleteProviderKind::OpenDataSoft => Arc::new(OpenDataSoftProvider::new(opendatasoft_url)),
+        AutocompleteProviderKind::Local => Arc::new(LocalProvider::new(places)),
+        AutocompleteProviderKind::Mock => Arc::new(MockProvider::default()),
+    }
+}
+
+/// Suggestions from the OpenDataSoft `osm-france-food-service` dataset
+pub struct OpenDataSoftProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenDataSoftProvider {
+    pub fn new(base_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("Gourmestre/1.0")
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client, base_url }
+    }
+
+    /// Parse OpenDataSoft response into suggestions
+    fn parse_response(response: OpenDataSoftResponse) -> Vec<AutocompleteSuggestion> {
+        let mut suggestions = Vec::new();
+
+        for record in response.records {
+            let coordinates = record.coordinates();
+            let fields = record.fields;
+            let cuisines = fields.cuisines();
+            let address = fields.address();
+            let source_id = fields.source_id();
+            if let Some(name) = fields.name {
+                let city = fields
+                    .meta_name_com
+                    .or(fields.meta_name_dep)
+                    .unwrap_or_else(|| UNKNOWN_CITY.to_string());
+
+                suggestions.push(AutocompleteSuggestion {
+                    name,
+                    city,
+                    cuisines,
+                    latitude: coordinates.map(|(lat, _)| lat),
+                    longitude: coordinates.map(|(_, lon)| lon),
+                    address,
+                    postcode: fields.addr_postcode,
+                    opening_hours: fields.opening_hours,
+                    website: fields.website,
+                    source_id,
+                    origin: SuggestionOrigin::External,
+                    existing_id: None,
+                });
+            }
+        }
+
+        suggestions
+    }
+}
+
+#[async_trait]
+impl AutocompleteProvider for OpenDataSoftProvider {
+    fn name(&self) -> &'static str {
+        "opendatasoft"
+    }
+
+    async fn search(&self, term: &str, limit: usize) -> Result<Vec<AutocompleteSuggestion>> {
+        let url = format!(
+            "{}?dataset=osm-france-food-service&q={}&rows={}",
+            self.base_url,
+            urlencoding::encode(term),
+            limit
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(format!("OpenDataSoft API request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "OpenDataSoft API returned status: {}",
+                response.status()
+            )));
+        }
+
+        let api_response: OpenDataSoftResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to parse OpenDataSoft response: {}", e)))?;
+
+        Ok(Self::parse_response(api_response))
+    }
+}
+
+/// Suggestions from places imported into PostgreSQL with `import_places`,
+/// so autocomplete works without internet access
+pub struct LocalProvider {
+    places: Arc<dyn PlaceRepository>,
+}
+
+impl LocalProvider {
+    pub fn new(places: Arc<dyn PlaceRepository>) -> Self {
+        Self { places }
+    }
+}
+
+#[async_trait]
+impl AutocompleteProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn search(&self, term: &str, limit: usize) -> Result<Vec<AutocompleteSuggestion>> {
+        let places = self.places.search(term, limit as i64).await?;
+        Ok(places.into_iter().map(AutocompleteSuggestion::from).collect())
+    }
+}
+
+/// Fixed suggestions matched by case-insensitive substring, for tests and
+/// demos that must not depend on the network or on imported data
+pub struct MockProvider {
+    suggestions: Vec<AutocompleteSuggestion>,
+}
+
+impl MockProvider {
+    pub fn new(suggestions: Vec<AutocompleteSuggestion>) -> Self {
+        Self { suggestions }
+    }
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        let suggestion = |id: u32, name: &str, city: &str, postcode: &str, cuisines: &[&str], lat: f64, lon: f64| {
+            AutocompleteSuggestion {
+                name: name.to_string(),
+                city: city.to_string(),
+                cuisines: cuisines.iter().map(|c| c.to_string()).collect(),
+                latitude: Some(lat),
+                longitude: Some(lon),
+                address: None,
+                postcode: Some(postcode.to_string()),
+                opening_hours: None,
+                website: None,
+                source_id: Some(source_id("mock", &id.to_string())),
+                origin: SuggestionOrigin::External,
+                existing_id: None,
+            }
+        };
+
+        Self::new(vec![
+            suggestion(1, "Pizza Mario", "Paris", "75001", &["pizza", "italian"], 48.8566, 2.3522),
+            suggestion(2, "Pizzeria Napoli", "Lyon", "69002", &["pizza"], 45.7640, 4.8357),
+            suggestion(3, "Le Bouchon Lyonnais", "Lyon", "69001", &["french"], 45.7600, 4.8300),
+            suggestion(4, "Sushi Shop", "Bordeaux", "33000", &["sushi", "japanese"], 44.8378, -0.5792),
+            suggestion(5, "Crêperie du Port", "Saint-Malo", "35400", &["crepe"], 48.6493, -2.0257),
+        ])
+    }
+}
+
+#[async_trait]
+impl AutocompleteProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn search(&self, term: &str, limit: usize) -> Result<Vec<AutocompleteSuggestion>> {
+        let term = term.to_lowercase();
+        Ok(self
+            .suggestions
+            .iter()
+            .filter(|s| s.name.to_lowercase().contains(&term) || s.city.to_lowercase().contains(&term))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
@@ -1,93 +1,115 @@
 use crate::domain::autocomplete::{
-    AutocompleteSuggestion, OpenDataSoftResponse, AutocompleteResponse
+    merge_suggestions, AutocompleteCacheStats, AutocompleteResponse, AutocompleteSuggestion,
+    AUTOCOMPLETE_LIMIT, OWN_SUGGESTION_LIMIT,
 };
-use crate::error::{AppError, Result};
+use crate::domain::user::UserId;
+use crate::repositories::restaurant_repository::RestaurantRepository;
+use crate::repositories::wishlist_repository::WishlistRepository;
+use crate::services::autocomplete_cache::AutocompleteCache;
+use crate::services::autocomplete_provider::AutocompleteProvider;
+use crate::error::Result;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{info, warn};
 
 #[derive(Clone)]
 pub struct AutocompleteService {
-    client: Arc<reqwest::Client>,
+    provider: Arc<dyn AutocompleteProvider>,
+    cache: Arc<AutocompleteCache>,
+    restaurant_repo: Arc<dyn RestaurantRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
 }
 
 impl AutocompleteService {
     /// Create a new autocomplete service
-    pub fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("Gourmestre/1.0")
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .expect("Failed to create HTTP client");
-        
-        Self {
-            client: Arc::new(client),
-        }
+    pub fn new(
+        provider: Arc<dyn AutocompleteProvider>,
+        cache: Arc<AutocompleteCache>,
+        restaurant_repo: Arc<dyn RestaurantRepository>,
+        wishlist_repo: Arc<dyn WishlistRepository>,
+    ) -> Self {
+        Self { provider, cache, restaurant_repo, wishlist_repo }
     }
 
-    /// Search for restaurant suggestions using OpenDataSoft API
-    pub async fn search_restaurants(&self, search_term: &str) -> Result<AutocompleteResponse> {
+    /// Search for restaurant suggestions. For a logged-in user, their own
+    /// restaurants and wishlist items are merged in and marked, so they do
+    /// not add them twice.
+    pub async fn search_restaurants(
+        &self,
+        search_term: &str,
+        user_id: Option<UserId>,
+    ) -> Result<AutocompleteResponse> {
         // Skip search for terms that are too short
-        if search_term.len() < 2 {
+        if search_term.trim().chars().count() < 2 {
             return Ok(AutocompleteResponse {
                 suggestions: Vec::new(),
             });
         }
 
-        info!("Searching restaurants with term: '{}'", search_term);
-
-        let url = format!(
-            "https://public.opendatasoft.com/api/records/1.0/search/?dataset=osm-france-food-service&q={}&rows=10",
-            urlencoding::encode(search_term)
-        );
+        let external = self.external_suggestions(search_term).await;
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("OpenDataSoft API request failed: {}", e)))?;
+        // Entries created from one of the suggestions are the user's even
+        // when they were renamed since
+        let source_ids: Vec<String> = external
+            .iter()
+            .flatten()
+            .filter_map(|s| s.source_id.clone())
+            .collect();
+        let own = match user_id {
+            Some(user_id) => self.own_suggestions(search_term, &source_ids, user_id).await?,
+            None => Vec::new(),
+        };
 
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "OpenDataSoft API returned status: {}",
-                response.status()
-            )));
-        }
-
-        let api_response: OpenDataSoftResponse = response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse OpenDataSoft response: {}", e)))?;
+        let external = match external {
+            Ok(external) => external,
+            // The user's own entries are still worth showing
+            Err(e) if !own.is_empty() => {
+                warn!("Autocomplete provider failed, showing own entries only: {}", e);
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
 
-        let suggestions = self.parse_opendatasoft_response(api_response);
-        
-        info!("Found {} restaurant suggestions", suggestions.len());
+        let suggestions = merge_suggestions(search_term, own, external, AUTOCOMPLETE_LIMIT);
 
         Ok(AutocompleteResponse { suggestions })
     }
 
-    /// Parse OpenDataSoft response into suggestions
-    fn parse_opendatasoft_response(&self, response: OpenDataSoftResponse) -> Vec<AutocompleteSuggestion> {
-        let mut suggestions = Vec::new();
+    /// Counters of the autocomplete cache
+    pub fn cache_stats(&self) -> AutocompleteCacheStats {
+        self.cache.stats()
+    }
 
-        for record in response.records {
-            let fields = record.fields;
-            if let Some(name) = fields.name {
-                let city = fields
-                    .meta_name_com
-                    .or(fields.meta_name_dep)
-                    .unwrap_or_else(|| "Ville inconnue".to_string());
-                
-                suggestions.push(AutocompleteSuggestion { name, city });
-            }
-        }
+    async fn own_suggestions(
+        &self,
+        search_term: &str,
+        source_ids: &[String],
+        user_id: UserId,
+    ) -> Result<Vec<AutocompleteSuggestion>> {
+        let restaurants = self.restaurant_repo
+            .suggest(user_id, search_term, source_ids, OWN_SUGGESTION_LIMIT)
+            .await?;
+        let items = self.wishlist_repo
+            .suggest(user_id, search_term, source_ids, OWN_SUGGESTION_LIMIT)
+            .await?;
 
-        suggestions
+        Ok(restaurants
+            .into_iter()
+            .map(AutocompleteSuggestion::from)
+            .chain(items.into_iter().map(AutocompleteSuggestion::from))
+            .collect())
     }
-}
 
-impl Default for AutocompleteService {
-    fn default() -> Self {
-        Self::new()
+    /// Provider suggestions, through the cache
+    async fn external_suggestions(&self, search_term: &str) -> Result<Vec<AutocompleteSuggestion>> {
+        let provider = Arc::clone(&self.provider);
+        let term = search_term.trim().to_string();
+        self.cache
+            .get_or_fetch(search_term, AUTOCOMPLETE_LIMIT, move || async move {
+                info!("Searching restaurants with term: '{}' ({})", term, provider.name());
+                let suggestions = provider.search(&term, AUTOCOMPLETE_LIMIT).await?;
+                info!("Found {} restaurant suggestions", suggestions.len());
+                Ok(suggestions)
+            })
+            .await
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,171 @@
+use crate::domain::export::{ExportAccount, ExportFormat, csv_header};
+use crate::domain::pagination::{PageRequest, SortDirection, MAX_PAGE_SIZE};
+use crate::domain::restaurant::{RestaurantResponse, RestaurantSort};
+use crate::domain::user::UserId;
+use crate::domain::wishlist::{WishlistItemResponse, WishlistSort};
+use crate::repositories::restaurant_repository::RestaurantRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::repositories::wishlist_repository::WishlistRepository;
+use crate::error::{AppError, Result};
+use actix_web::web::Bytes;
+use futures_util::stream::{self, Stream, StreamExt};
+use std::sync::Arc;
+
+pub struct ExportService {
+    user_repo: Arc<dyn UserRepository>,
+    restaurant_repo: Arc<dyn RestaurantRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+}
+
+/// Section of the export being written
+enum Stage {
+    Restaurants(Option<String>),
+    Wishlist(Option<String>),
+    Done,
+}
+
+/// State carried between the chunks of an export stream
+struct ExportState {
+    restaurant_repo: Arc<dyn RestaurantRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    owner_id: UserId,
+    format: ExportFormat,
+    stage: Stage,
+    /// Whether the current JSON array already holds an element
+    has_items: bool,
+}
+
+impl ExportState {
+    /// Render the next page of the current section, or None once finished
+    async fn next_chunk(&mut self) -> Option<Result<Bytes>> {
+        let chunk = match std::mem::replace(&mut self.stage, Stage::Done) {
+            Stage::Restaurants(cursor) => self.restaurant_chunk(cursor).await,
+            Stage::Wishlist(cursor) => self.wishlist_chunk(cursor).await,
+            Stage::Done => return None,
+        };
+
+        // Stop after the first error, the stage stays Done
+        Some(chunk.map(Bytes::from))
+    }
+
+    async fn restaurant_chunk(&mut self, cursor: Option<String>) -> Result<String> {
+        let page = PageRequest::new(
+            RestaurantSort::CreatedAt,
+            Some(SortDirection::Asc),
+            Some(MAX_PAGE_SIZE),
+            cursor.as_deref(),
+        )?;
+        let restaurants = self.restaurant_repo
+            .find_by_owner(self.owner_id, false, &[], &page)
+            .await?;
+
+        let mut chunk = String::new();
+        for restaurant in restaurants.items.into_iter().map(RestaurantResponse::from) {
+            match self.format {
+                ExportFormat::Csv => chunk.push_str(&restaurant.to_csv_row()?),
+                ExportFormat::Json => self.push_json(&mut chunk, &restaurant)?,
+            }
+        }
+
+        self.stage = match restaurants.next_cursor {
+            Some(next) => Stage::Restaurants(Some(next)),
+            None => {
+                if self.format == ExportFormat::Json {
+                    chunk.push_str("],\"wishlist\":[");
+                    self.has_items = false;
+                }
+                Stage::Wishlist(None)
+            }
+        };
+
+        Ok(chunk)
+    }
+
+    async fn wishlist_chunk(&mut self, cursor: Option<String>) -> Result<String> {
+        let page = PageRequest::new(
+            WishlistSort::CreatedAt,
+            Some(SortDirection::Asc),
+            Some(MAX_PAGE_SIZE),
+            cursor.as_deref(),
+        )?;
+        let items = self.wishlist_repo
+            .find_by_owner(self.owner_id, None, &[], &page)
+            .await?;
+
+        let mut chunk = String::new();
+        for item in items.items.into_iter().map(WishlistItemResponse::from) {
+            match self.format {
+                ExportFormat::Csv => chunk.push_str(&item.to_csv_row()?),
+                ExportFormat::Json => self.push_json(&mut chunk, &item)?,
+            }
+        }
+
+        self.stage = match items.next_cursor {
+            Some(next) => Stage::Wishlist(Some(next)),
+            None => {
+                if self.format == ExportFormat::Json {
+                    chunk.push_str("]}\n");
+                }
+                Stage::Done
+            }
+        };
+
+        Ok(chunk)
+    }
+
+    fn push_json<T: serde::Serialize>(&mut self, chunk: &mut String, value: &T) -> Result<()> {
+        if self.has_items {
+            chunk.push(',');
+        }
+        chunk.push_str(&serde_json::to_string(value)?);
+        self.has_items = true;
+        Ok(())
+    }
+}
+
+impl ExportService {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        restaurant_repo: Arc<dyn RestaurantRepository>,
+        wishlist_repo: Arc<dyn WishlistRepository>,
+    ) -> Self {
+        Self { user_repo, restaurant_repo, wishlist_repo }
+    }
+
+    /// Stream the user's account, restaurants and wishlist, one page at a time
+    pub async fn export(
+        &self,
+        owner_id: UserId,
+        format: ExportFormat,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + use<>> {
+        let user = self.user_repo
+            .find_by_id(owner_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+        let account = ExportAccount::from(&user);
+
+        let header = match format {
+            ExportFormat::Csv => format!("{}{}", csv_header()?, account.to_csv_row()?),
+            ExportFormat::Json => format!(
+                "{{\"account\":{},\"restaurants\":[",
+                serde_json::to_string(&account)?
+            ),
+        };
+
+        let state = ExportState {
+            restaurant_repo: self.restaurant_repo.clone(),
+            wishlist_repo: self.wishlist_repo.clone(),
+            owner_id,
+            format,
+            stage: Stage::Restaurants(None),
+            has_items: false,
+        };
+
+        let body = stream::unfold(state, |mut state| async move {
+            let chunk = state.next_chunk().await?;
+            Some((chunk, state))
+        });
+
+        Ok(stream::once(async move { Ok(Bytes::from(header)) }).chain(body))
+    }
+}
@@ -0,0 +1,106 @@
+use crate::domain::import::{
+    parse_csv, parse_json, ImportFormat, ImportOutcome, ImportQuery, ImportReport,
+    ImportRowReport, ImportRowStatus,
+};
+use crate::domain::user::UserId;
+use crate::repositories::import_repository::ImportRepository;
+use crate::error::{AppError, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct ImportService {
+    repo: Arc<dyn ImportRepository>,
+}
+
+/// Message shown for a failed row, without the error category prefix
+fn row_message(error: AppError) -> String {
+    match error {
+        AppError::Validation(message) | AppError::BadRequest(message) => message,
+        other => other.to_string(),
+    }
+}
+
+impl ImportService {
+    pub fn new(repo: Arc<dyn ImportRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Import restaurants and wishlist items from an uploaded CSV or JSON file
+    pub async fn import(
+        &self,
+        owner_id: UserId,
+        data: &[u8],
+        filename: Option<&str>,
+        content_type: Option<&str>,
+        query: ImportQuery,
+    ) -> Result<ImportReport> {
+        let rows = match ImportFormat::detect(filename, content_type, data) {
+            ImportFormat::Csv => parse_csv(data, query.target, owner_id)?,
+            ImportFormat::Json => parse_json(data, query.target, owner_id)?,
+        };
+
+        if rows.is_empty() {
+            return Err(AppError::BadRequest("The file contains no rows".into()));
+        }
+
+        let mut reports = Vec::with_capacity(rows.len());
+        let mut entries = Vec::new();
+        for row in rows {
+            let (status, message) = match row.entry {
+                Ok(entry) => {
+                    entries.push(entry);
+                    (ImportRowStatus::Created, None)
+                }
+                Err(error) => (ImportRowStatus::Failed, Some(row_message(error))),
+            };
+            reports.push(ImportRowReport {
+                row: row.row,
+                target: row.target,
+                name: row.name,
+                city: row.city,
+                status,
+                id: None,
+                message,
+            });
+        }
+
+        let outcomes = self.repo.import(owner_id, &entries, query.dry_run).await?;
+
+        // Outcomes come back in the order of the valid rows
+        let mut created_rows: HashMap<i32, usize> = HashMap::new();
+        let valid_rows = reports
+            .iter_mut()
+            .filter(|report| report.status == ImportRowStatus::Created);
+        for (report, outcome) in valid_rows.zip(outcomes) {
+            match outcome {
+                ImportOutcome::Created(id) => {
+                    created_rows.insert(id, report.row);
+                    // IDs from a rolled back dry run do not exist
+                    report.id = (!query.dry_run).then_some(id);
+                }
+                ImportOutcome::Duplicate(id) => {
+                    report.status = ImportRowStatus::Skipped;
+                    match created_rows.get(&id) {
+                        Some(row) => {
+                            report.id = (!query.dry_run).then_some(id);
+                            report.message = Some(format!("Duplicate of row {}", row));
+                        }
+                        None => {
+                            report.id = Some(id);
+                            report.message = Some("An entry with the same name and city already exists".into());
+                        }
+                    }
+                }
+            }
+        }
+
+        let count = |status| reports.iter().filter(|r| r.status == status).count();
+        Ok(ImportReport {
+            dry_run: query.dry_run,
+            created: count(ImportRowStatus::Created),
+            skipped: count(ImportRowStatus::Skipped),
+            failed: count(ImportRowStatus::Failed),
+            rows: reports,
+        })
+    }
+}
@@ -0,0 +1,232 @@
+use crate::config::Config;
+use crate::repositories::audit_repository::AuditRepository;
+use crate::repositories::session_repository::SessionRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::repositories::user_token_repository::UserTokenRepository;
+use crate::services::rate_limiter::RateLimitStore;
+use crate::error::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+/// Maintenance task run periodically in the background
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Run the task once, returning the number of records it touched
+    async fn run(&self) -> Result<u64>;
+}
+
+/// In-process scheduler for maintenance jobs.
+///
+/// Each job gets its own task and runs once at startup, then every interval.
+/// A run never overlaps the previous run of the same job, and a failed run is
+/// logged and retried at the next tick.
+#[derive(Default)]
+pub struct JobRunner {
+    jobs: Vec<(Arc<dyn Job>, Duration)>,
+}
+
+impl JobRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the built-in maintenance jobs with their configured intervals
+    pub fn from_config(
+        config: &Config,
+        user_repo: Arc<dyn UserRepository>,
+        session_repo: Arc<dyn SessionRepository>,
+        user_token_repo: Arc<dyn UserTokenRepository>,
+        rate_limit_store: Arc<dyn RateLimitStore>,
+        audit_repo: Arc<dyn AuditRepository>,
+    ) -> Self {
+        // A zero retention keeps the audit log forever
+        let audit_cleanup_interval = if config.audit_log_retention_days == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(config.audit_log_cleanup_interval_secs)
+        };
+
+        Self::new()
+            .register(
+                Arc::new(SessionCleanupJob::new(session_repo)),
+                Duration::from_secs(config.session_cleanup_interval_secs),
+            )
+            .register(
+                Arc::new(UserTokenCleanupJob::new(user_token_repo)),
+                Duration::from_secs(config.session_cleanup_interval_secs),
+            )
+            .register(
+                Arc::new(AccountUnlockJob::new(user_repo)),
+                Duration::from_secs(config.account_unlock_interval_secs),
+            )
+            .register(
+                Arc::new(RateLimitPruneJob::new(rate_limit_store)),
+                Duration::from_secs(config.rate_limit_prune_interval_secs),
+            )
+            .register(
+                Arc::new(AuditLogCleanupJob::new(audit_repo, config.audit_log_retention_days)),
+                audit_cleanup_interval,
+            )
+    }
+
+    /// Add a job. A zero interval disables it.
+    pub fn register(mut self, job: Arc<dyn Job>, interval: Duration) -> Self {
+        if interval.is_zero() {
+            info!("Job {} is disabled", job.name());
+        } else {
+            self.jobs.push((job, interval));
+        }
+        self
+    }
+
+    /// Spawn every registered job on the current runtime
+    pub fn start(self) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .into_iter()
+            .map(|(job, every)| {
+                info!("Scheduling job {} every {:?}", job.name(), every);
+                tokio::spawn(async move {
+                    let mut ticks = tokio::time::interval(every);
+                    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                    loop {
+                        ticks.tick().await;
+                        run_once(job.as_ref()).await;
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+async fn run_once(job: &dyn Job) {
+    debug!("Running job {}", job.name());
+    let started = Instant::now();
+    match job.run().await {
+        Ok(0) => debug!("Job {} finished in {:?}, nothing to do", job.name(), started.elapsed()),
+        Ok(count) => info!(
+            "Job {} finished in {:?}, {} record(s) affected",
+            job.name(),
+            started.elapsed(),
+            count
+        ),
+        Err(e) => warn!("Job {} failed after {:?}: {}", job.name(), started.elapsed(), e),
+    }
+}
+
+/// Delete expired and logged out sessions
+pub struct SessionCleanupJob {
+    session_repo: Arc<dyn SessionRepository>,
+}
+
+impl SessionCleanupJob {
+    pub fn new(session_repo: Arc<dyn SessionRepository>) -> Self {
+        Self { session_repo }
+    }
+}
+
+#[async_trait]
+impl Job for SessionCleanupJob {
+    fn name(&self) -> &'static str {
+        "session_cleanup"
+    }
+
+    async fn run(&self) -> Result<u64> {
+        self.session_repo.cleanup_expired().await
+    }
+}
+
+/// Delete expired email verification and password reset tokens
+pub struct UserTokenCleanupJob {
+    user_token_repo: Arc<dyn UserTokenRepository>,
+}
+
+impl UserTokenCleanupJob {
+    pub fn new(user_token_repo: Arc<dyn UserTokenRepository>) -> Self {
+        Self { user_token_repo }
+    }
+}
+
+#[async_trait]
+impl Job for UserTokenCleanupJob {
+    fn name(&self) -> &'static str {
+        "user_token_cleanup"
+    }
+
+    async fn run(&self) -> Result<u64> {
+        self.user_token_repo.cleanup_expired().await
+    }
+}
+
+/// Unlock accounts whose lock after too many failed logins has run out
+pub struct AccountUnlockJob {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl AccountUnlockJob {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { user_repo }
+    }
+}
+
+#[async_trait]
+impl Job for AccountUnlockJob {
+    fn name(&self) -> &'static str {
+        "account_unlock"
+    }
+
+    async fn run(&self) -> Result<u64> {
+        self.user_repo.unlock_expired_accounts().await
+    }
+}
+
+/// Forget rate limit buckets of clients that went quiet
+pub struct RateLimitPruneJob {
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimitPruneJob {
+    pub fn new(store: Arc<dyn RateLimitStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl Job for RateLimitPruneJob {
+    fn name(&self) -> &'static str {
+        "rate_limit_prune"
+    }
+
+    async fn run(&self) -> Result<u64> {
+        self.store.prune().await
+    }
+}
+
+/// Delete audit log entries older than the retention period
+pub struct AuditLogCleanupJob {
+    audit_repo: Arc<dyn AuditRepository>,
+    retention_days: u32,
+}
+
+impl AuditLogCleanupJob {
+    pub fn new(audit_repo: Arc<dyn AuditRepository>, retention_days: u32) -> Self {
+        Self { audit_repo, retention_days }
+    }
+}
+
+#[async_trait]
+impl Job for AuditLogCleanupJob {
+    fn name(&self) -> &'static str {
+        "audit_log_cleanup"
+    }
+
+    async fn run(&self) -> Result<u64> {
+        self.audit_repo.delete_older_than(self.retention_days).await
+    }
+}
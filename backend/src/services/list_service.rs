@@ -0,0 +1,230 @@
+use crate::domain::list::{
+    ListId, ListEntryId, ListEntryKind, CreateListRequest, UpdateListRequest,
+    AddListEntryRequest, UpdateListEntryRequest, ReorderListRequest, ListResponse,
+    ListEntryResponse, ListDetailResponse, PublicListResponse, PublicListEntryResponse,
+    MAX_LIST_ENTRIES,
+};
+use crate::domain::token::generate_token;
+use crate::domain::user::UserId;
+use crate::repositories::list_repository::ListRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::error::{AppError, Result};
+use std::sync::Arc;
+
+pub struct ListService {
+    repo: Arc<dyn ListRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl ListService {
+    pub fn new(repo: Arc<dyn ListRepository>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { repo, user_repo }
+    }
+
+    /// Create a new list
+    pub async fn create_list(
+        &self,
+        request: CreateListRequest,
+        owner_id: UserId,
+    ) -> Result<ListResponse> {
+        let command = request.to_command(owner_id)?;
+        let list = self.repo.create(command).await?;
+        Ok(ListResponse::from(list))
+    }
+
+    /// Get all lists of a user, most recently updated first
+    pub async fn get_lists(&self, owner_id: UserId) -> Result<Vec<ListResponse>> {
+        let lists = self.repo.find_by_owner(owner_id).await?;
+        Ok(lists.into_iter().map(ListResponse::from).collect())
+    }
+
+    /// Get a list with its entries
+    pub async fn get_list(&self, id: ListId, owner_id: UserId) -> Result<ListDetailResponse> {
+        let list = self.repo
+            .find_by_id(id, owner_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found".into()))?;
+        let entries = self.repo.find_entries(id).await?;
+
+        Ok(ListDetailResponse {
+            list: ListResponse::from(list),
+            entries: entries.into_iter().map(ListEntryResponse::from).collect(),
+        })
+    }
+
+    /// Update a list
+    pub async fn update_list(
+        &self,
+        id: ListId,
+        request: UpdateListRequest,
+        owner_id: UserId,
+    ) -> Result<ListResponse> {
+        let command = request.to_command(id, owner_id)?;
+        let list = self.repo
+            .update(command)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found or access denied".into()))?;
+
+        Ok(ListResponse::from(list))
+    }
+
+    /// Delete a list (the restaurants and wishlist items are kept)
+    pub async fn delete_list(&self, id: ListId, owner_id: UserId) -> Result<()> {
+        let deleted = self.repo.delete(id, owner_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("List not found or access denied".into()));
+        }
+        Ok(())
+    }
+
+    /// Publish a list under a new share token. Publishing again rotates the
+    /// token, so links shared earlier stop working.
+    pub async fn share_list(&self, id: ListId, owner_id: UserId) -> Result<ListResponse> {
+        let token = generate_token();
+        let list = self.repo
+            .set_share_token(id, owner_id, Some(&token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found or access denied".into()))?;
+
+        Ok(ListResponse::from(list))
+    }
+
+    /// Stop publishing a list
+    pub async fn unshare_list(&self, id: ListId, owner_id: UserId) -> Result<ListResponse> {
+        let list = self.repo
+            .set_share_token(id, owner_id, None)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found or access denied".into()))?;
+
+        Ok(ListResponse::from(list))
+    }
+
+    /// Add a restaurant or wishlist item to a list
+    pub async fn add_entry(
+        &self,
+        list_id: ListId,
+        request: AddListEntryRequest,
+        owner_id: UserId,
+    ) -> Result<ListEntryResponse> {
+        let command = request.to_command(list_id, owner_id)?;
+
+        let list = self.repo
+            .find_by_id(list_id, owner_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found".into()))?;
+        if list.entry_count >= MAX_LIST_ENTRIES {
+            return Err(AppError::Validation(format!(
+                "Lists are limited to {} entries",
+                MAX_LIST_ENTRIES
+            )));
+        }
+
+        let (kind, item_id) = match (command.restaurant_id, command.wishlist_item_id) {
+            (Some(id), _) => (ListEntryKind::Restaurant, id.0),
+            (None, id) => (ListEntryKind::Wishlist, id.map(|id| id.0).unwrap_or_default()),
+        };
+        let entries = self.repo.find_entries(list_id).await?;
+        if entries.iter().any(|e| e.kind == kind && e.item_id == item_id) {
+            return Err(AppError::Conflict("Already in this list".into()));
+        }
+
+        let entry_id = self.repo
+            .add_entry(command)
+            .await?
+            .ok_or_else(|| match kind {
+                ListEntryKind::Restaurant => AppError::NotFound("Restaurant not found".into()),
+                ListEntryKind::Wishlist => AppError::NotFound("Wishlist item not found".into()),
+            })?;
+
+        self.get_entry(entry_id, list_id).await
+    }
+
+    /// Update the note or position of a list entry
+    pub async fn update_entry(
+        &self,
+        id: ListEntryId,
+        list_id: ListId,
+        request: UpdateListEntryRequest,
+        owner_id: UserId,
+    ) -> Result<ListEntryResponse> {
+        let command = request.to_command(id, list_id, owner_id)?;
+        let updated = self.repo.update_entry(command).await?;
+        if !updated {
+            return Err(AppError::NotFound("List entry not found or access denied".into()));
+        }
+
+        self.get_entry(id, list_id).await
+    }
+
+    /// Remove an entry from a list
+    pub async fn remove_entry(
+        &self,
+        id: ListEntryId,
+        list_id: ListId,
+        owner_id: UserId,
+    ) -> Result<()> {
+        let removed = self.repo.remove_entry(id, list_id, owner_id).await?;
+        if !removed {
+            return Err(AppError::NotFound("List entry not found or access denied".into()));
+        }
+        Ok(())
+    }
+
+    /// Reorder every entry of a list
+    pub async fn reorder_entries(
+        &self,
+        list_id: ListId,
+        request: ReorderListRequest,
+        owner_id: UserId,
+    ) -> Result<ListDetailResponse> {
+        let entry_ids = request.entry_ids()?;
+        let current = self.get_list(list_id, owner_id).await?;
+
+        let mut expected: Vec<i32> = current.entries.iter().map(|e| e.id).collect();
+        let mut given: Vec<i32> = entry_ids.iter().map(|id| id.0).collect();
+        expected.sort_unstable();
+        given.sort_unstable();
+        if expected != given {
+            return Err(AppError::Validation(
+                "entry_ids must list every entry of the list exactly once".into(),
+            ));
+        }
+
+        let reordered = self.repo.reorder_entries(list_id, owner_id, &entry_ids).await?;
+        if !reordered {
+            return Err(AppError::Conflict("The list changed, reload it and try again".into()));
+        }
+
+        self.get_list(list_id, owner_id).await
+    }
+
+    /// Get a published list by its share token, without authentication
+    pub async fn get_public_list(&self, token: &str) -> Result<PublicListResponse> {
+        let list = self.repo
+            .find_by_share_token(token)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found".into()))?;
+        let curator = self.user_repo
+            .find_by_id(list.owner_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List not found".into()))?;
+        let entries = self.repo.find_entries(list.id).await?;
+
+        Ok(PublicListResponse {
+            name: list.name,
+            description: list.description,
+            curator: curator.username,
+            entries: entries.into_iter().map(PublicListEntryResponse::from).collect(),
+            updated_at: list.updated_at,
+        })
+    }
+
+    async fn get_entry(&self, id: ListEntryId, list_id: ListId) -> Result<ListEntryResponse> {
+        let entry = self.repo
+            .find_entry(id, list_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("List entry not found".into()))?;
+
+        Ok(ListEntryResponse::from(entry))
+    }
+}
@@ -0,0 +1,145 @@
+use crate::config::Config;
+use crate::domain::mail::{MailMessage, MailerKind};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+/// Delivers outgoing emails
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, message: MailMessage) -> Result<()>;
+}
+
+/// Build the mailer selected in the configuration
+pub fn from_config(config: &Config) -> Result<Arc<dyn Mailer>> {
+    Ok(match config.mailer {
+        MailerKind::Smtp => Arc::new(SmtpMailer::new(config)?),
+        MailerKind::File => Arc::new(FileMailer::new(PathBuf::from(&config.mail_dir))),
+        MailerKind::Log => Arc::new(LogMailer),
+    })
+}
+
+/// Sends through an SMTP server, with STARTTLS unless disabled
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &Config) -> Result<Self> {
+        let mut builder = if config.smtp_starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+                .map_err(|e| AppError::Internal(format!("Invalid SMTP_HOST: {}", e)))?
+        } else {
+            // Local catch-all servers such as MailHog do not speak TLS
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+        };
+        builder = builder.port(config.smtp_port);
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let from = config
+            .mail_from
+            .parse()
+            .map_err(|_| AppError::Internal("Invalid MAIL_FROM".into()))?;
+
+        Ok(Self { transport: builder.build(), from })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        let to = message
+            .to
+            .parse()
+            .map_err(|_| AppError::Validation("Invalid email address".into()))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body)
+            .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to send email: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// Writes each email to its own `.eml` file
+pub struct FileMailer {
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create mail directory: {}", e)))?;
+
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4()
+        ));
+        let contents = format!(
+            "To: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+            message.to, message.subject, message.body
+        );
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write email: {}", e)))?;
+
+        info!("Wrote email to {} into {}", message.to, path.display());
+        Ok(())
+    }
+}
+
+/// Writes the recipient and subject of emails to the application log, for
+/// development. Bodies hold verification and password reset links that
+/// would let anyone reading the log take over an account, so they are not
+/// written: use the file mailer to open the links.
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        info!("Email to {}: {} (body not logged)", message.to, message.subject);
+        Ok(())
+    }
+}
@@ -2,4 +2,19 @@ pub mod user_service;
 pub mod restaurant_service;
 pub mod wishlist_service;
 pub mod auth_service;
-pub mod autocomplete_service;
\ No newline at end of file
+pub mod autocomplete_service;
+pub mod visit_service;
+pub mod tag_service;
+pub mod export_service;
+pub mod import_service;
+pub mod list_service;
+pub mod social_service;
+pub mod autocomplete_provider;
+pub mod autocomplete_cache;
+pub mod job_runner;
+pub mod api_token_service;
+pub mod mailer;
+pub mod password_hasher;
+pub mod two_factor_service;
+pub mod rate_limiter;
+pub mod audit_service;
\ No newline at end of file
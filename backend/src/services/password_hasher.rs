@@ -0,0 +1,213 @@
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, SaltString};
+use argon2::{Algorithm, Argon2, Params, PasswordVerifier, Version};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Password hashing algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordHashAlgorithm {
+    Argon2id,
+    Bcrypt,
+}
+
+impl FromStr for PasswordHashAlgorithm {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "argon2id" | "argon2" => Ok(Self::Argon2id),
+            "bcrypt" => Ok(Self::Bcrypt),
+            other => Err(AppError::Internal(format!(
+                "Unknown password hasher '{}' (expected argon2id or bcrypt)",
+                other
+            ))),
+        }
+    }
+}
+
+/// One password hashing algorithm with its settings
+pub trait PasswordHasher: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Hash a password with the current settings
+    fn hash(&self, password: &str) -> Result<String>;
+
+    /// Whether a stored hash was made by this algorithm, whatever its settings
+    fn recognizes(&self, hash: &str) -> bool;
+
+    /// Check a password against a hash of this algorithm
+    fn verify(&self, password: &str, hash: &str) -> Result<bool>;
+
+    /// Whether a hash of this algorithm was made with other settings
+    fn is_outdated(&self, hash: &str) -> bool;
+}
+
+/// Argon2id in the PHC string format
+pub struct Argon2Hasher {
+    params: Params,
+}
+
+impl Argon2Hasher {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| AppError::Internal(format!("Invalid Argon2 parameters: {}", e)))?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl PasswordHasher for Argon2Hasher {
+    fn name(&self) -> &'static str {
+        "argon2id"
+    }
+
+    fn hash(&self, password: &str) -> Result<String> {
+        use argon2::PasswordHasher as _;
+
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|_| AppError::Internal("Failed to hash password".into()))
+    }
+
+    fn recognizes(&self, hash: &str) -> bool {
+        hash.starts_with("$argon2")
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool> {
+        let parsed = PasswordHash::new(hash)
+            .map_err(|_| AppError::Internal("Password verification failed".into()))?;
+        // The parameters stored in the hash are used, not the current ones
+        Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+    }
+
+    fn is_outdated(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return true;
+        };
+        let Ok(params) = Params::try_from(&parsed) else {
+            return true;
+        };
+
+        parsed.algorithm != Algorithm::Argon2id.ident()
+            || parsed.version != Some(Version::V0x13.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+    }
+}
+
+/// bcrypt, hashed by older versions of Gourmestre
+pub struct BcryptHasher {
+    cost: u32,
+}
+
+impl BcryptHasher {
+    pub fn new(cost: u32) -> Self {
+        Self { cost }
+    }
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn name(&self) -> &'static str {
+        "bcrypt"
+    }
+
+    fn hash(&self, password: &str) -> Result<String> {
+        bcrypt::hash(password, self.cost)
+            .map_err(|_| AppError::Internal("Failed to hash password".into()))
+    }
+
+    fn recognizes(&self, hash: &str) -> bool {
+        hash.starts_with("$2")
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool> {
+        bcrypt::verify(password, hash)
+            .map_err(|_| AppError::Internal("Password verification failed".into()))
+    }
+
+    fn is_outdated(&self, hash: &str) -> bool {
+        // "$2b$12$..." carries its cost in the third field
+        hash.split('$')
+            .nth(2)
+            .and_then(|cost| cost.parse::<u32>().ok())
+            .is_none_or(|cost| cost != self.cost)
+    }
+}
+
+/// Outcome of checking a password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordCheck {
+    pub valid: bool,
+    /// The password is right but its hash should be replaced by a hash from
+    /// the preferred algorithm and settings
+    pub needs_rehash: bool,
+}
+
+/// Hashes new passwords with the configured algorithm and verifies hashes
+/// of every supported one, so existing users keep logging in after a switch.
+pub struct PasswordHashing {
+    preferred: Arc<dyn PasswordHasher>,
+    hashers: Vec<Arc<dyn PasswordHasher>>,
+}
+
+impl PasswordHashing {
+    pub fn new(preferred: Arc<dyn PasswordHasher>, others: Vec<Arc<dyn PasswordHasher>>) -> Self {
+        let mut hashers = vec![preferred.clone()];
+        hashers.extend(others);
+        Self { preferred, hashers }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let argon2: Arc<dyn PasswordHasher> = Arc::new(Argon2Hasher::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+        )?);
+        let bcrypt: Arc<dyn PasswordHasher> = Arc::new(BcryptHasher::new(config.bcrypt_cost));
+
+        Ok(match config.password_hasher {
+            PasswordHashAlgorithm::Argon2id => Self::new(argon2, vec![bcrypt]),
+            PasswordHashAlgorithm::Bcrypt => Self::new(bcrypt, vec![argon2]),
+        })
+    }
+
+    /// Hash a new password. Hashing is slow on purpose, so it runs on the
+    /// blocking thread pool instead of holding up an async worker.
+    pub async fn hash(&self, password: &str) -> Result<String> {
+        let hasher = self.preferred.clone();
+        let password = password.to_string();
+        run_blocking(move || hasher.hash(&password)).await
+    }
+
+    /// Check a password against a stored hash of any supported algorithm
+    pub async fn verify(&self, password: &str, hash: &str) -> Result<PasswordCheck> {
+        let hasher = self.hashers
+            .iter()
+            .find(|hasher| hasher.recognizes(hash))
+            .cloned()
+            .ok_or_else(|| AppError::Internal("Unknown password hash format".into()))?;
+
+        let needs_rehash = hasher.name() != self.preferred.name() || hasher.is_outdated(hash);
+        let (password, hash) = (password.to_string(), hash.to_string());
+        let valid = run_blocking(move || hasher.verify(&password, &hash)).await?;
+
+        Ok(PasswordCheck { valid, needs_rehash: valid && needs_rehash })
+    }
+}
+
+/// Run a CPU-bound hashing call on the blocking thread pool
+async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Internal(format!("Password hashing task failed: {}", e)))?
+}
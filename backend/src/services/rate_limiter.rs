@@ -0,0 +1,283 @@
+use crate::config::Config;
+use crate::domain::rate_limit::{RateDecision, RateLimit, RateLimitStoreKind, RouteGroup};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// Keeps the token buckets of the rate limiter
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Take a token from the bucket of a key, creating it full if missing
+    async fn take(&self, key: &str, limit: RateLimit) -> Result<RateDecision>;
+
+    /// Forget buckets that have filled up again, returning how many. A full
+    /// bucket behaves like a missing one.
+    async fn prune(&self) -> Result<u64>;
+}
+
+/// Build the store selected in the configuration
+pub fn store_from_config(config: &Config) -> Arc<dyn RateLimitStore> {
+    match config.rate_limit_store {
+        RateLimitStoreKind::Memory => Arc::new(InMemoryRateLimitStore::default()),
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    limit: RateLimit,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.refill_per_sec())
+            .min(f64::from(self.limit.burst));
+        self.updated = now;
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= f64::from(self.limit.burst)
+    }
+
+    /// Refill up to `now`, then take a token if one is left
+    fn take(&mut self, now: Instant) -> RateDecision {
+        self.refill(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return RateDecision::Allowed;
+        }
+
+        let retry_after_secs = ((1.0 - self.tokens) / self.limit.refill_per_sec()).ceil() as u64;
+        RateDecision::Limited { retry_after_secs: retry_after_secs.max(1) }
+    }
+}
+
+/// Buckets in the process memory. Each backend instance counts on its own.
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn take(&self, key: &str, limit: RateLimit) -> Result<RateDecision> {
+        let now = Instant::now();
+        let mut buckets = self.buckets
+            .lock()
+            .map_err(|_| AppError::Internal("Rate limit store lock poisoned".into()))?;
+
+        let bucket = buckets.entry(key.to_string()).or_insert_with(|| Bucket {
+            tokens: f64::from(limit.burst),
+            updated: now,
+            limit,
+        });
+        // Settings may have changed since the bucket was created
+        bucket.limit = limit;
+        Ok(bucket.take(now))
+    }
+
+    async fn prune(&self) -> Result<u64> {
+        let now = Instant::now();
+        let mut buckets = self.buckets
+            .lock()
+            .map_err(|_| AppError::Internal("Rate limit store lock poisoned".into()))?;
+
+        let before = buckets.len();
+        buckets.retain(|_, bucket| {
+            bucket.refill(now);
+            !bucket.is_full()
+        });
+
+        Ok((before - buckets.len()) as u64)
+    }
+}
+
+/// Token bucket rate limiting per client and route group.
+///
+/// Clients logged in with a session cookie are counted per account, others,
+/// API tokens included, per IP address. The auth routes always count per IP
+/// address so that guessing a password cannot throttle its owner.
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    limits: HashMap<RouteGroup, RateLimit>,
+}
+
+impl RateLimiter {
+    pub fn new(store: Arc<dyn RateLimitStore>, limits: HashMap<RouteGroup, RateLimit>) -> Self {
+        Self { store, limits }
+    }
+
+    pub fn from_config(config: &Config, store: Arc<dyn RateLimitStore>) -> Self {
+        let limits = if config.rate_limit_enabled {
+            HashMap::from([
+                (RouteGroup::Auth, RateLimit {
+                    per_minute: config.rate_limit_auth_per_minute,
+                    burst: config.rate_limit_auth_burst,
+                }),
+                (RouteGroup::Autocomplete, RateLimit {
+                    per_minute: config.rate_limit_autocomplete_per_minute,
+                    burst: config.rate_limit_autocomplete_burst,
+                }),
+                (RouteGroup::Api, RateLimit {
+                    per_minute: config.rate_limit_api_per_minute,
+                    burst: config.rate_limit_api_burst,
+                }),
+            ])
+        } else {
+            HashMap::new()
+        };
+
+        Self::new(store, limits)
+    }
+
+    /// Take a token for a request of a client, identified by `client_key`.
+    /// Answers `AppError::RateLimited` when the bucket is empty. A failing
+    /// store lets the request through.
+    pub async fn check(&self, group: RouteGroup, client_key: &str) -> Result<()> {
+        let Some(limit) = self.limits.get(&group).filter(|limit| limit.is_enabled()) else {
+            return Ok(());
+        };
+
+        let key = format!("{}:{}", group.name(), client_key);
+        match self.store.take(&key, *limit).await {
+            Ok(RateDecision::Allowed) => Ok(()),
+            Ok(RateDecision::Limited { retry_after_secs }) => {
+                debug!("Rate limited {} for {}s", key, retry_after_secs);
+                Err(AppError::RateLimited(retry_after_secs))
+            }
+            Err(e) => {
+                warn!("Rate limit store {} failed: {}", self.store.name(), e);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn bucket(per_minute: u32, burst: u32, now: Instant) -> Bucket {
+        Bucket {
+            tokens: f64::from(burst),
+            updated: now,
+            limit: RateLimit { per_minute, burst },
+        }
+    }
+
+    fn retry_after(decision: RateDecision) -> u64 {
+        match decision {
+            RateDecision::Limited { retry_after_secs } => retry_after_secs,
+            RateDecision::Allowed => panic!("expected the request to be limited"),
+        }
+    }
+
+    #[test]
+    fn allows_a_burst_then_limits() {
+        let now = Instant::now();
+        let mut bucket = bucket(60, 3, now);
+
+        for _ in 0..3 {
+            assert_eq!(bucket.take(now), RateDecision::Allowed);
+        }
+        assert_eq!(retry_after(bucket.take(now)), 1);
+    }
+
+    #[test]
+    fn refills_at_the_per_minute_rate() {
+        let start = Instant::now();
+        let mut bucket = bucket(30, 2, start);
+        bucket.take(start);
+        bucket.take(start);
+
+        // One token every two seconds
+        assert!(matches!(bucket.take(start + Duration::from_secs(1)), RateDecision::Limited { .. }));
+        assert_eq!(bucket.take(start + Duration::from_secs(2)), RateDecision::Allowed);
+        assert!(matches!(bucket.take(start + Duration::from_secs(2)), RateDecision::Limited { .. }));
+    }
+
+    #[test]
+    fn refill_stops_at_the_burst() {
+        let start = Instant::now();
+        let mut bucket = bucket(60, 2, start);
+        bucket.take(start);
+        bucket.take(start);
+
+        let later = start + Duration::from_secs(3600);
+        assert_eq!(bucket.take(later), RateDecision::Allowed);
+        assert_eq!(bucket.take(later), RateDecision::Allowed);
+        assert!(matches!(bucket.take(later), RateDecision::Limited { .. }));
+    }
+
+    #[test]
+    fn retry_after_rounds_up_to_whole_seconds() {
+        let start = Instant::now();
+
+        // A token every 60 / 7 = 8.57 seconds
+        let mut bucket = bucket(7, 1, start);
+        bucket.take(start);
+        assert_eq!(retry_after(bucket.take(start)), 9);
+
+        // Part of the token has come back
+        assert_eq!(retry_after(bucket.take(start + Duration::from_secs(4))), 5);
+    }
+
+    #[test]
+    fn retry_after_is_at_least_one_second() {
+        let start = Instant::now();
+        let mut bucket = bucket(6000, 1, start);
+        bucket.take(start);
+
+        assert_eq!(retry_after(bucket.take(start + Duration::from_millis(5))), 1);
+    }
+
+    #[tokio::test]
+    async fn prune_forgets_buckets_that_filled_up() {
+        let store = InMemoryRateLimitStore::default();
+        let slow = RateLimit { per_minute: 1, burst: 1 };
+        let full = RateLimit { per_minute: 60, burst: 5 };
+        store.take("slow", slow).await.expect("take");
+        store.take("full", full).await.expect("take");
+        store.buckets.lock().expect("lock").get_mut("full").expect("bucket").tokens = 5.0;
+
+        assert_eq!(store.prune().await.expect("prune"), 1);
+        assert!(store.buckets.lock().expect("lock").contains_key("slow"));
+    }
+
+    #[tokio::test]
+    async fn limits_each_group_and_client_separately() {
+        let limiter = RateLimiter::new(
+            Arc::new(InMemoryRateLimitStore::default()),
+            HashMap::from([
+                (RouteGroup::Auth, RateLimit { per_minute: 1, burst: 1 }),
+                (RouteGroup::Api, RateLimit { per_minute: 0, burst: 10 }),
+            ]),
+        );
+
+        limiter.check(RouteGroup::Auth, "1.2.3.4").await.expect("first login");
+        assert!(matches!(
+            limiter.check(RouteGroup::Auth, "1.2.3.4").await,
+            Err(AppError::RateLimited(60))
+        ));
+        limiter.check(RouteGroup::Auth, "5.6.7.8").await.expect("other client");
+
+        // A zero rate turns the limit off, a missing group has none
+        for _ in 0..20 {
+            limiter.check(RouteGroup::Api, "1.2.3.4").await.expect("api");
+            limiter.check(RouteGroup::Autocomplete, "1.2.3.4").await.expect("autocomplete");
+        }
+    }
+}
@@ -1,20 +1,33 @@
 use crate::domain::restaurant::{
-    Restaurant, RestaurantId, CreateRestaurant, CreateRestaurantRequest, 
-    UpdateRestaurant, UpdateRestaurantRequest, RestaurantResponse, 
-    RestaurantQuery, RestaurantStatsResponse
+    Restaurant, RestaurantId, CreateRestaurantRequest,
+    UpdateRestaurantRequest, RestaurantResponse,
+    RestaurantQuery, RestaurantStatsResponse, RestaurantSearchRequest,
+    RestaurantSearchResult, RestaurantSearchResponse, RestaurantSort,
+    NearbyRestaurantResponse
 };
+use crate::domain::geo::{round_distance, NearbyQuery, WithinQuery};
+use crate::domain::pagination::{CursorPage, PageRequest};
+use crate::domain::social::{ActivityKind, RecordActivity};
 use crate::domain::user::UserId;
 use crate::repositories::restaurant_repository::RestaurantRepository;
+use crate::services::social_service::SocialService;
+use crate::services::tag_service::TagService;
 use crate::error::{AppError, Result};
 use std::sync::Arc;
 
 pub struct RestaurantService {
     repo: Arc<dyn RestaurantRepository>,
+    tag_service: Arc<TagService>,
+    social_service: Arc<SocialService>,
 }
 
 impl RestaurantService {
-    pub fn new(repo: Arc<dyn RestaurantRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<dyn RestaurantRepository>,
+        tag_service: Arc<TagService>,
+        social_service: Arc<SocialService>,
+    ) -> Self {
+        Self { repo, tag_service, social_service }
     }
 
     /// Create a new restaurant
@@ -24,7 +37,10 @@ impl RestaurantService {
         owner_id: UserId,
     ) -> Result<RestaurantResponse> {
         let command = request.to_command(owner_id)?;
+        self.tag_service.resolve_tags(owner_id, &command.tag_ids).await?;
         let restaurant = self.repo.create(command).await?;
+        self.record_restaurant_activity(ActivityKind::RestaurantAdded, &restaurant)
+            .await;
         Ok(RestaurantResponse::from(restaurant))
     }
 
@@ -33,12 +49,14 @@ impl RestaurantService {
         &self,
         owner_id: UserId,
         query: RestaurantQuery,
-    ) -> Result<Vec<RestaurantResponse>> {
+    ) -> Result<CursorPage<RestaurantResponse>> {
+        let tag_ids = query.tag_ids()?;
+        let page = query.page()?;
         let restaurants = self.repo
-            .find_by_owner(owner_id, query.favorites.unwrap_or(false), query.limit)
+            .find_by_owner(owner_id, query.favorites.unwrap_or(false), &tag_ids, &page)
             .await?;
 
-        Ok(restaurants.into_iter().map(RestaurantResponse::from).collect())
+        Ok(restaurants.map(RestaurantResponse::from))
     }
 
     /// Get a single restaurant by ID
@@ -68,11 +86,26 @@ impl RestaurantService {
         owner_id: UserId,
     ) -> Result<RestaurantResponse> {
         let command = request.to_command(id, owner_id)?;
+
+        // Only a changed rating is worth telling followers about
+        let previous_rating = match command.rating {
+            Some(Some(_)) => self.repo.find_by_id(id).await?.map(|r| r.rating),
+            _ => None,
+        };
+
         let restaurant = self.repo
             .update(command)
             .await?
             .ok_or_else(|| AppError::NotFound("Restaurant not found or access denied".into()))?;
 
+        if let Some(previous) = previous_rating
+            && restaurant.rating.is_some()
+            && restaurant.rating != previous
+        {
+            self.record_restaurant_activity(ActivityKind::RestaurantRated, &restaurant)
+                .await;
+        }
+
         Ok(RestaurantResponse::from(restaurant))
     }
 
@@ -95,20 +128,80 @@ impl RestaurantService {
     }
 
     /// Get favorite restaurants
-    pub async fn get_favorites(&self, owner_id: UserId) -> Result<Vec<RestaurantResponse>> {
+    pub async fn get_favorites(
+        &self,
+        owner_id: UserId,
+        query: RestaurantQuery,
+    ) -> Result<CursorPage<RestaurantResponse>> {
+        let page = query.page()?;
         let restaurants = self.repo
-            .find_by_owner(owner_id, true, None)
+            .find_by_owner(owner_id, true, &[], &page)
             .await?;
 
-        Ok(restaurants.into_iter().map(RestaurantResponse::from).collect())
+        Ok(restaurants.map(RestaurantResponse::from))
     }
 
     /// Get recent restaurants
     pub async fn get_recent(&self, owner_id: UserId, limit: Option<i64>) -> Result<Vec<RestaurantResponse>> {
+        let page = PageRequest::new(RestaurantSort::CreatedAt, None, limit, None)?;
         let restaurants = self.repo
-            .find_by_owner(owner_id, false, limit)
+            .find_by_owner(owner_id, false, &[], &page)
             .await?;
 
+        Ok(restaurants.items.into_iter().map(RestaurantResponse::from).collect())
+    }
+
+    /// Restaurants within a radius of a point, closest first
+    pub async fn get_nearby(&self, owner_id: UserId, query: NearbyQuery) -> Result<Vec<NearbyRestaurantResponse>> {
+        let search = query.to_command()?;
+        let restaurants = self.repo.find_nearby(owner_id, &search).await?;
+
+        Ok(restaurants
+            .into_iter()
+            .map(|(restaurant, distance_km)| NearbyRestaurantResponse {
+                restaurant: RestaurantResponse::from(restaurant),
+                distance_km: round_distance(distance_km),
+            })
+            .collect())
+    }
+
+    /// Restaurants inside a map bounding box
+    pub async fn get_within(&self, owner_id: UserId, query: WithinQuery) -> Result<Vec<RestaurantResponse>> {
+        let (bounds, limit) = query.to_command()?;
+        let restaurants = self.repo.find_within(owner_id, &bounds, limit).await?;
+
         Ok(restaurants.into_iter().map(RestaurantResponse::from).collect())
     }
-}
\ No newline at end of file
+
+    /// Full-text search over the user's restaurants
+    pub async fn search(
+        &self,
+        request: RestaurantSearchRequest,
+        owner_id: UserId,
+    ) -> Result<RestaurantSearchResponse> {
+        let search = request.to_command(owner_id)?;
+        let (hits, total) = self.repo.search(&search).await?;
+
+        Ok(RestaurantSearchResponse {
+            results: hits.into_iter().map(RestaurantSearchResult::from).collect(),
+            total,
+            page: search.page,
+            page_size: search.page_size,
+            total_pages: (total + search.page_size - 1) / search.page_size,
+        })
+    }
+
+    async fn record_restaurant_activity(&self, kind: ActivityKind, restaurant: &Restaurant) {
+        self.social_service
+            .record_activity(RecordActivity {
+                user_id: restaurant.owner_id,
+                kind,
+                restaurant_id: Some(restaurant.id),
+                wishlist_item_id: None,
+                name: restaurant.name.clone(),
+                city: restaurant.city.clone(),
+                rating: restaurant.rating,
+            })
+            .await;
+    }
+}
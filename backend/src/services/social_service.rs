@@ -0,0 +1,159 @@
+use crate::domain::social::{
+    ActivityResponse, FollowResponse, PrivacySettingsRequest, PrivacySettingsResponse,
+    RecordActivity, SocialPageQuery, UserProfile, UserProfileResponse,
+};
+use crate::domain::pagination::CursorPage;
+use crate::domain::user::UserId;
+use crate::repositories::activity_repository::ActivityRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::error::{AppError, Result};
+use std::sync::Arc;
+use tracing::warn;
+
+pub struct SocialService {
+    user_repo: Arc<dyn UserRepository>,
+    activity_repo: Arc<dyn ActivityRepository>,
+}
+
+impl SocialService {
+    pub fn new(user_repo: Arc<dyn UserRepository>, activity_repo: Arc<dyn ActivityRepository>) -> Self {
+        Self { user_repo, activity_repo }
+    }
+
+    /// Get a user profile with follower counts
+    pub async fn get_profile(&self, username: &str, viewer_id: UserId) -> Result<UserProfileResponse> {
+        let profile = self.find_profile(username).await?;
+        let is_followed = profile.user_id != viewer_id
+            && self.user_repo.is_following(viewer_id, profile.user_id).await?;
+
+        Ok(UserProfileResponse {
+            username: profile.username,
+            is_private: profile.is_private,
+            follower_count: profile.follower_count,
+            following_count: profile.following_count,
+            is_followed,
+            created_at: profile.created_at,
+        })
+    }
+
+    /// Follow a user. Following someone already followed is a no-op.
+    pub async fn follow(&self, username: &str, follower_id: UserId) -> Result<UserProfileResponse> {
+        let profile = self.find_profile(username).await?;
+        if profile.user_id == follower_id {
+            return Err(AppError::Validation("You cannot follow yourself".into()));
+        }
+        if profile.is_private {
+            return Err(AppError::Authorization("This profile is private".into()));
+        }
+
+        self.user_repo.follow(follower_id, profile.user_id).await?;
+        self.get_profile(username, follower_id).await
+    }
+
+    /// Stop following a user
+    pub async fn unfollow(&self, username: &str, follower_id: UserId) -> Result<()> {
+        let profile = self.find_profile(username).await?;
+        let removed = self.user_repo.unfollow(follower_id, profile.user_id).await?;
+        if !removed {
+            return Err(AppError::NotFound("You do not follow this user".into()));
+        }
+        Ok(())
+    }
+
+    /// List the followers of a user
+    pub async fn get_followers(
+        &self,
+        username: &str,
+        viewer_id: UserId,
+        query: SocialPageQuery,
+    ) -> Result<CursorPage<FollowResponse>> {
+        let page = query.page()?;
+        let profile = self.find_visible_profile(username, viewer_id).await?;
+        let followers = self.user_repo.find_followers(profile.user_id, &page).await?;
+        Ok(followers.map(FollowResponse::from))
+    }
+
+    /// List the users a user follows
+    pub async fn get_following(
+        &self,
+        username: &str,
+        viewer_id: UserId,
+        query: SocialPageQuery,
+    ) -> Result<CursorPage<FollowResponse>> {
+        let page = query.page()?;
+        let profile = self.find_visible_profile(username, viewer_id).await?;
+        let following = self.user_repo.find_following(profile.user_id, &page).await?;
+        Ok(following.map(FollowResponse::from))
+    }
+
+    /// List the recent activity of a user
+    pub async fn get_user_activity(
+        &self,
+        username: &str,
+        viewer_id: UserId,
+        query: SocialPageQuery,
+    ) -> Result<CursorPage<ActivityResponse>> {
+        let page = query.page()?;
+        let profile = self.find_visible_profile(username, viewer_id).await?;
+        let events = self.activity_repo.find_by_user(profile.user_id, &page).await?;
+        Ok(events.map(ActivityResponse::from))
+    }
+
+    /// Activity of the users the viewer follows, newest first
+    pub async fn get_feed(
+        &self,
+        viewer_id: UserId,
+        query: SocialPageQuery,
+    ) -> Result<CursorPage<ActivityResponse>> {
+        let page = query.page()?;
+        let events = self.activity_repo.find_feed(viewer_id, &page).await?;
+        Ok(events.map(ActivityResponse::from))
+    }
+
+    /// Get the privacy setting of the current user
+    pub async fn get_privacy(&self, user_id: UserId) -> Result<PrivacySettingsResponse> {
+        let user = self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+        Ok(PrivacySettingsResponse { is_private: user.is_private })
+    }
+
+    /// Change the privacy setting of the current user. Existing followers
+    /// are kept, but stop seeing the activity while the profile is private.
+    pub async fn set_privacy(
+        &self,
+        request: PrivacySettingsRequest,
+        user_id: UserId,
+    ) -> Result<PrivacySettingsResponse> {
+        self.user_repo.set_private(user_id, request.is_private).await?;
+        self.get_privacy(user_id).await
+    }
+
+    /// Record an activity event. Failures are logged and swallowed: the feed
+    /// must never make the action it reports on fail.
+    pub async fn record_activity(&self, activity: RecordActivity) {
+        let user_id = activity.user_id;
+        let kind = activity.kind;
+        if let Err(e) = self.activity_repo.record(activity).await {
+            warn!("Failed to record {:?} activity for user {}: {}", kind, user_id.0, e);
+        }
+    }
+
+    async fn find_profile(&self, username: &str) -> Result<UserProfile> {
+        self.user_repo
+            .find_profile(username)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+
+    /// Private profiles are only visible to their owner
+    async fn find_visible_profile(&self, username: &str, viewer_id: UserId) -> Result<UserProfile> {
+        let profile = self.find_profile(username).await?;
+        if profile.is_private && profile.user_id != viewer_id {
+            return Err(AppError::Authorization("This profile is private".into()));
+        }
+        Ok(profile)
+    }
+}
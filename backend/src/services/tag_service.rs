@@ -0,0 +1,175 @@
+use crate::domain::tag::{
+    TagId, CreateTagRequest, UpdateTagRequest, SetTagsRequest, TagResponse
+};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::wishlist::WishlistId;
+use crate::domain::user::UserId;
+use crate::repositories::tag_repository::TagRepository;
+use crate::repositories::restaurant_repository::RestaurantRepository;
+use crate::repositories::wishlist_repository::WishlistRepository;
+use crate::error::{AppError, Result};
+use std::sync::Arc;
+
+pub struct TagService {
+    repo: Arc<dyn TagRepository>,
+    restaurant_repo: Arc<dyn RestaurantRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+}
+
+impl TagService {
+    pub fn new(
+        repo: Arc<dyn TagRepository>,
+        restaurant_repo: Arc<dyn RestaurantRepository>,
+        wishlist_repo: Arc<dyn WishlistRepository>,
+    ) -> Self {
+        Self { repo, restaurant_repo, wishlist_repo }
+    }
+
+    /// Create a new tag
+    pub async fn create_tag(
+        &self,
+        request: CreateTagRequest,
+        owner_id: UserId,
+    ) -> Result<TagResponse> {
+        let command = request.to_command(owner_id)?;
+
+        if self.repo.find_by_name(owner_id, &command.name).await?.is_some() {
+            return Err(AppError::Conflict("Tag already exists".into()));
+        }
+
+        let tag = self.repo.create(command).await?;
+        Ok(TagResponse::from(tag))
+    }
+
+    /// Get all tags for a user
+    pub async fn get_tags(&self, owner_id: UserId) -> Result<Vec<TagResponse>> {
+        let tags = self.repo.find_by_owner(owner_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Update a tag
+    pub async fn update_tag(
+        &self,
+        id: TagId,
+        request: UpdateTagRequest,
+        owner_id: UserId,
+    ) -> Result<TagResponse> {
+        let command = request.to_command(id, owner_id)?;
+
+        if let Some(name) = &command.name
+            && let Some(existing) = self.repo.find_by_name(owner_id, name).await?
+            && existing.id != id
+        {
+            return Err(AppError::Conflict("Tag already exists".into()));
+        }
+
+        let tag = self.repo
+            .update(command)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tag not found or access denied".into()))?;
+
+        Ok(TagResponse::from(tag))
+    }
+
+    /// Delete a tag (detaches it from every restaurant and wishlist item)
+    pub async fn delete_tag(&self, id: TagId, owner_id: UserId) -> Result<()> {
+        let deleted = self.repo.delete(id, owner_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Tag not found or access denied".into()));
+        }
+        Ok(())
+    }
+
+    /// Get the tags of a restaurant
+    pub async fn get_restaurant_tags(
+        &self,
+        restaurant_id: RestaurantId,
+        owner_id: UserId,
+    ) -> Result<Vec<TagResponse>> {
+        let restaurant = self.restaurant_repo
+            .find_by_id(restaurant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Restaurant not found".into()))?;
+
+        // Check ownership
+        if restaurant.owner_id != owner_id {
+            return Err(AppError::Authorization("Access denied".into()));
+        }
+
+        let tags = self.repo.find_by_restaurant(restaurant_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Replace the tags of a restaurant
+    pub async fn set_restaurant_tags(
+        &self,
+        restaurant_id: RestaurantId,
+        request: SetTagsRequest,
+        owner_id: UserId,
+    ) -> Result<Vec<TagResponse>> {
+        let tag_ids = self.resolve_tags(owner_id, &request.tag_ids()).await?;
+
+        let updated = self.repo
+            .set_restaurant_tags(restaurant_id, owner_id, &tag_ids)
+            .await?;
+        if !updated {
+            return Err(AppError::NotFound("Restaurant not found or access denied".into()));
+        }
+
+        let tags = self.repo.find_by_restaurant(restaurant_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Get the tags of a wishlist item
+    pub async fn get_wishlist_item_tags(
+        &self,
+        item_id: WishlistId,
+        owner_id: UserId,
+    ) -> Result<Vec<TagResponse>> {
+        let item = self.wishlist_repo
+            .find_by_id(item_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Wishlist item not found".into()))?;
+
+        // Check ownership
+        if item.owner_id != owner_id {
+            return Err(AppError::Authorization("Access denied".into()));
+        }
+
+        let tags = self.repo.find_by_wishlist_item(item_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Replace the tags of a wishlist item
+    pub async fn set_wishlist_item_tags(
+        &self,
+        item_id: WishlistId,
+        request: SetTagsRequest,
+        owner_id: UserId,
+    ) -> Result<Vec<TagResponse>> {
+        let tag_ids = self.resolve_tags(owner_id, &request.tag_ids()).await?;
+
+        let updated = self.repo
+            .set_wishlist_item_tags(item_id, owner_id, &tag_ids)
+            .await?;
+        if !updated {
+            return Err(AppError::NotFound("Wishlist item not found or access denied".into()));
+        }
+
+        let tags = self.repo.find_by_wishlist_item(item_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Check that every tag belongs to the user
+    pub async fn resolve_tags(&self, owner_id: UserId, tag_ids: &[TagId]) -> Result<Vec<TagId>> {
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let owned = self.repo.find_by_ids(owner_id, tag_ids).await?;
+        if owned.len() != tag_ids.len() {
+            return Err(AppError::Validation("Unknown tag".into()));
+        }
+        Ok(owned.into_iter().map(|tag| tag.id).collect())
+    }
+}
\ No newline at end of file
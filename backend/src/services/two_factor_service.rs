@@ -0,0 +1,166 @@
+use crate::domain::token::hash_token;
+use crate::domain::two_factor::{
+    TwoFactor, TwoFactorStatusResponse, TwoFactorSetupResponse, TwoFactorCodeRequest,
+    RecoveryCodesResponse, RECOVERY_CODE_COUNT, TOTP_ISSUER, TOTP_STEP_SECS,
+    generate_totp_secret, generate_recovery_code, normalize_recovery_code, is_totp_code,
+};
+use crate::domain::user::{User, UserId};
+use crate::domain::session::ClientInfo;
+use crate::domain::audit::SecurityEventType;
+use crate::repositories::two_factor_repository::TwoFactorRepository;
+use crate::services::audit_service::AuditService;
+use crate::error::{AppError, Result};
+use chrono::Utc;
+use std::sync::Arc;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+pub struct TwoFactorService {
+    repo: Arc<dyn TwoFactorRepository>,
+    audit: Arc<AuditService>,
+}
+
+impl TwoFactorService {
+    pub fn new(repo: Arc<dyn TwoFactorRepository>, audit: Arc<AuditService>) -> Self {
+        Self { repo, audit }
+    }
+
+    /// Whether logins of the user need a second factor
+    pub async fn is_enabled(&self, user_id: UserId) -> Result<bool> {
+        Ok(self.repo.find(user_id).await?.is_some_and(|totp| totp.is_enabled()))
+    }
+
+    /// Two-factor status of the user
+    pub async fn status(&self, user_id: UserId) -> Result<TwoFactorStatusResponse> {
+        let enabled = self.is_enabled(user_id).await?;
+        let recovery_codes_remaining = if enabled {
+            self.repo.count_recovery_codes(user_id).await?
+        } else {
+            0
+        };
+
+        Ok(TwoFactorStatusResponse { enabled, recovery_codes_remaining })
+    }
+
+    /// Start enrolling, with a new secret. Nothing changes for logins until
+    /// `enable` receives a first code.
+    pub async fn setup(&self, user: &User) -> Result<TwoFactorSetupResponse> {
+        let totp = totp(generate_totp_secret(), &user.username)?;
+        let secret = totp.get_secret_base32();
+
+        if !self.repo.start_setup(user.id, &secret).await? {
+            return Err(AppError::Conflict("Two-factor authentication is already enabled".into()));
+        }
+
+        Ok(TwoFactorSetupResponse { secret, otpauth_uri: totp.get_url() })
+    }
+
+    /// Confirm the setup with a first code and hand out the recovery codes
+    pub async fn enable(
+        &self,
+        user: &User,
+        request: TwoFactorCodeRequest,
+        client: &ClientInfo,
+    ) -> Result<RecoveryCodesResponse> {
+        let pending = self.repo
+            .find(user.id)
+            .await?
+            .ok_or_else(|| AppError::Validation("Start two-factor setup first".into()))?;
+        if pending.is_enabled() {
+            return Err(AppError::Conflict("Two-factor authentication is already enabled".into()));
+        }
+
+        let step = matching_step(&pending, request.code.trim())?
+            .ok_or_else(|| AppError::Validation("Invalid two-factor code".into()))?;
+
+        let codes = new_recovery_codes();
+        if !self.repo.enable(user.id, step, &hash_codes(&codes)).await? {
+            return Err(AppError::Conflict("Two-factor authentication is already enabled".into()));
+        }
+        self.audit.record(Some(user.id), SecurityEventType::TwoFactorEnabled, client, None).await;
+
+        Ok(RecoveryCodesResponse { recovery_codes: codes })
+    }
+
+    /// Turn two-factor authentication off, once `AuthService::check_two_factor_code`
+    /// accepted a code
+    pub async fn disable(&self, user: &User, client: &ClientInfo) -> Result<()> {
+        self.repo.disable(user.id).await?;
+        self.audit.record(Some(user.id), SecurityEventType::TwoFactorDisabled, client, None).await;
+        Ok(())
+    }
+
+    /// Replace the recovery codes, once `AuthService::check_two_factor_code`
+    /// accepted a code
+    pub async fn regenerate_recovery_codes(&self, user: &User) -> Result<RecoveryCodesResponse> {
+        let codes = new_recovery_codes();
+        self.repo.replace_recovery_codes(user.id, &hash_codes(&codes)).await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes: codes })
+    }
+
+    /// Check a TOTP code or a recovery code of a user with two-factor
+    /// authentication enabled. Either works only once.
+    pub async fn verify(&self, user_id: UserId, code: &str) -> Result<bool> {
+        let Some(totp) = self.repo.find(user_id).await?.filter(TwoFactor::is_enabled) else {
+            return Ok(false);
+        };
+
+        let code = code.trim();
+        if is_totp_code(code) {
+            return match matching_step(&totp, code)? {
+                Some(step) => self.repo.use_step(user_id, step).await,
+                None => Ok(false),
+            };
+        }
+
+        let code = normalize_recovery_code(code);
+        if code.is_empty() {
+            return Ok(false);
+        }
+        self.repo.use_recovery_code(user_id, &hash_token(&code)).await
+    }
+}
+
+/// TOTP as authenticator apps compute it by default: SHA-1, 6 digits, 30 seconds
+fn totp(secret: Vec<u8>, account_name: &str) -> Result<TOTP> {
+    // The otpauth URI uses ':' as a separator
+    let account_name = account_name.replace(':', "_");
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP_SECS,
+        secret,
+        Some(TOTP_ISSUER.to_string()),
+        account_name,
+    )
+    .map_err(|e| AppError::Internal(format!("Invalid TOTP settings: {}", e)))
+}
+
+/// Step whose code matches, allowing one step of clock drift either way
+fn matching_step(two_factor: &TwoFactor, code: &str) -> Result<Option<i64>> {
+    if !is_totp_code(code) {
+        return Ok(None);
+    }
+
+    let secret = Secret::Encoded(two_factor.secret.clone())
+        .to_bytes()
+        .map_err(|_| AppError::Internal("Invalid TOTP secret".into()))?;
+    let totp = totp(secret, "")?;
+
+    let current = Utc::now().timestamp().max(0) as u64 / TOTP_STEP_SECS;
+    Ok((current.saturating_sub(1)..=current + 1)
+        .find(|step| totp.check(code, step * TOTP_STEP_SECS))
+        .map(|step| step as i64))
+}
+
+fn new_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT).map(|_| generate_recovery_code()).collect()
+}
+
+fn hash_codes(codes: &[String]) -> Vec<String> {
+    codes
+        .iter()
+        .map(|code| hash_token(&normalize_recovery_code(code)))
+        .collect()
+}
@@ -1,15 +1,26 @@
-use crate::domain::user::{User, UserId, UserInfoResponse};
+use crate::domain::user::{
+    User, UserId, UserInfoResponse, Username, Email, ChangePasswordRequest, ChangeUsernameRequest,
+    ChangeEmailRequest, DeleteAccountRequest,
+};
+use crate::domain::session::{ClientInfo, SessionData};
+use crate::domain::audit::SecurityEventType;
 use crate::repositories::user_repository::UserRepository;
+use crate::services::auth_service::AuthService;
+use crate::services::audit_service::AuditService;
 use crate::error::{AppError, Result};
+use serde_json::json;
 use std::sync::Arc;
+use tracing::warn;
 
 pub struct UserService {
     repo: Arc<dyn UserRepository>,
+    auth_service: Arc<AuthService>,
+    audit: Arc<AuditService>,
 }
 
 impl UserService {
-    pub fn new(repo: Arc<dyn UserRepository>) -> Self {
-        Self { repo }
+    pub fn new(repo: Arc<dyn UserRepository>, auth_service: Arc<AuthService>, audit: Arc<AuditService>) -> Self {
+        Self { repo, auth_service, audit }
     }
 
     /// Get user by ID
@@ -43,4 +54,89 @@ impl UserService {
         let user = self.repo.find_by_email(email).await?;
         Ok(user.is_some())
     }
-}
\ No newline at end of file
+
+    /// Change the password and log out every other session. Returns the
+    /// session the current login moves to.
+    pub async fn change_password(
+        &self,
+        user: &User,
+        current: &SessionData,
+        request: ChangePasswordRequest,
+        client: ClientInfo,
+    ) -> Result<SessionData> {
+        self.auth_service.check_password(user, &request.current_password, &client).await?;
+        let password_hash = self.auth_service.hash_new_password(request.new_password, &user.username).await?;
+
+        self.repo.update_password(user.id, &password_hash).await?;
+        self.audit.record(Some(user.id), SecurityEventType::PasswordChanged, &client, None).await;
+
+        let (session_data, _) = self.auth_service
+            .revoke_other_sessions(current, client)
+            .await?;
+
+        Ok(session_data)
+    }
+
+    /// Change the username
+    pub async fn change_username(
+        &self,
+        user: &User,
+        request: ChangeUsernameRequest,
+        client: &ClientInfo,
+    ) -> Result<UserInfoResponse> {
+        let username = Username::new(request.username.trim().to_string())?;
+
+        if username.as_str() != user.username {
+            if self.user_exists(username.as_str()).await? {
+                return Err(AppError::Conflict("Username already exists".into()));
+            }
+            self.repo.update_username(user.id, username.as_str()).await?;
+
+            let details = json!({"from": user.username, "to": username.as_str()});
+            self.audit.record(Some(user.id), SecurityEventType::UsernameChanged, client, Some(details)).await;
+        }
+
+        self.get_user(user.id).await
+    }
+
+    /// Change the email address and send a verification link to the new one
+    pub async fn change_email(
+        &self,
+        user: &User,
+        request: ChangeEmailRequest,
+        client: &ClientInfo,
+    ) -> Result<UserInfoResponse> {
+        self.auth_service.check_password(user, &request.current_password, client).await?;
+        let email = Email::new(request.email)?;
+
+        if email.as_str() != user.email {
+            if self.email_exists(email.as_str()).await? {
+                return Err(AppError::Conflict("Email already registered".into()));
+            }
+            self.repo.update_email(user.id, email.as_str()).await?;
+            self.audit.record(Some(user.id), SecurityEventType::EmailChanged, client, None).await;
+
+            let user = User { email: email.into_string(), email_verified_at: None, ..user.clone() };
+            if let Err(e) = self.auth_service.send_verification(&user).await {
+                warn!("Failed to send verification email to user {}: {}", user.id.0, e);
+            }
+        }
+
+        self.get_user(user.id).await
+    }
+
+    /// Delete the account with all its data
+    pub async fn delete_account(&self, user: &User, request: DeleteAccountRequest, client: &ClientInfo) -> Result<()> {
+        self.auth_service.check_password(user, &request.password, client).await?;
+
+        if !self.repo.delete(user.id).await? {
+            return Err(AppError::NotFound("User not found".into()));
+        }
+
+        // Kept after the account is gone, as the log has no foreign key
+        let details = json!({"username": user.username});
+        self.audit.record(Some(user.id), SecurityEventType::AccountDeleted, client, Some(details)).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::domain::visit::{
+    VisitId, CreateVisitRequest, UpdateVisitRequest, VisitResponse
+};
+use crate::domain::restaurant::RestaurantId;
+use crate::domain::user::UserId;
+use crate::repositories::visit_repository::VisitRepository;
+use crate::repositories::restaurant_repository::RestaurantRepository;
+use crate::error::{AppError, Result};
+use std::sync::Arc;
+
+pub struct VisitService {
+    repo: Arc<dyn VisitRepository>,
+    restaurant_repo: Arc<dyn RestaurantRepository>,
+}
+
+impl VisitService {
+    pub fn new(
+        repo: Arc<dyn VisitRepository>,
+        restaurant_repo: Arc<dyn RestaurantRepository>,
+    ) -> Self {
+        Self { repo, restaurant_repo }
+    }
+
+    /// Log a new visit to a restaurant
+    pub async fn create_visit(
+        &self,
+        restaurant_id: RestaurantId,
+        request: CreateVisitRequest,
+        owner_id: UserId,
+    ) -> Result<VisitResponse> {
+        let command = request.to_command(restaurant_id, owner_id)?;
+        let visit = self.repo
+            .create(command)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Restaurant not found or access denied".into()))?;
+
+        Ok(VisitResponse::from(visit))
+    }
+
+    /// Get all visits to a restaurant, most recent first
+    pub async fn get_visits(
+        &self,
+        restaurant_id: RestaurantId,
+        owner_id: UserId,
+    ) -> Result<Vec<VisitResponse>> {
+        let restaurant = self.restaurant_repo
+            .find_by_id(restaurant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Restaurant not found".into()))?;
+
+        // Check ownership
+        if restaurant.owner_id != owner_id {
+            return Err(AppError::Authorization("Access denied".into()));
+        }
+
+        let visits = self.repo.find_by_restaurant(restaurant_id, owner_id).await?;
+        Ok(visits.into_iter().map(VisitResponse::from).collect())
+    }
+
+    /// Get a single visit
+    pub async fn get_visit(
+        &self,
+        restaurant_id: RestaurantId,
+        id: VisitId,
+        owner_id: UserId,
+    ) -> Result<VisitResponse> {
+        let visit = self.repo
+            .find_by_id(id, owner_id)
+            .await?
+            .filter(|visit| visit.restaurant_id == restaurant_id)
+            .ok_or_else(|| AppError::NotFound("Visit not found".into()))?;
+
+        Ok(VisitResponse::from(visit))
+    }
+
+    /// Update a visit
+    pub async fn update_visit(
+        &self,
+        restaurant_id: RestaurantId,
+        id: VisitId,
+        request: UpdateVisitRequest,
+        owner_id: UserId,
+    ) -> Result<VisitResponse> {
+        let command = request.to_command(id, restaurant_id, owner_id)?;
+        let visit = self.repo
+            .update(command)
+            .await?
+            .filter(|visit| visit.restaurant_id == restaurant_id)
+            .ok_or_else(|| AppError::NotFound("Visit not found or access denied".into()))?;
+
+        Ok(VisitResponse::from(visit))
+    }
+
+    /// Delete a visit
+    pub async fn delete_visit(
+        &self,
+        restaurant_id: RestaurantId,
+        id: VisitId,
+        owner_id: UserId,
+    ) -> Result<()> {
+        let deleted = self.repo.delete(id, restaurant_id, owner_id).await?;
+        if !deleted {
+            return Err(AppError::NotFound("Visit not found or access denied".into()));
+        }
+        Ok(())
+    }
+}
@@ -1,20 +1,32 @@
 use crate::domain::wishlist::{
-    WishlistItem, WishlistId, CreateWishlistItem, CreateWishlistRequest,
-    UpdateWishlistItem, UpdateWishlistRequest, WishlistItemResponse,
-    WishlistQuery, WishlistCountResponse, WishlistPriority
+    WishlistId, CreateWishlistRequest,
+    UpdateWishlistRequest, WishlistItemResponse,
+    WishlistQuery, WishlistCountResponse, WishlistPriority,
+    NearbyWishlistItemResponse
 };
+use crate::domain::geo::{round_distance, NearbyQuery, WithinQuery};
+use crate::domain::pagination::CursorPage;
+use crate::domain::social::{ActivityKind, RecordActivity};
 use crate::domain::user::UserId;
 use crate::repositories::wishlist_repository::WishlistRepository;
+use crate::services::social_service::SocialService;
+use crate::services::tag_service::TagService;
 use crate::error::{AppError, Result};
 use std::sync::Arc;
 
 pub struct WishlistService {
     repo: Arc<dyn WishlistRepository>,
+    tag_service: Arc<TagService>,
+    social_service: Arc<SocialService>,
 }
 
 impl WishlistService {
-    pub fn new(repo: Arc<dyn WishlistRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<dyn WishlistRepository>,
+        tag_service: Arc<TagService>,
+        social_service: Arc<SocialService>,
+    ) -> Self {
+        Self { repo, tag_service, social_service }
     }
 
     /// Create a new wishlist item
@@ -23,8 +35,20 @@ impl WishlistService {
         request: CreateWishlistRequest,
         owner_id: UserId,
     ) -> Result<WishlistItemResponse> {
-        let command = request.to_command(owner_id);
+        let command = request.to_command(owner_id)?;
+        self.tag_service.resolve_tags(owner_id, &command.tag_ids).await?;
         let item = self.repo.create(command).await?;
+        self.social_service
+            .record_activity(RecordActivity {
+                user_id: owner_id,
+                kind: ActivityKind::WishlistAdded,
+                restaurant_id: None,
+                wishlist_item_id: Some(item.id),
+                name: item.name.clone(),
+                city: item.city.clone(),
+                rating: None,
+            })
+            .await;
         Ok(WishlistItemResponse::from(item))
     }
 
@@ -33,12 +57,14 @@ impl WishlistService {
         &self,
         owner_id: UserId,
         query: WishlistQuery,
-    ) -> Result<Vec<WishlistItemResponse>> {
+    ) -> Result<CursorPage<WishlistItemResponse>> {
+        let tag_ids = query.tag_ids()?;
+        let page = query.page()?;
         let items = self.repo
-            .find_by_owner(owner_id, query.priority, query.limit)
+            .find_by_owner(owner_id, query.priority, &tag_ids, &page)
             .await?;
 
-        Ok(items.into_iter().map(WishlistItemResponse::from).collect())
+        Ok(items.map(WishlistItemResponse::from))
     }
 
     /// Get a single wishlist item by ID
@@ -67,7 +93,7 @@ impl WishlistService {
         request: UpdateWishlistRequest,
         owner_id: UserId,
     ) -> Result<WishlistItemResponse> {
-        let command = request.to_command(id, owner_id);
+        let command = request.to_command(id, owner_id)?;
         let item = self.repo
             .update(command)
             .await?
@@ -94,16 +120,56 @@ impl WishlistService {
         self.repo.get_count(owner_id).await
     }
 
+    /// Wishlist items within a radius of a point, closest first
+    pub async fn get_nearby(&self, owner_id: UserId, query: NearbyQuery) -> Result<Vec<NearbyWishlistItemResponse>> {
+        let search = query.to_command()?;
+        let items = self.repo.find_nearby(owner_id, &search).await?;
+
+        Ok(items
+            .into_iter()
+            .map(|(item, distance_km)| NearbyWishlistItemResponse {
+                item: WishlistItemResponse::from(item),
+                distance_km: round_distance(distance_km),
+            })
+            .collect())
+    }
+
+    /// Wishlist items inside a map bounding box
+    pub async fn get_within(&self, owner_id: UserId, query: WithinQuery) -> Result<Vec<WishlistItemResponse>> {
+        let (bounds, limit) = query.to_command()?;
+        let items = self.repo.find_within(owner_id, &bounds, limit).await?;
+
+        Ok(items.into_iter().map(WishlistItemResponse::from).collect())
+    }
+
     /// Promote wishlist item to restaurant
     pub async fn promote_to_restaurant(
         &self,
         id: WishlistId,
         owner_id: UserId,
     ) -> Result<()> {
-        let promoted = self.repo.promote_to_restaurant(id, owner_id).await?;
-        if !promoted {
-            return Err(AppError::NotFound("Wishlist item not found or access denied".into()));
-        }
+        let not_found = || AppError::NotFound("Wishlist item not found or access denied".into());
+        let item = self.repo
+            .find_by_id(id)
+            .await?
+            .filter(|item| item.owner_id == owner_id)
+            .ok_or_else(not_found)?;
+        let restaurant_id = self.repo
+            .promote_to_restaurant(id, owner_id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        self.social_service
+            .record_activity(RecordActivity {
+                user_id: owner_id,
+                kind: ActivityKind::RestaurantAdded,
+                restaurant_id: Some(restaurant_id),
+                wishlist_item_id: None,
+                name: item.name,
+                city: item.city,
+                rating: None,
+            })
+            .await;
         Ok(())
     }
 
@@ -112,11 +178,13 @@ impl WishlistService {
         &self,
         owner_id: UserId,
         priority: WishlistPriority,
-    ) -> Result<Vec<WishlistItemResponse>> {
+        query: WishlistQuery,
+    ) -> Result<CursorPage<WishlistItemResponse>> {
+        let page = query.page()?;
         let items = self.repo
-            .find_by_owner(owner_id, Some(priority), None)
+            .find_by_owner(owner_id, Some(priority), &[], &page)
             .await?;
 
-        Ok(items.into_iter().map(WishlistItemResponse::from).collect())
+        Ok(items.map(WishlistItemResponse::from))
     }
 }
\ No newline at end of file
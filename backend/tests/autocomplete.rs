@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use gourmestre::domain::autocomplete::{
+    merge_suggestions, AutocompleteProviderKind, AutocompleteSuggestion, ImportPlace, Place,
+    SuggestionOrigin, AUTOCOMPLETE_LIMIT, UNKNOWN_CITY,
+};
+use gourmestre::error::Result;
+use gourmestre::repositories::place_repository::PlaceRepository;
+use gourmestre::services::autocomplete_provider::{self, AutocompleteProvider, MockProvider};
+use std::sync::Arc;
+
+/// Imported places kept in memory, matched by name
+struct StaticPlaces(Vec<Place>);
+
+#[async_trait]
+impl PlaceRepository for StaticPlaces {
+    async fn search(&self, term: &str, limit: i64) -> Result<Vec<Place>> {
+        let term = term.to_lowercase();
+        Ok(self
+            .0
+            .iter()
+            .filter(|place| place.name.to_lowercase().contains(&term))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_many(&self, _source: &str, places: &[ImportPlace]) -> Result<u64> {
+        Ok(places.len() as u64)
+    }
+}
+
+fn places() -> Arc<dyn PlaceRepository> {
+    Arc::new(StaticPlaces(vec![Place {
+        source: "osm".to_string(),
+        source_id: "node/42".to_string(),
+        name: "Chez Paulette".to_string(),
+        city: None,
+        cuisine: Some("french;regional".to_string()),
+        latitude: Some(48.85),
+        longitude: Some(2.35),
+        address: None,
+        postcode: None,
+        opening_hours: None,
+        website: None,
+    }]))
+}
+
+/// One of the user's restaurants or wishlist items, as the repositories
+/// suggest them: no cuisines nor coordinates
+fn own(name: &str, city: &str, origin: SuggestionOrigin, id: i32) -> AutocompleteSuggestion {
+    AutocompleteSuggestion {
+        name: name.to_string(),
+        city: city.to_string(),
+        cuisines: Vec::new(),
+        latitude: None,
+        longitude: None,
+        address: None,
+        postcode: None,
+        opening_hours: None,
+        website: None,
+        source_id: None,
+        origin,
+        existing_id: Some(id),
+    }
+}
+
+async fn mock_search(term: &str) -> Vec<AutocompleteSuggestion> {
+    MockProvider::default()
+        .search(term, AUTOCOMPLETE_LIMIT)
+        .await
+        .expect("mock search")
+}
+
+#[test]
+fn builds_the_configured_provider() {
+    let cases = [
+        (AutocompleteProviderKind::OpenDataSoft, "opendatasoft"),
+        (AutocompleteProviderKind::Local, "local"),
+        (AutocompleteProviderKind::Mock, "mock"),
+    ];
+
+    for (kind, name) in cases {
+        let provider = autocomplete_provider::build(kind, "http://localhost".to_string(), places());
+        assert_eq!(provider.name(), name);
+    }
+}
+
+#[tokio::test]
+async fn mock_provider_matches_name_or_city_ignoring_case() {
+    let names: Vec<String> = mock_search("LYON").await.into_iter().map(|s| s.name).collect();
+    assert_eq!(names, ["Pizzeria Napoli", "Le Bouchon Lyonnais"]);
+
+    let limited = MockProvider::default().search("pizz", 1).await.expect("mock search");
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn local_provider_searches_imported_places() {
+    let provider = autocomplete_provider::build(
+        AutocompleteProviderKind::Local,
+        String::new(),
+        places(),
+    );
+
+    let suggestions = provider.search("paulette", AUTOCOMPLETE_LIMIT).await.expect("local search");
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].city, UNKNOWN_CITY);
+    assert_eq!(suggestions[0].cuisines, ["french", "regional"]);
+    assert_eq!(suggestions[0].source_id.as_deref(), Some("osm:node/42"));
+}
+
+#[tokio::test]
+async fn merge_keeps_the_own_entry_completed_by_the_provider() {
+    let external = mock_search("pizza mario").await;
+    let own = vec![own("Pizza Mario", "Paris", SuggestionOrigin::Restaurant, 7)];
+
+    let merged = merge_suggestions("pizza mario", own, external, AUTOCOMPLETE_LIMIT);
+
+    assert_eq!(merged.len(), 1);
+    let kept = &merged[0];
+    assert_eq!(kept.origin, SuggestionOrigin::Restaurant);
+    assert_eq!(kept.existing_id, Some(7));
+    assert_eq!(kept.cuisines, ["pizza", "italian"]);
+    assert_eq!(kept.latitude, Some(48.8566));
+    assert_eq!(kept.source_id.as_deref(), Some("mock:1"));
+}
+
+#[tokio::test]
+async fn merge_recognizes_renamed_entries_by_source_id() {
+    let external = mock_search("pizz").await;
+    let mut renamed = own("Chez Mario", "Paris", SuggestionOrigin::Wishlist, 3);
+    renamed.source_id = Some("mock:1".to_string());
+
+    let merged = merge_suggestions("pizz", vec![renamed], external, AUTOCOMPLETE_LIMIT);
+
+    let names: Vec<&str> = merged.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Pizzeria Napoli", "Chez Mario"]);
+    assert_eq!(merged[1].origin, SuggestionOrigin::Wishlist);
+}
+
+#[tokio::test]
+async fn merge_puts_own_entries_first_among_equal_matches_and_truncates() {
+    let external = mock_search("pizz").await;
+    let own = vec![own("Pizzeria Roma", "Nice", SuggestionOrigin::Wishlist, 5)];
+
+    let merged = merge_suggestions("pizz", own, external, 2);
+
+    let names: Vec<&str> = merged.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Pizzeria Roma", "Pizza Mario"]);
+}
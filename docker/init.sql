@@ -128,44 +128,45 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+-- Security events recorded by the application, as created by migration 014
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'security_event_type') THEN
+        CREATE TYPE security_event_type AS ENUM (
+            'login',
+            'login_failed',
+            'account_locked',
+            'logout',
+            'password_changed',
+            'password_reset',
+            'session_revoked',
+            'username_changed',
+            'email_changed',
+            'two_factor_enabled',
+            'two_factor_disabled',
+            'api_token_created',
+            'api_token_revoked',
+            'data_deleted',
+            'account_deleted'
+        );
+    END IF;
+END $$;
+
+-- No foreign key on user_id: events outlive deleted accounts until the
+-- retention job removes them
 CREATE TABLE IF NOT EXISTS audit_log (
     id SERIAL PRIMARY KEY,
-    table_name TEXT NOT NULL,
-    operation TEXT NOT NULL,
     user_id INTEGER,
-    old_values JSONB,
-    new_values JSONB,
-    timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    event_type security_event_type NOT NULL,
+    details JSONB,
     ip_address INET,
-    user_agent TEXT
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
-CREATE OR REPLACE FUNCTION audit_trigger_function() RETURNS TRIGGER AS $$
-BEGIN
-    IF TG_OP = 'DELETE' THEN
-        INSERT INTO audit_log (table_name, operation, user_id, old_values)
-        VALUES (TG_TABLE_NAME, TG_OP, OLD.id, row_to_json(OLD)::jsonb);
-        RETURN OLD;
-    ELSIF TG_OP = 'UPDATE' THEN
-        INSERT INTO audit_log (table_name, operation, user_id, old_values, new_values)
-        VALUES (TG_TABLE_NAME, TG_OP, NEW.id, row_to_json(OLD)::jsonb, row_to_json(NEW)::jsonb);
-        RETURN NEW;
-    ELSIF TG_OP = 'INSERT' THEN
-        INSERT INTO audit_log (table_name, operation, user_id, new_values)
-        VALUES (TG_TABLE_NAME, TG_OP, NEW.id, row_to_json(NEW)::jsonb);
-        RETURN NEW;
-    END IF;
-    RETURN NULL;
-END;
-$$ LANGUAGE plpgsql;
-
-CREATE TRIGGER users_audit_trigger
-    AFTER INSERT OR UPDATE OR DELETE ON users
-    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
-
-CREATE TRIGGER sessions_audit_trigger
-    AFTER INSERT OR DELETE ON sessions
-    FOR EACH ROW EXECUTE FUNCTION audit_trigger_function();
+CREATE INDEX IF NOT EXISTS idx_audit_log_user_id ON audit_log(user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
 
-GRANT SELECT, INSERT ON audit_log TO gourmestre_app;
+-- DELETE for the retention job
+GRANT SELECT, INSERT, DELETE ON audit_log TO gourmestre_app;
 GRANT USAGE, SELECT ON SEQUENCE audit_log_id_seq TO gourmestre_app;
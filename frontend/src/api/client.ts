@@ -38,6 +38,7 @@ export interface User {
   failed_login_attempts: number;
   last_login: string | null;
   account_locked_until: string | null;
+  is_private?: boolean;
 }
 
 export interface Restaurant {
@@ -46,7 +47,13 @@ export interface Restaurant {
   city: string;
   description?: string;
   rating: number;
+  // Mean rating of the rated visits, shown instead of rating when present
+  visit_rating?: number | null;
   is_favorite: boolean;
+  address?: string | null;
+  latitude?: number | null;
+  longitude?: number | null;
+  source_id?: string | null;
   created_at: string;
   updated_at: string;
 }
@@ -57,6 +64,7 @@ export interface RestaurantInput {
   description?: string;
   rating: number;
   is_favorite: boolean;
+  source_id?: string | null;
 }
 
 export interface WishlistItem {
@@ -65,6 +73,10 @@ export interface WishlistItem {
   city: string;
   notes?: string;
   priority: "low" | "medium" | "high";
+  address?: string | null;
+  latitude?: number | null;
+  longitude?: number | null;
+  source_id?: string | null;
   created_at: string;
   updated_at: string;
 }
@@ -74,25 +86,84 @@ export interface WishlistInput {
   city: string;
   notes?: string;
   priority: "low" | "medium" | "high";
+  source_id?: string | null;
+}
+
+export interface CursorPage<T> {
+  items: T[];
+  next_cursor: string | null;
+  total: number;
+}
+
+// Follow next_cursor until the listing is exhausted
+async function fetchAllPages<T>(
+  url: string,
+  params: Record<string, unknown> = {},
+): Promise<T[]> {
+  const items: T[] = [];
+  let cursor: string | null = null;
+  do {
+    const res: { data: CursorPage<T> } = await apiClient.get<CursorPage<T>>(
+      url,
+      { params: { ...params, limit: 100, cursor: cursor ?? undefined } },
+    );
+    items.push(...res.data.items);
+    cursor = res.data.next_cursor;
+  } while (cursor);
+  return items;
 }
 
 export interface Stats {
   total_restaurants: number;
   total_favorites: number;
-  average_rating: string;
+  favorite_ratio: number;
+  total_visits: number;
+  average_rating: number | null;
+  rating_histogram: { rating: number; count: number }[];
+  unrated_restaurants: number;
+  restaurants_per_city: { city: string; count: number }[];
+  top_cities: { city: string; count: number }[];
+  added_per_month: { month: string; count: number }[];
+  wishlist_conversion: {
+    promoted: number;
+    pending: number;
+    conversion_rate: number;
+  };
 }
 
+// Answer to a login whose account has two-factor authentication
+export interface LoginChallenge {
+  message: string;
+  two_factor_required: true;
+  challenge: string;
+}
+
+export const isLoginChallenge = (data: unknown): data is LoginChallenge =>
+  typeof data === "object" &&
+  data !== null &&
+  (data as LoginChallenge).two_factor_required === true;
+
 // Auth API
 export const authApi = {
   register: (credentials: {
     username: string;
+    email: string;
     password: string;
   }): Promise<User> =>
     apiClient.post("/auth/register", credentials).then((res) => res.data),
 
-  login: (credentials: { username: string; password: string }): Promise<User> =>
+  login: (credentials: {
+    username: string;
+    password: string;
+  }): Promise<User | LoginChallenge> =>
     apiClient.post("/auth/login", credentials).then((res) => res.data),
 
+  loginTwoFactor: (request: {
+    challenge: string;
+    code: string;
+  }): Promise<User> =>
+    apiClient.post("/auth/login/2fa", request).then((res) => res.data),
+
   logout: (): Promise<void> =>
     apiClient.post("/auth/logout").then((res) => res.data),
 
@@ -104,7 +175,15 @@ export const authApi = {
 
 export const restaurantApi = {
   getRestaurants: (): Promise<Restaurant[]> =>
-    apiClient.get("/restaurants").then((res) => res.data),
+    fetchAllPages<Restaurant>("/restaurants"),
+
+  getRestaurantPage: (params: {
+    sort?: "name" | "city" | "rating" | "created_at" | "updated_at";
+    direction?: "asc" | "desc";
+    limit?: number;
+    cursor?: string;
+  }): Promise<CursorPage<Restaurant>> =>
+    apiClient.get("/restaurants", { params }).then((res) => res.data),
 
   createRestaurant: (restaurant: RestaurantInput): Promise<Restaurant> =>
     apiClient.post("/restaurants", restaurant).then((res) => res.data),
@@ -129,18 +208,20 @@ export const restaurantApi = {
 
   // Helper methods for filtering
   getFavorites: (): Promise<Restaurant[]> =>
-    apiClient
-      .get("/restaurants")
-      .then((res) => res.data.filter((r: Restaurant) => r.is_favorite)),
+    fetchAllPages<Restaurant>("/restaurants", { favorites: true }),
 
   getRecent: (limit: number = 20): Promise<Restaurant[]> =>
-    apiClient.get("/restaurants").then((res) => res.data.slice(0, limit)),
+    apiClient
+      .get<CursorPage<Restaurant>>("/restaurants", {
+        params: { sort: "created_at", limit },
+      })
+      .then((res) => res.data.items),
 };
 
 // Wishlist API
 export const wishlistApi = {
   getWishlist: (): Promise<WishlistItem[]> =>
-    apiClient.get("/wishlist").then((res) => res.data),
+    fetchAllPages<WishlistItem>("/wishlist"),
 
   createWishlistItem: (item: WishlistInput): Promise<WishlistItem> =>
     apiClient.post("/wishlist", item).then((res) => res.data),
@@ -151,7 +232,7 @@ export const wishlistApi = {
   getWishlistByPriority: (
     priority: "low" | "medium" | "high",
   ): Promise<WishlistItem[]> =>
-    apiClient.get(`/wishlist/priority/${priority}`).then((res) => res.data),
+    fetchAllPages<WishlistItem>(`/wishlist/priority/${priority}`),
 
   getWishlistItem: (id: number): Promise<WishlistItem> =>
     apiClient.get(`/wishlist/${id}`).then((res) => res.data),
@@ -170,9 +251,21 @@ export const wishlistApi = {
 };
 
 // Autocomplete types
+export type SuggestionOrigin = "restaurant" | "wishlist" | "external";
+
 export interface AutocompleteSuggestion {
   name: string;
   city: string;
+  latitude?: number | null;
+  longitude?: number | null;
+  cuisines: string[];
+  address?: string | null;
+  postcode?: string | null;
+  opening_hours?: string | null;
+  website?: string | null;
+  source_id?: string | null;
+  origin: SuggestionOrigin;
+  existing_id?: number | null;
 }
 
 export interface AutocompleteResponse {
@@ -190,6 +190,13 @@ const AutocompleteInput: React.FC<AutocompleteInputProps> = ({
                 className="text-sm text-gray-500"
                 style={{ color: "rgb(var(--color-secondary))" }}
               >
+                {suggestion.origin !== "external" && (
+                  <span className="mr-2 text-xs font-medium">
+                    {suggestion.origin === "restaurant"
+                      ? "Déjà visité"
+                      : "Dans la wishlist"}
+                  </span>
+                )}
                 {suggestion.city}
               </span>
             </div>
@@ -17,7 +17,7 @@ import { DashboardLayout } from "../features/dashboard/components";
 interface Stats {
   total_restaurants: number;
   total_favorites: number;
-  average_rating: string;
+  average_rating: number | null;
 }
 
 const Dashboard: React.FC = () => {
@@ -27,7 +27,7 @@ const Dashboard: React.FC = () => {
   const [stats, setStats] = useState<Stats>({
     total_restaurants: 0,
     total_favorites: 0,
-    average_rating: "-",
+    average_rating: null,
   });
   const [wishlistCount, setWishlistCount] = useState(0);
   const [_restaurants, setRestaurants] = useState<Restaurant[]>([]);
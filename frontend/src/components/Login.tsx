@@ -9,11 +9,14 @@ interface LoginProps {
 
 const Login: React.FC<LoginProps> = ({ showWelcomeMessage = false }) => {
   const navigate = useNavigate();
-  const { login } = useAuth();
+  const { login, loginTwoFactor } = useAuth();
   const [formData, setFormData] = useState({
     username: "",
     password: "",
   });
+  // Set once the password is right on an account with two-factor authentication
+  const [challenge, setChallenge] = useState<string | null>(null);
+  const [code, setCode] = useState("");
   const [message, setMessage] = useState("");
   const [loading, setLoading] = useState(false);
 
@@ -31,7 +34,15 @@ const Login: React.FC<LoginProps> = ({ showWelcomeMessage = false }) => {
     setMessage("");
 
     try {
-      await login(formData);
+      if (challenge) {
+        await loginTwoFactor(challenge, code);
+      } else {
+        const pending = await login(formData);
+        if (pending) {
+          setChallenge(pending.challenge);
+          return;
+        }
+      }
 
       // Successful login - redirect to dashboard
       navigate("/dashboard");
@@ -114,31 +125,56 @@ const Login: React.FC<LoginProps> = ({ showWelcomeMessage = false }) => {
         )}
 
         <form onSubmit={handleSubmit} className="space-y-6">
-          <div className="form-group">
-            <input
-              type="text"
-              name="username"
-              value={formData.username}
-              onChange={handleInputChange}
-              placeholder="Nom d'utilisateur"
-              className="form-input"
-              required
-            />
-            <label className="form-label">Nom d'utilisateur</label>
-          </div>
+          {challenge ? (
+            <>
+              <p className="text-body-sm">
+                Saisissez le code de votre application d'authentification, ou
+                un code de récupération.
+              </p>
+              <div className="form-group">
+                <input
+                  type="text"
+                  name="code"
+                  value={code}
+                  onChange={(e) => setCode(e.target.value)}
+                  placeholder="Code de vérification"
+                  className="form-input"
+                  autoComplete="one-time-code"
+                  autoFocus
+                  required
+                />
+                <label className="form-label">Code de vérification</label>
+              </div>
+            </>
+          ) : (
+            <>
+              <div className="form-group">
+                <input
+                  type="text"
+                  name="username"
+                  value={formData.username}
+                  onChange={handleInputChange}
+                  placeholder="Nom d'utilisateur"
+                  className="form-input"
+                  required
+                />
+                <label className="form-label">Nom d'utilisateur</label>
+              </div>
 
-          <div className="form-group">
-            <input
-              type="password"
-              name="password"
-              value={formData.password}
-              onChange={handleInputChange}
-              placeholder="Mot de passe"
-              className="form-input"
-              required
-            />
-            <label className="form-label">Mot de passe</label>
-          </div>
+              <div className="form-group">
+                <input
+                  type="password"
+                  name="password"
+                  value={formData.password}
+                  onChange={handleInputChange}
+                  placeholder="Mot de passe"
+                  className="form-input"
+                  required
+                />
+                <label className="form-label">Mot de passe</label>
+              </div>
+            </>
+          )}
 
           <button
             type="submit"
@@ -158,8 +194,28 @@ const Login: React.FC<LoginProps> = ({ showWelcomeMessage = false }) => {
               <polyline points="10,17 15,12 10,7" />
               <line x1="15" y1="12" x2="3" y2="12" />
             </svg>
-            {loading ? "Connexion..." : "Se connecter"}
+            {loading ? "Connexion..." : challenge ? "Vérifier" : "Se connecter"}
           </button>
+
+          {challenge && (
+            <button
+              type="button"
+              onClick={() => {
+                setChallenge(null);
+                setCode("");
+                setMessage("");
+              }}
+              className="text-link"
+              style={{
+                background: "none",
+                border: "none",
+                cursor: "pointer",
+                width: "100%",
+              }}
+            >
+              Revenir à la connexion
+            </button>
+          )}
         </form>
 
         {message && (
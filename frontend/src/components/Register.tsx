@@ -11,6 +11,7 @@ const Register: React.FC<RegisterProps> = ({ onRegistrationSuccess }) => {
   const navigate = useNavigate();
   const [formData, setFormData] = useState({
     username: "",
+    email: "",
     password: "",
   });
   const [message, setMessage] = useState("");
@@ -92,6 +93,19 @@ const Register: React.FC<RegisterProps> = ({ onRegistrationSuccess }) => {
             <label className="form-label">Nom d'utilisateur</label>
           </div>
 
+          <div className="form-group">
+            <input
+              type="email"
+              name="email"
+              value={formData.email}
+              onChange={handleInputChange}
+              placeholder="Adresse e-mail"
+              className="form-input"
+              required
+            />
+            <label className="form-label">Adresse e-mail</label>
+          </div>
+
           <div className="form-group">
             <input
               type="password"
@@ -1,11 +1,21 @@
 import React, { createContext, useContext, useEffect, useState } from "react";
-import { authApi, type User } from "../api/client";
+import {
+  authApi,
+  isLoginChallenge,
+  type LoginChallenge,
+  type User,
+} from "../api/client";
 
 interface AuthContextType {
   user: User | null;
   loading: boolean;
   authenticated: boolean;
-  login: (credentials: { username: string; password: string }) => Promise<void>;
+  // Resolves with a challenge when the account needs a two-factor code
+  login: (credentials: {
+    username: string;
+    password: string;
+  }) => Promise<LoginChallenge | null>;
+  loginTwoFactor: (challenge: string, code: string) => Promise<void>;
   logout: () => Promise<void>;
   checkAuth: () => Promise<void>;
 }
@@ -52,9 +62,24 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
 
   const login = async (credentials: { username: string; password: string }) => {
     try {
-      await authApi.login(credentials);
+      const result = await authApi.login(credentials);
+      if (isLoginChallenge(result)) {
+        return result;
+      }
       // Re-check auth status after login to get user data
       await checkAuth();
+      return null;
+    } catch (error) {
+      setAuthenticated(false);
+      setUser(null);
+      throw error;
+    }
+  };
+
+  const loginTwoFactor = async (challenge: string, code: string) => {
+    try {
+      await authApi.loginTwoFactor({ challenge, code });
+      await checkAuth();
     } catch (error) {
       setAuthenticated(false);
       setUser(null);
@@ -82,6 +107,7 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
     loading,
     authenticated,
     login,
+    loginTwoFactor,
     logout,
     checkAuth,
   };
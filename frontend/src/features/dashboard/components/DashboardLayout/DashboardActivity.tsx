@@ -5,6 +5,7 @@ interface Restaurant {
   name: string;
   city: string;
   rating: number;
+  visit_rating?: number | null;
 }
 
 interface DashboardActivityProps {
@@ -18,7 +19,9 @@ const DashboardActivity: React.FC<DashboardActivityProps> = ({
   recent,
   loading,
 }) => {
-  const renderStars = (rating: number) => {
+  // The mean of the visits wins over the rating given by hand
+  const renderStars = (restaurant: Restaurant) => {
+    const rating = Math.round(restaurant.visit_rating ?? restaurant.rating);
     return (
       <div className="star-rating">
         {[1, 2, 3, 4, 5].map((star) => (
@@ -80,7 +83,7 @@ const DashboardActivity: React.FC<DashboardActivityProps> = ({
                   {favorites[0].name}
                 </h4>
                 <p className="text-body-sm mb-2">{favorites[0].city}</p>
-                {renderStars(favorites[0].rating)}
+                {renderStars(favorites[0])}
               </div>
             ) : (
               <p
@@ -135,7 +138,7 @@ const DashboardActivity: React.FC<DashboardActivityProps> = ({
                       </p>
                       <p className="text-caption">{restaurant.city}</p>
                     </div>
-                    {renderStars(restaurant.rating)}
+                    {renderStars(restaurant)}
                   </div>
                 ))}
               </div>
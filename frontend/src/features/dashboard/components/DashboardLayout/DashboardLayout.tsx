@@ -6,7 +6,7 @@ import { useResponsive, MobileMenu } from "../../../../shared";
 interface Stats {
   total_restaurants: number;
   total_favorites: number;
-  average_rating: string;
+  average_rating: number | null;
 }
 
 interface Restaurant {
@@ -14,6 +14,7 @@ interface Restaurant {
   name: string;
   city: string;
   rating: number;
+  visit_rating?: number | null;
 }
 
 interface DashboardLayoutProps {
@@ -3,7 +3,7 @@ import React from "react";
 interface Stats {
   total_restaurants: number;
   total_favorites: number;
-  average_rating: string;
+  average_rating: number | null;
 }
 
 interface DashboardSidebarProps {
@@ -142,7 +142,7 @@ const DashboardSidebar: React.FC<DashboardSidebarProps> = ({
                 className="text-body font-semibold"
                 style={{ color: "rgb(var(--color-accent))" }}
               >
-                {stats.average_rating}
+                {stats.average_rating?.toFixed(1) ?? "-"}
               </span>
             </div>
           </div>
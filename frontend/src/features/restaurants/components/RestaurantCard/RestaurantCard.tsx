@@ -7,6 +7,7 @@ interface Restaurant {
   city: string;
   description?: string;
   rating: number;
+  visit_rating?: number | null;
   is_favorite: boolean;
   created_at: string;
   updated_at: string;
@@ -24,6 +25,9 @@ const RestaurantCard: React.FC<RestaurantCardProps> = memo(
   ({ restaurant, onEdit, onDelete, className = "", showActions = false }) => {
     const { isMobile } = useResponsive();
 
+    // The mean of the visits wins over the rating given by hand
+    const rating = Math.round(restaurant.visit_rating ?? restaurant.rating);
+
     // Memorize star rendering to avoid re-computation
     const stars = useMemo(
       () => (
@@ -31,7 +35,7 @@ const RestaurantCard: React.FC<RestaurantCardProps> = memo(
           {[1, 2, 3, 4, 5].map((star) => (
             <svg
               key={star}
-              className={`star ${star <= restaurant.rating ? "active" : ""}`}
+              className={`star ${star <= rating ? "active" : ""}`}
               data-rating={star}
               fill="currentColor"
               viewBox="0 0 24 24"
@@ -42,7 +46,7 @@ const RestaurantCard: React.FC<RestaurantCardProps> = memo(
           ))}
         </div>
       ),
-      [restaurant.rating],
+      [rating],
     );
 
     const formattedDate = useMemo(
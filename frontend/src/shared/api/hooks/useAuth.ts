@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
-import { authApi } from '../../../api/client';
+import { authApi, isLoginChallenge } from '../../../api/client';
 
 // Types pour l'authentification
 export interface LoginRequest {
@@ -9,6 +9,7 @@ export interface LoginRequest {
 
 export interface RegisterRequest {
   username: string;
+  email: string;
   password: string;
 }
 
@@ -34,9 +35,13 @@ export const useLogin = () => {
 
   return useMutation({
     mutationFn: (credentials: LoginRequest) => authApi.login(credentials),
-    onSuccess: (user) => {
+    onSuccess: (result) => {
+      // Un compte avec double authentification attend encore son code
+      if (isLoginChallenge(result)) {
+        return;
+      }
       // Mettre à jour le cache utilisateur
-      queryClient.setQueryData(authKeys.user(), user);
+      queryClient.setQueryData(authKeys.user(), result);
     },
     onError: (error) => {
       console.error('Erreur lors de la connexion:', error);